@@ -0,0 +1,9 @@
+-- Add migration script here
+ALTER TABLE articles
+    ADD COLUMN IF NOT EXISTS updated_at TIMESTAMP;
+
+UPDATE articles SET updated_at = created_at WHERE updated_at IS NULL;
+
+ALTER TABLE articles
+    ALTER COLUMN updated_at SET NOT NULL,
+    ALTER COLUMN updated_at SET DEFAULT NOW();
@@ -0,0 +1,25 @@
+-- Add migration script here
+ALTER TABLE articles
+    ADD COLUMN IF NOT EXISTS version INTEGER NOT NULL DEFAULT 1;
+
+CREATE TABLE IF NOT EXISTS article_revisions (
+    article_id UUID NOT NULL,
+    version INTEGER NOT NULL,
+    title VARCHAR(100) NOT NULL,
+    text TEXT NOT NULL,
+    tags TEXT [],
+    editor_id UUID NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (article_id, version),
+    CONSTRAINT fk_revision_article
+        FOREIGN KEY (article_id)
+            REFERENCES articles(id)
+            ON DELETE CASCADE,
+    CONSTRAINT fk_revision_editor
+        FOREIGN KEY (editor_id)
+            REFERENCES users(id)
+);
+
+INSERT INTO article_revisions (article_id, version, title, text, tags, editor_id, created_at)
+SELECT id, version, title, text, tags, author_id, updated_at FROM articles
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,12 @@
+-- Add migration script here
+CREATE TYPE article_status AS ENUM ('draft', 'scheduled', 'published', 'archived');
+
+ALTER TABLE articles
+    ADD COLUMN IF NOT EXISTS status article_status NOT NULL DEFAULT 'published',
+    ADD COLUMN IF NOT EXISTS published_at TIMESTAMP;
+
+UPDATE articles SET published_at = created_at WHERE status = 'published';
+
+CREATE INDEX IF NOT EXISTS articles_scheduled_idx
+    ON articles (published_at)
+    WHERE status = 'scheduled';
@@ -0,0 +1,48 @@
+-- Add migration script here
+ALTER TABLE articles
+    ADD COLUMN IF NOT EXISTS slug TEXT;
+
+WITH bases AS (
+    SELECT
+        id,
+        author_id,
+        COALESCE(
+            NULLIF(TRIM(BOTH '-' FROM LOWER(REGEXP_REPLACE(title, '[^a-zA-Z0-9]+', '-', 'g'))), ''),
+            'article'
+        ) AS base,
+        created_at
+    FROM articles
+),
+numbered AS (
+    SELECT
+        id,
+        base,
+        ROW_NUMBER() OVER (PARTITION BY author_id, base ORDER BY created_at) AS n
+    FROM bases
+)
+UPDATE articles a
+SET slug = CASE WHEN numbered.n = 1 THEN numbered.base ELSE numbered.base || '-' || numbered.n END
+FROM numbered
+WHERE numbered.id = a.id AND a.slug IS NULL;
+
+ALTER TABLE articles
+    ALTER COLUMN slug SET NOT NULL,
+    ADD CONSTRAINT articles_author_slug_key UNIQUE (author_id, slug),
+    DROP CONSTRAINT IF EXISTS articles_title_key;
+
+-- Current and previous slugs; previous ones redirect to the current slug
+CREATE TABLE IF NOT EXISTS article_slugs (
+    author_id UUID NOT NULL,
+    slug TEXT NOT NULL,
+    article_id UUID NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (author_id, slug),
+    CONSTRAINT fk_slug_article
+        FOREIGN KEY (article_id)
+            REFERENCES articles(id)
+            ON DELETE CASCADE
+);
+
+INSERT INTO article_slugs (author_id, slug, article_id)
+SELECT author_id, slug, id FROM articles
+ON CONFLICT DO NOTHING;
@@ -0,0 +1,17 @@
+-- Add migration script here
+-- array_to_string is only STABLE, generated columns need an IMMUTABLE expression
+CREATE OR REPLACE FUNCTION article_tags_to_text(tags TEXT []) RETURNS TEXT
+    LANGUAGE SQL IMMUTABLE PARALLEL SAFE
+    AS $$ SELECT COALESCE(array_to_string(tags, ' '), '') $$;
+
+ALTER TABLE articles
+    ADD COLUMN IF NOT EXISTS search_language REGCONFIG NOT NULL DEFAULT 'english';
+
+ALTER TABLE articles
+    ADD COLUMN IF NOT EXISTS search TSVECTOR GENERATED ALWAYS AS (
+        setweight(to_tsvector(search_language, title), 'A') ||
+        setweight(to_tsvector(search_language, article_tags_to_text(tags)), 'B') ||
+        setweight(to_tsvector(search_language, text), 'C')
+    ) STORED;
+
+CREATE INDEX IF NOT EXISTS articles_search_idx ON articles USING GIN (search);
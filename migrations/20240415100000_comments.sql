@@ -0,0 +1,28 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS comments (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    article_id UUID NOT NULL,
+    author_id UUID NOT NULL,
+    parent_id UUID,
+    text TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    -- Deleted comments stay in place so that replies keep their parent
+    deleted_at TIMESTAMP,
+    CONSTRAINT fk_comment_article
+        FOREIGN KEY (article_id)
+            REFERENCES articles(id)
+            ON DELETE CASCADE,
+    CONSTRAINT fk_comment_author
+        FOREIGN KEY (author_id)
+            REFERENCES users(id),
+    CONSTRAINT fk_comment_parent
+        FOREIGN KEY (parent_id)
+            REFERENCES comments(id)
+            ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS comments_article_idx ON comments (article_id, created_at, id);
+
+ALTER TABLE articles
+    ADD COLUMN IF NOT EXISTS comment_count INTEGER NOT NULL DEFAULT 0;
@@ -0,0 +1,24 @@
+-- Add migration script here
+CREATE TYPE reaction_kind AS ENUM ('like', 'love', 'laugh', 'wow', 'sad', 'angry');
+
+CREATE TABLE IF NOT EXISTS article_reactions (
+    article_id UUID NOT NULL,
+    user_id UUID NOT NULL,
+    kind reaction_kind NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (article_id, user_id, kind),
+    CONSTRAINT fk_reaction_article
+        FOREIGN KEY (article_id)
+            REFERENCES articles(id)
+            ON DELETE CASCADE,
+    CONSTRAINT fk_reaction_user
+        FOREIGN KEY (user_id)
+            REFERENCES users(id)
+            ON DELETE CASCADE
+);
+
+-- Kept alongside the reactions so that listings can be ordered by likes
+ALTER TABLE articles
+    ADD COLUMN IF NOT EXISTS like_count INTEGER NOT NULL DEFAULT 0;
+
+CREATE INDEX IF NOT EXISTS articles_like_count_idx ON articles (like_count, id);
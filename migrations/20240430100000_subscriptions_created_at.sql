@@ -0,0 +1,6 @@
+-- Add migration script here
+ALTER TABLE subscriptions
+    ADD COLUMN IF NOT EXISTS created_at TIMESTAMP NOT NULL DEFAULT NOW();
+
+CREATE INDEX IF NOT EXISTS subscriptions_author_idx
+    ON subscriptions (author_id, created_at, subscriber_id);
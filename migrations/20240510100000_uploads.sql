@@ -0,0 +1,29 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS uploads (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    owner_id UUID NOT NULL,
+    content_type TEXT NOT NULL,
+    size BIGINT NOT NULL,
+    width INTEGER NOT NULL,
+    height INTEGER NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT fk_upload_owner
+        FOREIGN KEY (owner_id)
+            REFERENCES users(id)
+            ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS article_assets (
+    article_id UUID NOT NULL,
+    upload_id UUID NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    PRIMARY KEY (article_id, upload_id),
+    CONSTRAINT fk_asset_article
+        FOREIGN KEY (article_id)
+            REFERENCES articles(id)
+            ON DELETE CASCADE,
+    CONSTRAINT fk_asset_upload
+        FOREIGN KEY (upload_id)
+            REFERENCES uploads(id)
+            ON DELETE CASCADE
+);
@@ -0,0 +1,6 @@
+-- Add migration script here
+-- html stays NULL until the article is rendered, existing rows are backfilled on startup
+ALTER TABLE articles
+    ADD COLUMN IF NOT EXISTS html TEXT,
+    ADD COLUMN IF NOT EXISTS word_count INTEGER NOT NULL DEFAULT 0,
+    ADD COLUMN IF NOT EXISTS reading_time_minutes INTEGER NOT NULL DEFAULT 0;
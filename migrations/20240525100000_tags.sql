@@ -0,0 +1,47 @@
+-- Add migration script here
+CREATE TABLE IF NOT EXISTS tags (
+    id UUID PRIMARY KEY DEFAULT uuid_generate_v4(),
+    name TEXT NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT NOW(),
+    CONSTRAINT tags_name_key UNIQUE (name)
+);
+
+CREATE INDEX IF NOT EXISTS tags_name_prefix_idx ON tags (name text_pattern_ops);
+
+CREATE TABLE IF NOT EXISTS article_tags (
+    article_id UUID NOT NULL REFERENCES articles (id) ON DELETE CASCADE,
+    tag_id UUID NOT NULL REFERENCES tags (id) ON DELETE CASCADE,
+    position INTEGER NOT NULL,
+    PRIMARY KEY (article_id, tag_id)
+);
+
+CREATE INDEX IF NOT EXISTS article_tags_tag_id_idx ON article_tags (tag_id);
+
+-- Same normalization as utils::tag::normalize_tag: lowercase, whitespace runs collapsed and trimmed
+CREATE TEMPORARY TABLE normalized_tags AS
+SELECT a.id AS article_id, lower(btrim(regexp_replace(t.name, '\s+', ' ', 'g'))) AS name, t.position
+FROM articles a, unnest(a.tags) WITH ORDINALITY AS t(name, position);
+
+INSERT INTO tags (name)
+SELECT DISTINCT name FROM normalized_tags WHERE name <> ''
+ON CONFLICT DO NOTHING;
+
+INSERT INTO article_tags (article_id, tag_id, position)
+SELECT n.article_id, t.id, MIN(n.position)
+FROM normalized_tags n
+JOIN tags t ON t.name = n.name
+GROUP BY n.article_id, t.id
+ON CONFLICT DO NOTHING;
+
+DROP TABLE normalized_tags;
+
+-- article_tags is the source of truth, articles.tags stays as a copy in tag order for the search
+-- vector, which cannot read other tables
+UPDATE articles a SET tags = ARRAY(
+    SELECT t.name
+    FROM article_tags at
+    JOIN tags t ON t.id = at.tag_id
+    WHERE at.article_id = a.id
+    ORDER BY at.position
+)
+WHERE a.tags IS NOT NULL;
@@ -0,0 +1,8 @@
+-- Add migration script here
+-- Slugs backfilled by the slugs migration only kept ASCII letters and had no length cap,
+-- flagged rows are slugified again on startup and keep their old slug as a redirect
+ALTER TABLE articles
+    ADD COLUMN IF NOT EXISTS legacy_slug BOOLEAN NOT NULL DEFAULT TRUE;
+
+ALTER TABLE articles
+    ALTER COLUMN legacy_slug SET DEFAULT FALSE;
@@ -0,0 +1,9 @@
+-- Add migration script here
+-- Local registration never confirms the email, only emails asserted by an identity
+-- provider or a SCIM client are trusted for linking external logins
+ALTER TABLE users
+    ADD COLUMN IF NOT EXISTS email_verified BOOLEAN NOT NULL DEFAULT FALSE;
+
+UPDATE users
+SET email_verified = TRUE
+WHERE external_id IS NOT NULL OR id IN (SELECT user_id FROM identities);
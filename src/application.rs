@@ -1,4 +1,10 @@
-use axum::{extract::MatchedPath, http::Request, Router};
+use std::{collections::HashMap, sync::Arc};
+
+use axum::{
+    extract::{FromRef, MatchedPath},
+    http::Request,
+    Router,
+};
 use sqlx::{postgres::PgPoolOptions, PgPool};
 use tokio::net::TcpListener;
 use tower_http::trace::{self, TraceLayer};
@@ -6,8 +12,14 @@ use tracing::{info_span, Level};
 use uuid::Uuid;
 
 use crate::{
-    configuration::{Configuration, DBConfig},
+    configuration::{
+        Configuration, DBConfig, OidcProviderConfig, RegistrationMode, StorageConfig,
+        WebAuthnConfig,
+    },
+    jobs,
     routes::routes,
+    storage::{self, BlobStore},
+    utils::jwt::TokenService,
 };
 
 pub struct App {
@@ -19,12 +31,47 @@ pub struct App {
 #[derive(Clone)]
 pub struct AppCtx {
     pub db: PgPool,
+    pub registration_mode: RegistrationMode,
+    pub scim_token: Option<String>,
+    pub oidc_providers: Arc<HashMap<String, OidcProviderConfig>>,
+    pub http_client: reqwest::Client,
+    pub webauthn: Arc<WebAuthnConfig>,
+    pub tokens: Arc<TokenService>,
+    pub search_language: String,
+    pub blobs: Arc<dyn BlobStore>,
+    pub storage: Arc<StorageConfig>,
+    pub secure_cookies: bool,
+    pub public_url: String,
+}
+
+impl FromRef<AppCtx> for Arc<TokenService> {
+    fn from_ref(ctx: &AppCtx) -> Self {
+        ctx.tokens.clone()
+    }
 }
 
 impl App {
     pub async fn build(config: &Configuration) -> Self {
+        let tokens = TokenService::new(&config.jwt).expect("Invalid JWT configuration");
         let pool = connect(&config.db).await;
-        let state = AppCtx { db: pool };
+        check_search_language(&pool, &config.search.language).await;
+        jobs::spawn(&pool, &config.jobs);
+        let http_client = reqwest::Client::new();
+
+        let state = AppCtx {
+            db: pool,
+            registration_mode: config.app.registration_mode,
+            scim_token: config.scim.token.clone(),
+            oidc_providers: Arc::new(config.oidc.providers.clone()),
+            http_client: http_client.clone(),
+            webauthn: Arc::new(config.webauthn.clone()),
+            tokens: Arc::new(tokens),
+            search_language: config.search.language.clone(),
+            blobs: storage::from_config(&config.storage.backend, http_client),
+            storage: Arc::new(config.storage.clone()),
+            secure_cookies: config.app.secure_cookies,
+            public_url: config.app.public_url.trim_end_matches('/').to_string(),
+        };
 
         let router = Router::new().merge(routes()).with_state(state).layer(
             TraceLayer::new_for_http()
@@ -75,3 +122,11 @@ async fn connect(db_config: &DBConfig) -> PgPool {
         .await
         .expect("Failed to connect to DB")
 }
+
+async fn check_search_language(pool: &PgPool, language: &str) {
+    sqlx::query("SELECT $1::TEXT::REGCONFIG")
+        .bind(language)
+        .execute(pool)
+        .await
+        .expect("Unknown text search language");
+}
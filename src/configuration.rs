@@ -1,3 +1,5 @@
+use std::{collections::HashMap, path::PathBuf};
+
 use config::Config;
 use serde::Deserialize;
 use sqlx::postgres::PgConnectOptions;
@@ -6,12 +8,221 @@ use sqlx::postgres::PgConnectOptions;
 pub struct Configuration {
     pub db: DBConfig,
     pub app: AppConfig,
+
+    #[serde(default)]
+    pub scim: ScimConfig,
+
+    #[serde(default)]
+    pub oidc: OidcConfig,
+
+    #[serde(default)]
+    pub webauthn: WebAuthnConfig,
+
+    #[serde(default)]
+    pub jwt: JwtConfig,
+
+    #[serde(default)]
+    pub jobs: JobsConfig,
+
+    #[serde(default)]
+    pub search: SearchConfig,
+
+    #[serde(default)]
+    pub storage: StorageConfig,
 }
 
 #[derive(Deserialize, Clone)]
 pub struct AppConfig {
     pub host: String,
     pub port: u16,
+
+    #[serde(default)]
+    pub registration_mode: RegistrationMode,
+
+    /// Marks the session cookie of the HTML pages `Secure`, enable when served over HTTPS.
+    #[serde(default)]
+    pub secure_cookies: bool,
+
+    /// Origin the site is reached at, used for absolute links in feeds and to check
+    /// where the HTML forms are submitted from.
+    #[serde(default = "default_public_url")]
+    pub public_url: String,
+}
+
+fn default_public_url() -> String {
+    "http://localhost:3000".to_string()
+}
+
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum RegistrationMode {
+    #[default]
+    Open,
+    InviteOnly,
+    Closed,
+}
+
+#[derive(Deserialize, Clone, Default)]
+pub struct ScimConfig {
+    /// Bearer token the provisioning client must send. SCIM is disabled when unset.
+    pub token: Option<String>,
+}
+
+#[derive(Deserialize, Clone, Default)]
+pub struct OidcConfig {
+    /// External identity providers keyed by the name used in `/auth/external/:provider`.
+    #[serde(default)]
+    pub providers: HashMap<String, OidcProviderConfig>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct OidcProviderConfig {
+    pub issuer: String,
+    pub authorization_endpoint: String,
+    pub token_endpoint: String,
+    pub jwks_uri: String,
+    pub client_id: String,
+    pub client_secret: Option<String>,
+    pub redirect_uri: String,
+
+    #[serde(default = "default_oidc_scopes")]
+    pub scopes: Vec<String>,
+}
+
+fn default_oidc_scopes() -> Vec<String> {
+    vec![
+        "openid".to_string(),
+        "email".to_string(),
+        "profile".to_string(),
+    ]
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct WebAuthnConfig {
+    /// Relying party ID, usually the registrable domain of `origin`.
+    pub rp_id: String,
+    pub rp_name: String,
+    /// Origin the browser reports in `clientDataJSON`, e.g. `https://example.com`.
+    pub origin: String,
+}
+
+impl Default for WebAuthnConfig {
+    fn default() -> Self {
+        Self {
+            rp_id: "localhost".to_string(),
+            rp_name: "auth_server".to_string(),
+            origin: "http://localhost:3000".to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct JwtConfig {
+    /// HMAC secret for new tokens. Falls back to the `JWT_SECRET` environment variable.
+    pub secret: Option<String>,
+    /// Secrets that are still accepted for verification after a rotation.
+    pub previous_secrets: Vec<String>,
+    /// Expected `iss`; tokens from other environments sharing the secret are rejected.
+    pub issuer: String,
+    /// Expected `aud`.
+    pub audience: String,
+    pub ttl_seconds: u64,
+    /// Allowed clock skew when checking `exp` and `nbf`.
+    pub leeway_seconds: u64,
+}
+
+impl Default for JwtConfig {
+    fn default() -> Self {
+        Self {
+            secret: None,
+            previous_secrets: vec![],
+            issuer: "auth_server".to_string(),
+            audience: "auth_server".to_string(),
+            ttl_seconds: 60 * 60 * 24 * 2, // 48 hours
+            leeway_seconds: 60,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct JobsConfig {
+    /// How often scheduled articles are checked for publication.
+    pub publish_interval_seconds: u64,
+}
+
+impl Default for JobsConfig {
+    fn default() -> Self {
+        Self {
+            publish_interval_seconds: 30,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct SearchConfig {
+    /// Postgres text search configuration, e.g. `english` or `simple`. Articles keep the
+    /// language they were created with.
+    pub language: String,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            language: "english".to_string(),
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(default)]
+pub struct StorageConfig {
+    pub backend: StorageBackend,
+    pub max_upload_bytes: usize,
+    /// Largest accepted width or height, checked before the image is decoded.
+    pub max_image_dimension: u32,
+    /// Thumbnails fit in a square of this size, keeping the aspect ratio.
+    pub thumbnail_size: u32,
+    pub cache_max_age_seconds: u64,
+}
+
+impl Default for StorageConfig {
+    fn default() -> Self {
+        Self {
+            backend: StorageBackend::default(),
+            max_upload_bytes: 5 * 1024 * 1024,
+            max_image_dimension: 4096,
+            thumbnail_size: 320,
+            cache_max_age_seconds: 60 * 60 * 24 * 365,
+        }
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum StorageBackend {
+    Local {
+        root: PathBuf,
+    },
+    /// Any S3-compatible service, addressed path-style as `{endpoint}/{bucket}/{key}`.
+    S3 {
+        endpoint: String,
+        bucket: String,
+        region: String,
+        access_key: String,
+        secret_key: String,
+    },
+}
+
+impl Default for StorageBackend {
+    fn default() -> Self {
+        StorageBackend::Local {
+            root: PathBuf::from("uploads"),
+        }
+    }
 }
 
 #[derive(Deserialize)]
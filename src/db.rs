@@ -1,10 +1,21 @@
-use sqlx::{error::DatabaseError, postgres::PgPoolOptions, Pool, Postgres};
+use chrono::NaiveDateTime;
+use serde_json::{json, Value};
+use sqlx::{
+    error::DatabaseError,
+    postgres::{PgArguments, PgPoolOptions},
+    PgPool, Pool, Postgres, QueryBuilder,
+};
 use tracing::error;
+use uuid::Uuid;
 
 use crate::{
     configuration::DBConfig,
-    types::SearchType,
-    utils::{err::AppError, response::AppResult},
+    types::{CountMode, SearchType, SortingDirection},
+    utils::{
+        cursor::{decode_pair, Cursor},
+        err::AppError,
+        response::AppResult,
+    },
 };
 
 pub async fn connect(db_config: &DBConfig) -> Pool<Postgres> {
@@ -34,10 +45,10 @@ impl<T> DbResultExt<T> for Result<T, sqlx::Error> {
         self.map_err(|e| match &e {
             sqlx::Error::Database(db_err) => {
                 if db_err.constraint() == Some(name) {
-                    return map_err(&db_err);
+                    return map_err(db_err);
                 }
 
-                return AppError::DbError(e);
+                AppError::DbError(e)
             }
             _ => e.into(),
         })
@@ -60,26 +71,247 @@ impl<T> DbResultExt<T> for Result<T, sqlx::Error> {
             }
 
             tracing::error!("DB Error ({context}) {db_err}");
-            return db_err.into();
+            db_err.into()
         })
     }
 }
 
-pub trait Total {
-    fn total(&self) -> usize;
+/// Total of a listing as returned in `SearchType`.
+#[derive(Debug, Clone, Copy)]
+pub struct Count {
+    pub total: Option<usize>,
+    pub is_exact: bool,
+}
+
+impl Count {
+    pub fn exact(total: usize) -> Self {
+        Self {
+            total: Some(total),
+            is_exact: true,
+        }
+    }
 }
 
-pub fn into_search_type<T, R>(data: Vec<T>) -> SearchType<R>
+/// Counts the rows of `SELECT ... {from_sql}`, where `from_sql` holds the `FROM` and `WHERE`
+/// clauses of the listing and `args` builds the arguments it references.
+pub async fn count_rows(
+    pool: &PgPool,
+    mode: CountMode,
+    from_sql: &str,
+    args: impl Fn() -> PgArguments,
+) -> AppResult<Count> {
+    match mode {
+        CountMode::Exact => {
+            let sql = format!("SELECT COUNT(*) {from_sql}");
+            let total: i64 = sqlx::query_scalar_with(&sql, args())
+                .fetch_one(pool)
+                .await
+                .trace_db("Failed to count rows")?;
+
+            Ok(Count::exact(total as usize))
+        }
+        CountMode::Estimated => {
+            let sql = format!("EXPLAIN (FORMAT JSON) SELECT 1 {from_sql}");
+            let plan: Value = sqlx::query_scalar_with(&sql, args())
+                .fetch_one(pool)
+                .await
+                .trace_db("Failed to estimate rows")?;
+
+            let total = plan[0]["Plan"]["Plan Rows"].as_f64().unwrap_or(0.0);
+
+            Ok(Count {
+                total: Some(total.round() as usize),
+                is_exact: false,
+            })
+        }
+        CountMode::None => Ok(Count {
+            total: None,
+            is_exact: false,
+        }),
+    }
+}
+
+pub fn into_search_type<T, R>(data: Vec<T>, count: Count) -> SearchType<R>
 where
-    T: Into<R> + Total,
+    T: Into<R>,
 {
-    let mut total: usize = 0;
-    let mut results: Vec<R> = vec![];
+    SearchType {
+        results: data.into_iter().map(Into::into).collect(),
+        total: count.total,
+        total_is_exact: count.is_exact,
+        next_cursor: None,
+        prev_cursor: None,
+    }
+}
+
+/// Which slice of a keyset-paginated listing was requested.
+#[derive(Debug)]
+pub struct KeysetPage {
+    pub limit: usize,
+    /// Fetching the page before a cursor, rows come in reversed order.
+    pub backward: bool,
+    /// The page does not start at the beginning of the listing.
+    pub has_previous: bool,
+}
 
-    for item in data {
-        total = item.total();
-        results.push(item.into());
+impl KeysetPage {
+    /// Counts rows only when the page itself does not already tell the total: a first page
+    /// that is not full holds every matching row.
+    pub async fn count(
+        &self,
+        rows: usize,
+        pool: &PgPool,
+        mode: CountMode,
+        from_sql: &str,
+        args: impl Fn() -> PgArguments,
+    ) -> AppResult<Count> {
+        if mode == CountMode::Exact && !self.has_previous && !self.backward && rows <= self.limit {
+            return Ok(Count::exact(rows));
+        }
+
+        count_rows(pool, mode, from_sql, args).await
     }
+}
+
+/// Ordering of a listing by a timestamp and then the row id, both in `direction`.
+#[derive(Debug, Clone, Copy)]
+pub struct TimestampOrder {
+    /// Stored in cursors, e.g. `created_at DESC,id DESC`.
+    pub signature: &'static str,
+    pub timestamp: &'static str,
+    pub id: &'static str,
+    pub direction: SortingDirection,
+}
+
+/// A `KeysetPage` of a listing in `TimestampOrder`, together with its decoded cursor.
+#[derive(Debug)]
+pub struct TimestampKeyset {
+    pub page: KeysetPage,
+    order: TimestampOrder,
+    cursor: Option<(NaiveDateTime, Uuid)>,
+    offset: u64,
+}
+
+impl KeysetPage {
+    /// Decodes the `after` or `before` cursor of a listing, only one of them may be set.
+    pub fn from_query(
+        limit: u64,
+        after: Option<&str>,
+        before: Option<&str>,
+        order: TimestampOrder,
+    ) -> AppResult<TimestampKeyset> {
+        let (cursor, backward) = match (after, before) {
+            (Some(_), Some(_)) => {
+                return Err(AppError::BadRequest(
+                    "Only one of after and before can be set".to_string(),
+                ))
+            }
+            (after, None) => (after, false),
+            (None, before) => (before, true),
+        };
 
-    return SearchType { results, total };
+        Ok(TimestampKeyset {
+            page: KeysetPage {
+                limit: limit as usize,
+                backward,
+                has_previous: after.is_some(),
+            },
+            order,
+            cursor: cursor
+                .map(|cursor| decode_pair(cursor, order.signature))
+                .transpose()?,
+            offset: 0,
+        })
+    }
+}
+
+impl TimestampKeyset {
+    pub fn with_offset(mut self, offset: u64) -> Self {
+        self.offset = offset;
+        self.page.has_previous |= offset > 0;
+        self
+    }
+
+    /// Appends the cursor condition, `ORDER BY` and `LIMIT` to a query ending in a `WHERE`
+    /// clause. Pages before a cursor are read in the reversed order and flipped afterwards.
+    pub fn push_sql(&self, builder: &mut QueryBuilder<'_, Postgres>) {
+        let TimestampOrder { timestamp, id, .. } = self.order;
+        let direction = match self.page.backward {
+            true => self.order.direction.reversed(),
+            false => self.order.direction,
+        };
+        let (op, keyword) = match direction {
+            SortingDirection::ASC => (" > ", " ASC"),
+            SortingDirection::DESC => (" < ", " DESC"),
+        };
+
+        if let Some((cursor_timestamp, cursor_id)) = self.cursor {
+            builder
+                .push(" AND (")
+                .push(timestamp)
+                .push(", ")
+                .push(id)
+                .push(")")
+                .push(op)
+                .push("(")
+                .push_bind(cursor_timestamp)
+                .push(", ")
+                .push_bind(cursor_id)
+                .push(")");
+        }
+
+        builder
+            .push(" ORDER BY ")
+            .push(timestamp)
+            .push(keyword)
+            .push(", ")
+            .push(id)
+            .push(keyword)
+            .push(" LIMIT ")
+            .push_bind(self.page.limit as i64 + 1)
+            .push(" OFFSET ")
+            .push_bind(self.offset as i64);
+    }
+
+    pub fn cursor_of(&self, timestamp: NaiveDateTime, id: Uuid) -> String {
+        Cursor {
+            order: self.order.signature.to_string(),
+            key: vec![json!(timestamp), json!(id)],
+        }
+        .encode()
+    }
+}
+
+/// Like `into_search_type`, for rows fetched with `LIMIT page.limit + 1` so that the extra row
+/// tells whether another page follows.
+pub fn into_keyset_search_type<T, R>(
+    mut data: Vec<T>,
+    page: &KeysetPage,
+    count: Count,
+    cursor_of: impl Fn(&T) -> String,
+) -> SearchType<R>
+where
+    T: Into<R>,
+{
+    let has_more = data.len() > page.limit;
+    data.truncate(page.limit);
+
+    if page.backward {
+        data.reverse();
+    }
+
+    let (has_next, has_prev) = if page.backward {
+        (true, has_more)
+    } else {
+        (has_more, page.has_previous)
+    };
+
+    let next_cursor = data.last().filter(|_| has_next).map(&cursor_of);
+    let prev_cursor = data.first().filter(|_| has_prev).map(&cursor_of);
+
+    SearchType {
+        next_cursor,
+        prev_cursor,
+        ..into_search_type(data, count)
+    }
 }
@@ -0,0 +1,27 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(PartialEq, Debug, Deserialize, Serialize, Clone, Copy, Default, sqlx::Type)]
+#[sqlx(type_name = "article_status", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ArticleStatus {
+    /// Visible only to the author.
+    Draft,
+    /// Published by the scheduler once `published_at` is reached.
+    Scheduled,
+    #[default]
+    Published,
+    /// Hidden from everyone except the author, but kept with its history.
+    Archived,
+}
+
+#[derive(PartialEq, Debug, Deserialize, Serialize, Clone, Copy, sqlx::Type)]
+#[sqlx(type_name = "reaction_kind", rename_all = "snake_case")]
+#[serde(rename_all = "snake_case")]
+pub enum ReactionKind {
+    Like,
+    Love,
+    Laugh,
+    Wow,
+    Sad,
+    Angry,
+}
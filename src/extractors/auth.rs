@@ -1,12 +1,54 @@
+use std::sync::Arc;
+
 use axum::{
     async_trait,
-    extract::{FromRequestParts, Query},
-    http::{header::AUTHORIZATION, request::Parts},
+    extract::{FromRef, FromRequestParts, Query},
+    http::{
+        header::{AUTHORIZATION, COOKIE},
+        request::Parts,
+    },
 };
 use serde::de::DeserializeOwned;
 use validator::Validate;
 
-use crate::utils::{err::AppError, jwt};
+use crate::{
+    application::AppCtx,
+    db::DbResultExt,
+    domains::user::UserID,
+    utils::{
+        err::AppError,
+        jwt::{TokenService, UserData},
+        response::AppResult,
+    },
+};
+
+/// Claims of an access token that name its user.
+pub trait UserClaims {
+    fn user_id(&self) -> &UserID;
+}
+
+impl UserClaims for UserData {
+    fn user_id(&self) -> &UserID {
+        &self.user_id
+    }
+}
+
+/// Tokens stay valid until they expire, so deactivated (or deleted) users are rejected
+/// when the token is used rather than only at login.
+async fn is_active(ctx: &AppCtx, user_id: &UserID) -> AppResult<bool> {
+    let active = sqlx::query_scalar!(
+        r#"
+            SELECT active FROM public.users WHERE id = $1
+        "#,
+        user_id.as_ref()
+    )
+    .fetch_optional(&ctx.db)
+    .await
+    .trace_db("Failed to fetch user status")?
+    .unwrap_or(false);
+
+    Ok(active)
+}
 
 pub struct AuthUser<T>(pub T);
 
@@ -14,11 +56,15 @@ pub struct AuthUser<T>(pub T);
 impl<S, T> FromRequestParts<S> for AuthUser<T>
 where
     S: Sync + Send,
-    T: DeserializeOwned,
+    T: DeserializeOwned + UserClaims + Send,
+    AppCtx: FromRef<S>,
+    Arc<TokenService>: FromRef<S>,
 {
     type Rejection = AppError;
 
-    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let tokens = Arc::<TokenService>::from_ref(state);
+
         let token = parts
             .headers
             .get(AUTHORIZATION)
@@ -26,11 +72,15 @@ where
 
         let token = token.to_str().map_err(|_| AppError::Unauthorized)?;
 
-        let user_info: T = jwt::verify(token).map_err(|e| {
+        let user_info: T = tokens.verify(token).map_err(|e| {
             dbg!(e);
             AppError::Unauthorized
         })?;
 
+        if !is_active(&AppCtx::from_ref(state), user_info.user_id()).await? {
+            return Err(AppError::Unauthorized);
+        }
+
         Ok(Self(user_info))
     }
 }
@@ -41,16 +91,23 @@ pub struct MaybeAuthUser<T>(pub Option<T>);
 impl<S, T> FromRequestParts<S> for MaybeAuthUser<T>
 where
     S: Sync + Send,
-    T: DeserializeOwned,
+    T: DeserializeOwned + UserClaims + Send,
+    AppCtx: FromRef<S>,
+    Arc<TokenService>: FromRef<S>,
 {
     type Rejection = AppError;
 
-    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
         let token = parts.headers.get(AUTHORIZATION);
 
         if let Some(token) = token {
+            let tokens = Arc::<TokenService>::from_ref(state);
             let token = token.to_str().map_err(|_| AppError::Unauthorized)?;
-            let user_info: T = jwt::verify(token).map_err(|_| AppError::Unauthorized)?;
+            let user_info: T = tokens.verify(token).map_err(|_| AppError::Unauthorized)?;
+
+            if !is_active(&AppCtx::from_ref(state), user_info.user_id()).await? {
+                return Err(AppError::Unauthorized);
+            }
 
             return Ok(Self(Some(user_info)));
         }
@@ -59,6 +116,82 @@ where
     }
 }
 
+/// Cookie holding the access token of the HTML pages.
+pub const SESSION_COOKIE: &str = "session";
+
+/// Like `MaybeAuthUser`, but reads the token from the session cookie. A missing, expired or
+/// otherwise invalid session, or one of a deactivated user, counts as signed out.
+pub struct MaybeSessionUser<T>(pub Option<T>);
+
+#[async_trait]
+impl<S, T> FromRequestParts<S> for MaybeSessionUser<T>
+where
+    S: Sync + Send,
+    T: DeserializeOwned + UserClaims + Send,
+    AppCtx: FromRef<S>,
+    Arc<TokenService>: FromRef<S>,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let token = parts
+            .headers
+            .get_all(COOKIE)
+            .iter()
+            .filter_map(|header| header.to_str().ok())
+            .flat_map(|header| header.split(';'))
+            .filter_map(|cookie| cookie.trim().split_once('='))
+            .find(|(name, _)| *name == SESSION_COOKIE)
+            .map(|(_, value)| value);
+
+        let Some(token) = token else {
+            return Ok(Self(None));
+        };
+
+        let tokens = Arc::<TokenService>::from_ref(state);
+        let Ok(user_info) = tokens.verify::<T>(token) else {
+            return Ok(Self(None));
+        };
+
+        let active = is_active(&AppCtx::from_ref(state), user_info.user_id()).await?;
+        Ok(Self(active.then_some(user_info)))
+    }
+}
+
+pub struct AdminUser(pub UserData);
+
+#[async_trait]
+impl<S> FromRequestParts<S> for AdminUser
+where
+    S: Sync + Send,
+    AppCtx: FromRef<S>,
+    Arc<TokenService>: FromRef<S>,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let AuthUser(user) = AuthUser::<UserData>::from_request_parts(parts, state).await?;
+        let ctx = AppCtx::from_ref(state);
+
+        let is_admin = sqlx::query_scalar!(
+            r#"
+                SELECT is_admin FROM public.users WHERE id = $1
+            "#,
+            user.user_id.as_ref()
+        )
+        .fetch_optional(&ctx.db)
+        .await
+        .trace_db("Failed to fetch user role")?
+        .unwrap_or(false);
+
+        if !is_admin {
+            return Err(AppError::Forbidden);
+        }
+
+        Ok(Self(user))
+    }
+}
+
 pub struct ValidateQuery<T>(pub T);
 
 #[async_trait]
@@ -1,5 +1,7 @@
 mod auth;
+mod origin;
 mod validators;
 
 pub use auth::*;
+pub use origin::*;
 pub use validators::*;
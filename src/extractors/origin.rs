@@ -0,0 +1,53 @@
+use axum::{
+    async_trait,
+    extract::{FromRef, FromRequestParts},
+    http::{
+        header::{ORIGIN, REFERER},
+        request::Parts,
+    },
+};
+
+use crate::{application::AppCtx, utils::err::AppError};
+
+/// Rejects form submissions that don't come from the app's own pages.
+///
+/// The session cookie alone can't tell whether a POST was sent by another site, so the
+/// `Origin` header (or the `Referer` when a browser leaves it out) has to name
+/// `app.public_url`. Requests carrying neither are rejected as well.
+pub struct SameOrigin;
+
+#[async_trait]
+impl<S> FromRequestParts<S> for SameOrigin
+where
+    S: Sync + Send,
+    AppCtx: FromRef<S>,
+{
+    type Rejection = AppError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let ctx = AppCtx::from_ref(state);
+
+        let source = parts
+            .headers
+            .get(ORIGIN)
+            .or_else(|| parts.headers.get(REFERER))
+            .and_then(|value| value.to_str().ok())
+            .ok_or(AppError::Forbidden)?;
+
+        if origin(source) != origin(&ctx.public_url) {
+            return Err(AppError::Forbidden);
+        }
+
+        Ok(SameOrigin)
+    }
+}
+
+/// The `scheme://host[:port]` part of a URL.
+fn origin(url: &str) -> &str {
+    let authority = url.find("://").map_or(0, |i| i + 3);
+
+    match url[authority..].find(['/', '?', '#']) {
+        Some(end) => &url[..authority + end],
+        None => url,
+    }
+}
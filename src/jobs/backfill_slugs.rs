@@ -0,0 +1,71 @@
+use sqlx::PgPool;
+use tracing::{info, instrument};
+
+use crate::{
+    db::DbResultExt,
+    routes::articles::slugs::{pick_slug, record_slug},
+    utils::response::AppResult,
+};
+
+const BATCH_SIZE: i64 = 100;
+
+/// Recomputes the slugs the slugs migration derived in SQL, so that they match `slugify`.
+pub async fn run(pool: PgPool) {
+    let mut total = 0;
+
+    while let Ok(updated @ 1..) = backfill_batch(&pool).await {
+        total += updated;
+    }
+
+    if total > 0 {
+        info!("Recomputed {total} article slugs");
+    }
+}
+
+#[instrument(skip(pool))]
+async fn backfill_batch(pool: &PgPool) -> AppResult<u64> {
+    let mut tx = pool.begin().await.trace_db("Failed to begin transaction")?;
+
+    let articles = sqlx::query!(
+        r#"
+            SELECT id, author_id, title, slug
+            FROM articles
+            WHERE legacy_slug
+            LIMIT $1
+            FOR UPDATE SKIP LOCKED
+        "#,
+        BATCH_SIZE
+    )
+    .fetch_all(&mut *tx)
+    .await
+    .trace_db("Failed to fetch legacy slugs")?;
+
+    for article in &articles {
+        let slug = pick_slug(
+            &mut tx,
+            &article.author_id,
+            &article.title,
+            Some(&article.id),
+        )
+        .await?;
+
+        sqlx::query!(
+            "UPDATE articles SET slug = $2, legacy_slug = FALSE WHERE id = $1",
+            article.id,
+            slug,
+        )
+        .execute(&mut *tx)
+        .await
+        .trace_db("Failed to store article slug")?;
+
+        if slug != article.slug {
+            record_slug(&mut tx, &article.author_id, &slug, &article.id).await?;
+        }
+    }
+
+    tx.commit()
+        .await
+        .trace_db("Failed to commit article slugs")?;
+
+    Ok(articles.len() as u64)
+}
@@ -0,0 +1,19 @@
+use std::time::Duration;
+
+use sqlx::PgPool;
+
+use crate::configuration::JobsConfig;
+
+pub mod backfill_slugs;
+pub mod publish_scheduled;
+pub mod render_markdown;
+
+/// Starts the background jobs on the current runtime. They run until the process exits.
+pub fn spawn(pool: &PgPool, config: &JobsConfig) {
+    tokio::spawn(publish_scheduled::run(
+        pool.clone(),
+        Duration::from_secs(config.publish_interval_seconds),
+    ));
+    tokio::spawn(render_markdown::run(pool.clone()));
+    tokio::spawn(backfill_slugs::run(pool.clone()));
+}
@@ -0,0 +1,39 @@
+use std::time::Duration;
+
+use sqlx::PgPool;
+use tokio::time::{interval, MissedTickBehavior};
+use tracing::{info, instrument};
+
+use crate::db::DbResultExt;
+
+pub async fn run(pool: PgPool, period: Duration) {
+    let mut ticker = interval(period);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    loop {
+        ticker.tick().await;
+
+        // Errors are traced and retried on the next tick
+        let _ = publish_due_articles(&pool).await;
+    }
+}
+
+#[instrument(skip(pool))]
+async fn publish_due_articles(pool: &PgPool) -> Result<u64, sqlx::Error> {
+    let published = sqlx::query!(
+        r#"
+            UPDATE articles SET status = 'published'
+            WHERE status = 'scheduled' AND published_at <= NOW()
+        "#
+    )
+    .execute(pool)
+    .await
+    .trace_db("Failed to publish scheduled articles")?
+    .rows_affected();
+
+    if published > 0 {
+        info!("Published {published} scheduled articles");
+    }
+
+    Ok(published)
+}
@@ -0,0 +1,51 @@
+use sqlx::PgPool;
+use tracing::{info, instrument};
+
+use crate::{db::DbResultExt, utils::markdown};
+
+const BATCH_SIZE: i64 = 100;
+
+/// Renders articles stored before their HTML was cached, a batch at a time.
+pub async fn run(pool: PgPool) {
+    let mut total = 0;
+
+    while let Ok(rendered @ 1..) = render_batch(&pool).await {
+        total += rendered;
+    }
+
+    if total > 0 {
+        info!("Rendered {total} articles");
+    }
+}
+
+#[instrument(skip(pool))]
+async fn render_batch(pool: &PgPool) -> Result<u64, sqlx::Error> {
+    let articles = sqlx::query!(
+        "SELECT id, text FROM articles WHERE html IS NULL LIMIT $1",
+        BATCH_SIZE
+    )
+    .fetch_all(pool)
+    .await
+    .trace_db("Failed to fetch unrendered articles")?;
+
+    for article in &articles {
+        let rendered = markdown::render(&article.text);
+
+        sqlx::query!(
+            r#"
+                UPDATE articles SET html = $2, word_count = $3, reading_time_minutes = $4
+                WHERE id = $1 AND text = $5
+            "#,
+            article.id,
+            rendered.html,
+            rendered.word_count,
+            rendered.reading_time_minutes,
+            article.text,
+        )
+        .execute(pool)
+        .await
+        .trace_db("Failed to store rendered article")?;
+    }
+
+    Ok(articles.len() as u64)
+}
@@ -3,8 +3,10 @@ pub mod configuration;
 pub mod db;
 pub mod domains;
 pub mod extractors;
+pub mod jobs;
 pub mod middlewares;
 pub mod parsers;
 pub mod routes;
+pub mod storage;
 pub mod types;
 pub mod utils;
@@ -1,13 +1,16 @@
+use std::sync::Arc;
+
 use axum::{
-    extract::Request,
+    extract::{Request, State},
     http::{header::AUTHORIZATION, HeaderMap, StatusCode},
     middleware::Next,
     response::Response,
 };
 
-use crate::utils::jwt::{self};
+use crate::utils::jwt::{TokenService, UserData};
 
 pub async fn auth(
+    State(tokens): State<Arc<TokenService>>,
     headers: HeaderMap,
     mut request: Request,
     next: Next,
@@ -15,7 +18,7 @@ pub async fn auth(
     let token = headers.get(AUTHORIZATION).ok_or(StatusCode::UNAUTHORIZED)?;
     let token = token.to_str().map_err(|_| StatusCode::UNAUTHORIZED)?;
 
-    let user_data = jwt::verify(token).map_err(|e| {
+    let user_data: UserData = tokens.verify(token).map_err(|e| {
         dbg!(e);
         StatusCode::UNAUTHORIZED
     })?;
@@ -2,6 +2,8 @@ use std::{borrow::Cow, collections::HashMap};
 
 use validator::ValidationError;
 
+use crate::utils::tag::normalize_tag;
+
 pub fn validate_order_by(sorting_direction: &str) -> Result<(), ValidationError> {
     if sorting_direction != "ASC" && sorting_direction != "DESC" {
         return Err(ValidationError {
@@ -13,3 +15,16 @@ pub fn validate_order_by(sorting_direction: &str) -> Result<(), ValidationError>
 
     Ok(())
 }
+
+/// Tag filters are normalized before matching, so blank entries would match nothing.
+pub fn validate_tag_filter(tags: &[String]) -> Result<(), ValidationError> {
+    if tags.iter().any(|tag| normalize_tag(tag).is_empty()) {
+        return Err(ValidationError {
+            code: Cow::from("invalid_tag"),
+            message: Some(Cow::from("Tags must not be blank")),
+            params: HashMap::new(),
+        });
+    }
+
+    Ok(())
+}
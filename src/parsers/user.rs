@@ -7,13 +7,17 @@ use crate::domains::user::{Email, Password, Username};
 pub fn validate_username(username: &Username) -> Result<(), ValidationError> {
     let username_len = username.as_ref().len();
 
-    if username_len < 3 || username_len > 50 {
+    if !(3..=50).contains(&username_len) {
         return Err(ValidationError::new("invalid_username"));
     }
 
     Ok(())
 }
 
+pub fn validate_usernames(usernames: &[Username]) -> Result<(), ValidationError> {
+    usernames.iter().try_for_each(validate_username)
+}
+
 pub fn validate_email(email: &Email) -> Result<(), ValidationError> {
     if !validator::validate_email(email.as_ref()) {
         return Err(ValidationError {
@@ -29,7 +33,7 @@ pub fn validate_email(email: &Email) -> Result<(), ValidationError> {
 pub fn validate_password(password: &Password) -> Result<(), ValidationError> {
     let len = password.as_ref().len();
 
-    if len < 8 || len > 50 {
+    if !(8..=50).contains(&len) {
         return Err(ValidationError {
             code: Cow::from("invalid_password"),
             message: Some(Cow::from(
@@ -41,3 +45,19 @@ pub fn validate_password(password: &Password) -> Result<(), ValidationError> {
 
     Ok(())
 }
+
+pub fn validate_web_url(url: &str) -> Result<(), ValidationError> {
+    let is_web_url = reqwest::Url::parse(url)
+        .map(|url| matches!(url.scheme(), "http" | "https"))
+        .unwrap_or(false);
+
+    if !is_web_url || url.len() > 2048 {
+        return Err(ValidationError {
+            code: Cow::from("invalid_url"),
+            message: Some(Cow::from("Expected an http or https URL")),
+            params: HashMap::new(),
+        });
+    }
+
+    Ok(())
+}
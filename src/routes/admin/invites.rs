@@ -0,0 +1,105 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse};
+use chrono::{NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tracing::instrument;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    application::AppCtx,
+    db::DbResultExt,
+    domains::user::UserID,
+    extractors::{AdminUser, ValidateJson},
+    utils::{
+        err::AppError,
+        response::{AppResponse, AppResult, DataResponse},
+    },
+};
+
+#[derive(Deserialize, Serialize, Validate, Debug, Default)]
+pub struct Payload {
+    #[validate(range(min = 1, max = 10000))]
+    pub max_uses: Option<i32>,
+
+    pub expires_at: Option<NaiveDateTime>,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct InviteCode {
+    pub code: String,
+    pub max_uses: i32,
+    pub uses: i32,
+    pub expires_at: Option<NaiveDateTime>,
+    pub created_at: NaiveDateTime,
+}
+
+#[instrument(skip(ctx))]
+pub async fn create_invite(
+    ctx: State<AppCtx>,
+    AdminUser(admin): AdminUser,
+    ValidateJson(payload): ValidateJson<Payload>,
+) -> AppResponse {
+    if let Some(expires_at) = payload.expires_at {
+        if expires_at <= Utc::now().naive_utc() {
+            return Err(AppError::BadRequest(
+                "Invite expiration date must be in the future".to_string(),
+            ));
+        }
+    }
+
+    let invite = insert_invite_code(&ctx.db, &admin.user_id, &payload).await?;
+
+    Ok((StatusCode::CREATED, DataResponse::new(invite)).into_response())
+}
+
+#[instrument(skip(ctx))]
+pub async fn list_invites(ctx: State<AppCtx>, AdminUser(_): AdminUser) -> AppResponse {
+    let invites = get_invite_codes(&ctx.db).await?;
+
+    Ok((StatusCode::OK, DataResponse::new(invites)).into_response())
+}
+
+#[instrument(skip(pool))]
+async fn insert_invite_code(
+    pool: &PgPool,
+    admin_id: &UserID,
+    payload: &Payload,
+) -> AppResult<InviteCode> {
+    let code = Uuid::new_v4().simple().to_string();
+
+    let invite = sqlx::query_as!(
+        InviteCode,
+        r#"
+            INSERT INTO invite_codes (code, created_by, max_uses, expires_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING code, max_uses, uses, expires_at, created_at
+        "#,
+        &code,
+        admin_id.as_ref(),
+        payload.max_uses.unwrap_or(1),
+        payload.expires_at
+    )
+    .fetch_one(pool)
+    .await
+    .trace_db("Failed to create invite code")?;
+
+    Ok(invite)
+}
+
+#[instrument(skip(pool))]
+async fn get_invite_codes(pool: &PgPool) -> AppResult<Vec<InviteCode>> {
+    let invites = sqlx::query_as!(
+        InviteCode,
+        r#"
+            SELECT code, max_uses, uses, expires_at, created_at
+            FROM invite_codes
+            ORDER BY created_at DESC
+        "#
+    )
+    .fetch_all(pool)
+    .await
+    .trace_db("Failed to fetch invite codes")?;
+
+    Ok(invites)
+}
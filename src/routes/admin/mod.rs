@@ -0,0 +1,19 @@
+pub mod invites;
+pub mod tags;
+
+use axum::{
+    routing::{patch, post},
+    Router,
+};
+
+use crate::application::AppCtx;
+
+pub fn routes() -> Router<AppCtx> {
+    Router::new()
+        .route(
+            "/admin/invites",
+            post(invites::create_invite).get(invites::list_invites),
+        )
+        .route("/admin/tags/:name", patch(tags::rename_tag))
+        .route("/admin/tags/:name/merge", post(tags::merge_tag))
+}
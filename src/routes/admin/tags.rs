@@ -0,0 +1,144 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use validator::Validate;
+
+use crate::{
+    application::AppCtx,
+    db::DbResultExt,
+    extractors::{AdminUser, ValidateJson},
+    routes::tags::{fetch_tag_count, refresh_tag_arrays},
+    utils::{
+        err::AppError,
+        response::{AppResponse, AppResult, DataResponse},
+        tag::normalize_tag,
+    },
+};
+
+#[derive(Deserialize, Serialize, Validate, Debug)]
+pub struct RenamePayload {
+    #[validate(length(min = 1, max = 64))]
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, Validate, Debug)]
+pub struct MergePayload {
+    /// Tag that takes over the articles, created if it does not exist yet.
+    #[validate(length(min = 1, max = 64))]
+    pub into: String,
+}
+
+/// Renaming onto an existing tag fails, such tags have to be merged instead.
+#[instrument(skip(ctx))]
+pub async fn rename_tag(
+    ctx: State<AppCtx>,
+    AdminUser(_): AdminUser,
+    Path(name): Path<String>,
+    ValidateJson(payload): ValidateJson<RenamePayload>,
+) -> AppResponse {
+    let (from, to) = (normalize_tag(&name), non_empty_tag(&payload.name)?);
+
+    let mut tx = ctx
+        .db
+        .begin()
+        .await
+        .trace_db("Failed to begin transaction")?;
+
+    let tag_id = sqlx::query_scalar!(
+        "UPDATE tags SET name = $2 WHERE name = $1 RETURNING id",
+        from,
+        to,
+    )
+    .fetch_optional(&mut *tx)
+    .await
+    .with_unique_violation(AppError::DuplicatedTag, "Duplicated tag name")?
+    .ok_or_else(|| tag_not_found(&from))?;
+
+    refresh_tag_arrays(&mut tx, &tag_id).await?;
+    tx.commit().await.trace_db("Failed to commit tag rename")?;
+
+    let tag = fetch_tag_count(&ctx.db, &tag_id).await?;
+
+    Ok((StatusCode::OK, DataResponse::new(tag)).into_response())
+}
+
+/// Moves the articles of a tag to another one and removes the merged tag.
+#[instrument(skip(ctx))]
+pub async fn merge_tag(
+    ctx: State<AppCtx>,
+    AdminUser(_): AdminUser,
+    Path(name): Path<String>,
+    ValidateJson(payload): ValidateJson<MergePayload>,
+) -> AppResponse {
+    let (from, into) = (normalize_tag(&name), non_empty_tag(&payload.into)?);
+
+    if from == into {
+        return Err(AppError::BadRequest(
+            "A tag cannot be merged into itself".to_string(),
+        ));
+    }
+
+    let mut tx = ctx
+        .db
+        .begin()
+        .await
+        .trace_db("Failed to begin transaction")?;
+
+    let source_id = sqlx::query_scalar!("SELECT id FROM tags WHERE name = $1 FOR UPDATE", from)
+        .fetch_optional(&mut *tx)
+        .await
+        .trace_db("Failed to fetch merged tag")?
+        .ok_or_else(|| tag_not_found(&from))?;
+
+    let target_id = sqlx::query_scalar!(
+        r#"
+            INSERT INTO tags (name) VALUES ($1)
+            ON CONFLICT (name) DO UPDATE SET name = EXCLUDED.name
+            RETURNING id
+        "#,
+        into,
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .trace_db("Failed to fetch merge target tag")?;
+
+    // Articles that already have both tags keep the target where it was
+    sqlx::query!(
+        r#"
+            INSERT INTO article_tags (article_id, tag_id, position)
+            SELECT article_id, $2, position FROM article_tags WHERE tag_id = $1
+            ON CONFLICT DO NOTHING
+        "#,
+        source_id,
+        target_id,
+    )
+    .execute(&mut *tx)
+    .await
+    .trace_db("Failed to move article tags")?;
+
+    sqlx::query!("DELETE FROM tags WHERE id = $1", source_id)
+        .execute(&mut *tx)
+        .await
+        .trace_db("Failed to delete merged tag")?;
+
+    refresh_tag_arrays(&mut tx, &target_id).await?;
+    tx.commit().await.trace_db("Failed to commit tag merge")?;
+
+    let tag = fetch_tag_count(&ctx.db, &target_id).await?;
+
+    Ok((StatusCode::OK, DataResponse::new(tag)).into_response())
+}
+
+fn non_empty_tag(name: &str) -> AppResult<String> {
+    Some(normalize_tag(name))
+        .filter(|name| !name.is_empty())
+        .ok_or_else(|| AppError::BadRequest("Tag name must not be blank".to_string()))
+}
+
+fn tag_not_found(name: &str) -> AppError {
+    AppError::NotFound(format!("Tag not found: {name}"))
+}
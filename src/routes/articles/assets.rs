@@ -0,0 +1,116 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tracing::instrument;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    application::AppCtx,
+    db::DbResultExt,
+    extractors::{AuthUser, MaybeAuthUser, ValidateJson},
+    routes::uploads::{fetch_upload, RawUpload, Upload},
+    utils::{
+        err::AppError,
+        jwt::UserData,
+        response::{AppResponse, AppResult, DataResponse},
+    },
+};
+
+use super::{get_article::fetch_visible_article, update_article::ensure_author};
+
+#[derive(Deserialize, Serialize, Validate, Debug)]
+pub struct AssetPayload {
+    pub upload_id: Uuid,
+}
+
+#[instrument(skip(ctx))]
+pub async fn list_assets(
+    ctx: State<AppCtx>,
+    MaybeAuthUser(user): MaybeAuthUser<UserData>,
+    Path(id): Path<Uuid>,
+) -> AppResponse {
+    let viewer = user.map(|user| user.user_id);
+    fetch_visible_article(&ctx.db, &id, viewer.as_ref()).await?;
+
+    let assets = fetch_assets(&ctx.db, &id).await?;
+
+    Ok((StatusCode::OK, DataResponse::new(assets)).into_response())
+}
+
+/// Authors can only attach files they uploaded themselves.
+#[instrument(skip(ctx))]
+pub async fn add_asset(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    Path(id): Path<Uuid>,
+    ValidateJson(payload): ValidateJson<AssetPayload>,
+) -> AppResponse {
+    ensure_author(&ctx.db, &id, &user.user_id).await?;
+
+    fetch_upload(&ctx.db, &payload.upload_id)
+        .await?
+        .filter(|upload| &upload.owner_id == user.user_id.as_ref())
+        .ok_or_else(|| AppError::NotFound(format!("Upload not found: {}", payload.upload_id)))?;
+
+    sqlx::query!(
+        r#"
+            INSERT INTO article_assets (article_id, upload_id)
+            VALUES ($1, $2)
+            ON CONFLICT DO NOTHING
+        "#,
+        id,
+        payload.upload_id,
+    )
+    .execute(&ctx.db)
+    .await
+    .trace_db("Failed to attach asset")?;
+
+    let assets = fetch_assets(&ctx.db, &id).await?;
+
+    Ok((StatusCode::OK, DataResponse::new(assets)).into_response())
+}
+
+#[instrument(skip(ctx))]
+pub async fn remove_asset(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    Path((id, upload_id)): Path<(Uuid, Uuid)>,
+) -> AppResponse {
+    ensure_author(&ctx.db, &id, &user.user_id).await?;
+
+    sqlx::query!(
+        "DELETE FROM article_assets WHERE article_id = $1 AND upload_id = $2",
+        id,
+        upload_id,
+    )
+    .execute(&ctx.db)
+    .await
+    .trace_db("Failed to detach asset")?;
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[instrument(skip(pool))]
+async fn fetch_assets(pool: &PgPool, id: &Uuid) -> AppResult<Vec<Upload>> {
+    let assets = sqlx::query_as!(
+        RawUpload,
+        r#"
+            SELECT u.id, u.owner_id, u.content_type, u.size, u.width, u.height, u.created_at
+            FROM article_assets aa
+            JOIN uploads u ON u.id = aa.upload_id
+            WHERE aa.article_id = $1
+            ORDER BY aa.created_at, u.id
+        "#,
+        id,
+    )
+    .fetch_all(pool)
+    .await
+    .trace_db("Failed to fetch article assets")?;
+
+    Ok(assets.into_iter().map(Upload::from).collect())
+}
@@ -0,0 +1,416 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::{postgres::PgArguments, Arguments, PgPool, QueryBuilder};
+use tracing::instrument;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    application::AppCtx,
+    db::{self, DbResultExt, KeysetPage, TimestampOrder},
+    domains::user::UserID,
+    extractors::{AuthUser, ValidateJson, ValidateQuery},
+    types::{CountMode, SearchType, SortingDirection},
+    utils::{
+        err::AppError,
+        jwt::UserData,
+        response::{AppResponse, AppResult, DataResponse},
+    },
+};
+
+use super::{get_article::fetch_visible_article, reactions::attach_reactions, Article, RawArticle};
+
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct BookmarkQuery {
+    /// Collection to file the bookmark under, the plain reading list when missing.
+    pub collection_id: Option<Uuid>,
+}
+
+#[derive(Deserialize, Serialize, Validate, Debug, Default)]
+pub struct ListQuery {
+    pub collection_id: Option<Uuid>,
+
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u64>,
+    pub after: Option<String>,
+    pub before: Option<String>,
+
+    #[serde(default)]
+    pub count: CountMode,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Collection {
+    pub id: Uuid,
+    pub name: String,
+    pub position: i32,
+    pub bookmark_count: i64,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Deserialize, Serialize, Validate, Debug)]
+pub struct CollectionPayload {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+}
+
+#[derive(Deserialize, Serialize, Validate, Debug)]
+pub struct OrderPayload {
+    /// Every collection of the user, in the new order.
+    pub collection_ids: Vec<Uuid>,
+}
+
+#[derive(sqlx::FromRow)]
+struct BookmarkRow {
+    #[sqlx(flatten)]
+    article: RawArticle,
+    bookmarked_at: NaiveDateTime,
+}
+
+impl From<BookmarkRow> for Article {
+    fn from(row: BookmarkRow) -> Self {
+        row.article.into()
+    }
+}
+
+const ORDER: TimestampOrder = TimestampOrder {
+    signature: "bookmarked_at DESC,id DESC",
+    timestamp: "b.created_at",
+    id: "a.id",
+    direction: SortingDirection::DESC,
+};
+
+const SELECT_SQL: &str = r#"
+    SELECT
+        a.id,
+        a.slug,
+        a.title,
+        a.text,
+        a.html,
+        a.word_count,
+        a.reading_time_minutes,
+        a.tags,
+        a.author_id,
+        a.created_at,
+        a.updated_at,
+        a.version,
+        a.status,
+        a.published_at,
+        a.comment_count,
+        u.username AS author_username,
+        u.display_name AS author_display_name,
+        u.avatar_url AS author_avatar_url,
+        NULL::TEXT AS headline,
+        b.created_at AS bookmarked_at
+"#;
+
+/// Articles that are no longer visible to the reader drop out of the list without
+/// losing the bookmark.
+const FILTER_SQL: &str = r#"
+    FROM bookmarks b
+    JOIN articles a ON a.id = b.article_id
+    JOIN users u ON u.id = a.author_id
+    WHERE
+        b.user_id = $1
+    AND
+        ($2::UUID IS NULL OR b.collection_id = $2)
+    AND
+        (a.status = 'published' OR a.author_id = $1)
+"#;
+
+fn collection_not_found(id: &Uuid) -> AppError {
+    AppError::NotFound(format!("Collection not found: {id}"))
+}
+
+fn duplicated_collection(name: &str) -> AppError {
+    AppError::BadRequest(format!("Collection already exists: {name}"))
+}
+
+/// Bookmarking an article again only moves it to the given collection.
+#[instrument(skip(ctx))]
+pub async fn add_bookmark(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<BookmarkQuery>,
+) -> AppResponse {
+    fetch_visible_article(&ctx.db, &id, Some(&user.user_id)).await?;
+
+    if let Some(collection_id) = &query.collection_id {
+        ensure_collection_owner(&ctx.db, collection_id, &user.user_id).await?;
+    }
+
+    sqlx::query!(
+        r#"
+            INSERT INTO bookmarks (user_id, article_id, collection_id)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (user_id, article_id)
+            DO UPDATE SET collection_id = EXCLUDED.collection_id
+        "#,
+        user.user_id.as_ref(),
+        id,
+        query.collection_id,
+    )
+    .execute(&ctx.db)
+    .await
+    .trace_db("Failed to add bookmark")?;
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[instrument(skip(ctx))]
+pub async fn remove_bookmark(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    Path(id): Path<Uuid>,
+) -> AppResponse {
+    sqlx::query!(
+        "DELETE FROM bookmarks WHERE user_id = $1 AND article_id = $2",
+        user.user_id.as_ref(),
+        id,
+    )
+    .execute(&ctx.db)
+    .await
+    .trace_db("Failed to remove bookmark")?;
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[instrument(skip(ctx))]
+pub async fn list_bookmarks(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    ValidateQuery(query): ValidateQuery<ListQuery>,
+) -> AppResponse {
+    let mut data = fetch_bookmarks(&ctx.db, &user.user_id, &query).await?;
+    attach_reactions(&ctx.db, &mut data.results, Some(&user.user_id)).await?;
+
+    Ok((StatusCode::OK, Json(data)).into_response())
+}
+
+#[instrument(skip(ctx))]
+pub async fn list_collections(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+) -> AppResponse {
+    let collections = fetch_collections(&ctx.db, &user.user_id).await?;
+
+    Ok((StatusCode::OK, DataResponse::new(collections)).into_response())
+}
+
+/// New collections go to the end of the list.
+#[instrument(skip(ctx))]
+pub async fn create_collection(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    ValidateJson(payload): ValidateJson<CollectionPayload>,
+) -> AppResponse {
+    let collection = sqlx::query_as!(
+        Collection,
+        r#"
+            INSERT INTO bookmark_collections (user_id, name, position)
+            SELECT $1, $2, COALESCE(MAX(position) + 1, 0)
+            FROM bookmark_collections
+            WHERE user_id = $1
+            RETURNING id, name, position, 0::BIGINT AS "bookmark_count!", created_at
+        "#,
+        user.user_id.as_ref(),
+        payload.name,
+    )
+    .fetch_one(&ctx.db)
+    .await
+    .trace_db("Failed to create collection")
+    .on_constraint("bookmark_collections_name_key", |_| {
+        duplicated_collection(&payload.name)
+    })?;
+
+    Ok((StatusCode::CREATED, DataResponse::new(collection)).into_response())
+}
+
+#[instrument(skip(ctx))]
+pub async fn rename_collection(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    Path(id): Path<Uuid>,
+    ValidateJson(payload): ValidateJson<CollectionPayload>,
+) -> AppResponse {
+    let renamed = sqlx::query!(
+        "UPDATE bookmark_collections SET name = $3 WHERE id = $1 AND user_id = $2",
+        id,
+        user.user_id.as_ref(),
+        payload.name,
+    )
+    .execute(&ctx.db)
+    .await
+    .trace_db("Failed to rename collection")
+    .on_constraint("bookmark_collections_name_key", |_| {
+        duplicated_collection(&payload.name)
+    })?
+    .rows_affected();
+
+    if renamed == 0 {
+        return Err(collection_not_found(&id));
+    }
+
+    let collection = fetch_collections(&ctx.db, &user.user_id)
+        .await?
+        .into_iter()
+        .find(|collection| collection.id == id)
+        .ok_or_else(|| collection_not_found(&id))?;
+
+    Ok((StatusCode::OK, DataResponse::new(collection)).into_response())
+}
+
+/// Bookmarks of a removed collection are kept in the plain reading list.
+#[instrument(skip(ctx))]
+pub async fn delete_collection(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    Path(id): Path<Uuid>,
+) -> AppResponse {
+    let deleted = sqlx::query!(
+        "DELETE FROM bookmark_collections WHERE id = $1 AND user_id = $2",
+        id,
+        user.user_id.as_ref(),
+    )
+    .execute(&ctx.db)
+    .await
+    .trace_db("Failed to delete collection")?
+    .rows_affected();
+
+    if deleted == 0 {
+        return Err(collection_not_found(&id));
+    }
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[instrument(skip(ctx))]
+pub async fn reorder_collections(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    ValidateJson(payload): ValidateJson<OrderPayload>,
+) -> AppResponse {
+    let mut current: Vec<Uuid> = fetch_collections(&ctx.db, &user.user_id)
+        .await?
+        .into_iter()
+        .map(|collection| collection.id)
+        .collect();
+    let mut requested = payload.collection_ids.clone();
+    current.sort();
+    requested.sort();
+
+    if current != requested {
+        return Err(AppError::BadRequest(
+            "Order must list every collection exactly once".to_string(),
+        ));
+    }
+
+    sqlx::query!(
+        r#"
+            UPDATE bookmark_collections c
+            SET position = o.position - 1
+            FROM UNNEST($2::UUID[]) WITH ORDINALITY AS o(id, position)
+            WHERE c.id = o.id AND c.user_id = $1
+        "#,
+        user.user_id.as_ref(),
+        &payload.collection_ids,
+    )
+    .execute(&ctx.db)
+    .await
+    .trace_db("Failed to reorder collections")?;
+
+    let collections = fetch_collections(&ctx.db, &user.user_id).await?;
+
+    Ok((StatusCode::OK, DataResponse::new(collections)).into_response())
+}
+
+#[instrument(skip(pool))]
+async fn ensure_collection_owner(pool: &PgPool, id: &Uuid, user_id: &UserID) -> AppResult<()> {
+    sqlx::query_scalar!(
+        "SELECT id FROM bookmark_collections WHERE id = $1 AND user_id = $2",
+        id,
+        user_id.as_ref(),
+    )
+    .fetch_optional(pool)
+    .await
+    .trace_db("Failed to fetch collection")?
+    .ok_or_else(|| collection_not_found(id))?;
+
+    Ok(())
+}
+
+#[instrument(skip(pool))]
+async fn fetch_collections(pool: &PgPool, user_id: &UserID) -> AppResult<Vec<Collection>> {
+    let collections = sqlx::query_as!(
+        Collection,
+        r#"
+            SELECT
+                c.id,
+                c.name,
+                c.position,
+                COUNT(b.article_id) AS "bookmark_count!",
+                c.created_at
+            FROM bookmark_collections c
+            LEFT JOIN bookmarks b ON b.collection_id = c.id
+            WHERE c.user_id = $1
+            GROUP BY c.id
+            ORDER BY c.position, c.created_at
+        "#,
+        user_id.as_ref(),
+    )
+    .fetch_all(pool)
+    .await
+    .trace_db("Failed to fetch collections")?;
+
+    Ok(collections)
+}
+
+#[instrument(skip(pool))]
+async fn fetch_bookmarks(
+    pool: &PgPool,
+    user_id: &UserID,
+    query: &ListQuery,
+) -> AppResult<SearchType<Article>> {
+    let keyset = KeysetPage::from_query(
+        query.limit.unwrap_or(20),
+        query.after.as_deref(),
+        query.before.as_deref(),
+        ORDER,
+    )?;
+
+    let filter_args = || {
+        let mut args = PgArguments::default();
+        args.add(user_id.as_ref());
+        args.add(query.collection_id);
+        args
+    };
+
+    let mut builder = QueryBuilder::with_arguments(SELECT_SQL, filter_args());
+    builder.push(FILTER_SQL);
+    keyset.push_sql(&mut builder);
+
+    let rows = builder
+        .build_query_as::<BookmarkRow>()
+        .fetch_all(pool)
+        .await
+        .trace_db("Failed to fetch bookmarks")?;
+
+    let count = keyset
+        .page
+        .count(rows.len(), pool, query.count, FILTER_SQL, filter_args)
+        .await?;
+
+    Ok(db::into_keyset_search_type(
+        rows,
+        &keyset.page,
+        count,
+        |row| keyset.cursor_of(row.bookmarked_at, row.article.id),
+    ))
+}
@@ -0,0 +1,403 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::{postgres::PgArguments, Arguments, PgConnection, PgPool, QueryBuilder};
+use tracing::instrument;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    application::AppCtx,
+    db::{self, DbResultExt, KeysetPage, TimestampOrder},
+    domains::user::{UserID, Username},
+    extractors::{AuthUser, MaybeAuthUser, ValidateJson, ValidateQuery},
+    types::{CountMode, SearchType, SortingDirection},
+    utils::{
+        err::AppError,
+        jwt::UserData,
+        response::{AppResponse, AppResult, DataResponse},
+    },
+};
+
+use super::{get_article::fetch_visible_article, Author};
+
+/// Deleted comments keep their place in the thread but lose their author and text.
+#[derive(Deserialize, Serialize)]
+pub struct Comment {
+    pub id: Uuid,
+    pub article_id: Uuid,
+    pub parent_id: Option<Uuid>,
+    pub author: Option<Author>,
+    pub text: Option<String>,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub deleted: bool,
+}
+
+#[derive(sqlx::FromRow)]
+struct RawComment {
+    id: Uuid,
+    article_id: Uuid,
+    parent_id: Option<Uuid>,
+    author_id: Uuid,
+    author_username: String,
+    author_display_name: Option<String>,
+    author_avatar_url: Option<String>,
+    text: String,
+    created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+    deleted_at: Option<NaiveDateTime>,
+}
+
+impl From<RawComment> for Comment {
+    fn from(raw: RawComment) -> Self {
+        let deleted = raw.deleted_at.is_some();
+
+        Comment {
+            id: raw.id,
+            article_id: raw.article_id,
+            parent_id: raw.parent_id,
+            author: (!deleted).then_some(Author {
+                id: UserID(raw.author_id),
+                username: Username(raw.author_username),
+                display_name: raw.author_display_name,
+                avatar_url: raw.author_avatar_url,
+            }),
+            text: (!deleted).then_some(raw.text),
+            created_at: raw.created_at,
+            updated_at: raw.updated_at,
+            deleted,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize, Validate, Debug)]
+pub struct CreatePayload {
+    #[validate(length(min = 1, max = 10000))]
+    pub text: String,
+
+    /// Comment being replied to, it must belong to the same article.
+    pub parent_id: Option<Uuid>,
+}
+
+#[derive(Deserialize, Serialize, Validate, Debug)]
+pub struct UpdatePayload {
+    #[validate(length(min = 1, max = 10000))]
+    pub text: String,
+}
+
+#[derive(Deserialize, Serialize, Validate, Debug, Default)]
+pub struct ListQuery {
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u64>,
+    pub after: Option<String>,
+    pub before: Option<String>,
+
+    #[serde(default)]
+    pub count: CountMode,
+}
+
+const ORDER: TimestampOrder = TimestampOrder {
+    signature: "created_at ASC,id ASC",
+    timestamp: "c.created_at",
+    id: "c.id",
+    direction: SortingDirection::ASC,
+};
+
+const SELECT_SQL: &str = r#"
+    SELECT
+        c.id,
+        c.article_id,
+        c.parent_id,
+        c.author_id,
+        u.username AS author_username,
+        u.display_name AS author_display_name,
+        u.avatar_url AS author_avatar_url,
+        c.text,
+        c.created_at,
+        c.updated_at,
+        c.deleted_at
+    FROM comments c
+    JOIN users u ON u.id = c.author_id
+    WHERE c.article_id = $1
+"#;
+
+/// Counts every row of the listing, so unlike `Article::comment_count` the `total` of a page
+/// includes the placeholders left by deleted comments.
+const COUNT_FROM_SQL: &str = "FROM comments WHERE article_id = $1";
+
+fn comment_not_found(id: &Uuid) -> AppError {
+    AppError::NotFound(format!("Comment not found: {id}"))
+}
+
+#[instrument(skip(ctx))]
+pub async fn create_comment(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    Path(article_id): Path<Uuid>,
+    ValidateJson(payload): ValidateJson<CreatePayload>,
+) -> AppResponse {
+    fetch_visible_article(&ctx.db, &article_id, Some(&user.user_id)).await?;
+
+    let mut tx = ctx
+        .db
+        .begin()
+        .await
+        .trace_db("Failed to begin transaction")?;
+
+    if let Some(parent_id) = &payload.parent_id {
+        ensure_parent(&mut tx, &article_id, parent_id).await?;
+    }
+
+    let id = sqlx::query_scalar!(
+        r#"
+            INSERT INTO comments (article_id, author_id, parent_id, text)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id
+        "#,
+        article_id,
+        user.user_id.as_ref(),
+        payload.parent_id,
+        payload.text,
+    )
+    .fetch_one(&mut *tx)
+    .await
+    .trace_db("Failed to create comment")?;
+
+    sqlx::query!(
+        "UPDATE articles SET comment_count = comment_count + 1 WHERE id = $1",
+        article_id,
+    )
+    .execute(&mut *tx)
+    .await
+    .trace_db("Failed to update comment count")?;
+
+    tx.commit().await.trace_db("Failed to commit comment")?;
+
+    let comment = fetch_comment(&ctx.db, &article_id, &id)
+        .await?
+        .ok_or_else(|| comment_not_found(&id))?;
+
+    Ok((
+        StatusCode::CREATED,
+        DataResponse::new(Comment::from(comment)),
+    )
+        .into_response())
+}
+
+#[instrument(skip(ctx))]
+pub async fn list_comments(
+    ctx: State<AppCtx>,
+    MaybeAuthUser(user): MaybeAuthUser<UserData>,
+    Path(article_id): Path<Uuid>,
+    ValidateQuery(query): ValidateQuery<ListQuery>,
+) -> AppResponse {
+    let viewer = user.map(|user| user.user_id);
+    fetch_visible_article(&ctx.db, &article_id, viewer.as_ref()).await?;
+
+    let data = fetch_comments(&ctx.db, &article_id, &query).await?;
+
+    Ok((StatusCode::OK, Json(data)).into_response())
+}
+
+#[instrument(skip(ctx))]
+pub async fn update_comment(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    Path((article_id, id)): Path<(Uuid, Uuid)>,
+    ValidateJson(payload): ValidateJson<UpdatePayload>,
+) -> AppResponse {
+    let comment = fetch_comment(&ctx.db, &article_id, &id)
+        .await?
+        .filter(|comment| comment.deleted_at.is_none())
+        .ok_or_else(|| comment_not_found(&id))?;
+
+    if &comment.author_id != user.user_id.as_ref() {
+        return Err(AppError::Forbidden);
+    }
+
+    sqlx::query!(
+        "UPDATE comments SET text = $2, updated_at = NOW() WHERE id = $1",
+        id,
+        payload.text,
+    )
+    .execute(&ctx.db)
+    .await
+    .trace_db("Failed to update comment")?;
+
+    let comment = fetch_comment(&ctx.db, &article_id, &id)
+        .await?
+        .ok_or_else(|| comment_not_found(&id))?;
+
+    Ok((StatusCode::OK, DataResponse::new(Comment::from(comment))).into_response())
+}
+
+/// Both the comment author and the owner of the article may remove a comment.
+#[instrument(skip(ctx))]
+pub async fn delete_comment(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    Path((article_id, id)): Path<(Uuid, Uuid)>,
+) -> AppResponse {
+    let comment = fetch_comment(&ctx.db, &article_id, &id)
+        .await?
+        .filter(|comment| comment.deleted_at.is_none())
+        .ok_or_else(|| comment_not_found(&id))?;
+
+    let article_author =
+        sqlx::query_scalar!("SELECT author_id FROM articles WHERE id = $1", article_id)
+            .fetch_one(&ctx.db)
+            .await
+            .trace_db("Failed to fetch article author")?;
+
+    let user_id = user.user_id.as_ref();
+    if &comment.author_id != user_id && &article_author != user_id {
+        return Err(AppError::Forbidden);
+    }
+
+    let mut tx = ctx
+        .db
+        .begin()
+        .await
+        .trace_db("Failed to begin transaction")?;
+
+    // Replies keep pointing at the row, only its content goes away
+    let deleted = sqlx::query!(
+        r#"
+            UPDATE comments
+            SET deleted_at = NOW(), updated_at = NOW()
+            WHERE id = $1 AND deleted_at IS NULL
+        "#,
+        id,
+    )
+    .execute(&mut *tx)
+    .await
+    .trace_db("Failed to delete comment")?
+    .rows_affected();
+
+    if deleted > 0 {
+        sqlx::query!(
+            "UPDATE articles SET comment_count = comment_count - 1 WHERE id = $1",
+            article_id,
+        )
+        .execute(&mut *tx)
+        .await
+        .trace_db("Failed to update comment count")?;
+    }
+
+    tx.commit()
+        .await
+        .trace_db("Failed to commit comment deletion")?;
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[instrument(skip(conn))]
+async fn ensure_parent(
+    conn: &mut PgConnection,
+    article_id: &Uuid,
+    parent_id: &Uuid,
+) -> AppResult<()> {
+    let parent = sqlx::query!(
+        "SELECT article_id, deleted_at FROM comments WHERE id = $1",
+        parent_id,
+    )
+    .fetch_optional(conn)
+    .await
+    .trace_db("Failed to fetch parent comment")?;
+
+    match parent {
+        Some(parent) if &parent.article_id == article_id && parent.deleted_at.is_none() => Ok(()),
+        _ => Err(AppError::BadRequest(format!(
+            "Cannot reply to comment {parent_id}"
+        ))),
+    }
+}
+
+#[instrument(skip(pool))]
+async fn fetch_comment(
+    pool: &PgPool,
+    article_id: &Uuid,
+    id: &Uuid,
+) -> AppResult<Option<RawComment>> {
+    let comment = sqlx::query_as!(
+        RawComment,
+        r#"
+            SELECT
+                c.id,
+                c.article_id,
+                c.parent_id,
+                c.author_id,
+                u.username AS author_username,
+                u.display_name AS author_display_name,
+                u.avatar_url AS author_avatar_url,
+                c.text,
+                c.created_at,
+                c.updated_at,
+                c.deleted_at
+            FROM comments c
+            JOIN users u ON u.id = c.author_id
+            WHERE c.id = $1 AND c.article_id = $2
+        "#,
+        id,
+        article_id,
+    )
+    .fetch_optional(pool)
+    .await
+    .trace_db("Failed to fetch comment")?;
+
+    Ok(comment)
+}
+
+/// Comments come oldest first as a flat list, clients rebuild the threads from `parent_id`.
+#[instrument(skip(pool))]
+async fn fetch_comments(
+    pool: &PgPool,
+    article_id: &Uuid,
+    query: &ListQuery,
+) -> AppResult<SearchType<Comment>> {
+    let keyset = KeysetPage::from_query(
+        query.limit.unwrap_or(50),
+        query.after.as_deref(),
+        query.before.as_deref(),
+        ORDER,
+    )?;
+
+    let filter_args = || {
+        let mut args = PgArguments::default();
+        args.add(article_id);
+        args
+    };
+
+    let mut builder = QueryBuilder::with_arguments(SELECT_SQL, filter_args());
+    keyset.push_sql(&mut builder);
+
+    let comments = builder
+        .build_query_as::<RawComment>()
+        .fetch_all(pool)
+        .await
+        .trace_db("Failed to fetch comments")?;
+
+    let count = keyset
+        .page
+        .count(
+            comments.len(),
+            pool,
+            query.count,
+            COUNT_FROM_SQL,
+            filter_args,
+        )
+        .await?;
+
+    Ok(db::into_keyset_search_type(
+        comments,
+        &keyset.page,
+        count,
+        |row| keyset.cursor_of(row.created_at, row.id),
+    ))
+}
@@ -1,23 +1,28 @@
 use axum::{extract::State, http::StatusCode, response::IntoResponse};
+use chrono::{NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sqlx::PgConnection;
 use tracing::instrument;
 use validator::Validate;
 
 use crate::{
     application::AppCtx,
     db::DbResultExt,
+    domains::article::ArticleStatus,
     extractors::{AuthUser, ValidateJson},
+    routes::tags::set_article_tags,
     utils::{
         err::AppError,
         jwt::UserData,
+        markdown,
         response::{AppResponse, AppResult, DataResponse},
+        tag::normalize_tags,
     },
 };
 
-use super::{Article, RawArticleFullCount};
+use super::{slugs::pick_slug, Article, RawArticle};
 
-#[derive(Deserialize, Validate, Debug, Serialize)]
+#[derive(Deserialize, Validate, Debug, Serialize, Default)]
 pub struct Payload {
     #[validate(length(min = 3))]
     pub title: String,
@@ -27,6 +32,12 @@ pub struct Payload {
 
     #[validate(length(min = 1))]
     pub tags: Option<Vec<String>>,
+
+    #[serde(default)]
+    pub status: ArticleStatus,
+
+    /// Required for `scheduled` articles, ignored otherwise.
+    pub published_at: Option<NaiveDateTime>,
 }
 
 #[instrument(skip(ctx))]
@@ -35,44 +46,126 @@ pub async fn create_article(
     AuthUser(user): AuthUser<UserData>,
     ValidateJson(payload): ValidateJson<Payload>,
 ) -> AppResponse {
-    let result = insert_new_article(&ctx.db, &payload, &user).await?;
+    validate_schedule(payload.status, payload.published_at)?;
+
+    let mut tx = ctx
+        .db
+        .begin()
+        .await
+        .trace_db("Failed to begin transaction")?;
+    let slug = pick_slug(&mut tx, user.user_id.as_ref(), &payload.title, None).await?;
+    let result = insert_new_article(&mut tx, &payload, &slug, &ctx.search_language, &user).await?;
+    tx.commit().await.trace_db("Failed to commit new article")?;
 
     Ok((StatusCode::CREATED, DataResponse::new(result)).into_response())
 }
 
+/// Scheduled articles need a publication time in the future; other statuses derive it.
+pub(super) fn validate_schedule(
+    status: ArticleStatus,
+    published_at: Option<NaiveDateTime>,
+) -> AppResult<()> {
+    match (status, published_at) {
+        (ArticleStatus::Scheduled, Some(at)) if at > Utc::now().naive_utc() => Ok(()),
+        (ArticleStatus::Scheduled, _) => Err(AppError::BadRequest(
+            "Scheduled articles require published_at in the future".to_string(),
+        )),
+        (_, Some(_)) => Err(AppError::BadRequest(
+            "published_at can only be set for scheduled articles".to_string(),
+        )),
+        (_, None) => Ok(()),
+    }
+}
+
 async fn insert_new_article(
-    pool: &PgPool,
+    conn: &mut PgConnection,
     payload: &Payload,
+    slug: &str,
+    search_language: &str,
     user: &UserData,
 ) -> AppResult<Article> {
-    let empty_tags = vec![];
+    let tags = normalize_tags(payload.tags.as_deref().unwrap_or_default());
+    let rendered = markdown::render(&payload.text);
 
-    sqlx::query_as!(
-        RawArticleFullCount,
+    let article = sqlx::query_as!(
+        RawArticle,
         r#"
             WITH inserted_article AS (
                 INSERT INTO articles (
                     author_id,
                     title,
                     text,
-                    tags
-                ) VALUES ($1, $2, $3, $4)
-                RETURNING id, author_id, title, text, tags, created_at, 0::BIGINT AS full_count
+                    tags,
+                    status,
+                    published_at,
+                    slug,
+                    search_language,
+                    html,
+                    word_count,
+                    reading_time_minutes
+                ) VALUES (
+                    $1, $2, $3, $4, $5::article_status,
+                    CASE $5::article_status
+                        WHEN 'published'::article_status THEN NOW()
+                        WHEN 'scheduled'::article_status THEN $6::TIMESTAMP
+                    END,
+                    $7,
+                    $8::TEXT::REGCONFIG,
+                    $9, $10, $11
+                )
+                RETURNING *
+            ),
+
+            inserted_revision AS (
+                INSERT INTO article_revisions (article_id, version, title, text, tags, editor_id, created_at)
+                SELECT id, version, title, text, tags, author_id, created_at FROM inserted_article
+            ),
+
+            inserted_slug AS (
+                INSERT INTO article_slugs (author_id, slug, article_id)
+                SELECT author_id, slug, id FROM inserted_article
             )
 
             SELECT
-                inserted_article.*,
-                u.username AS author_username
-            FROM inserted_article
+                a.id,
+                a.slug,
+                a.title,
+                a.text,
+                a.html,
+                a.word_count,
+                a.reading_time_minutes,
+                a.tags,
+                a.author_id,
+                a.created_at,
+                a.updated_at,
+                a.version,
+                a.status AS "status: ArticleStatus",
+                a.published_at,
+                a.comment_count,
+                u.username AS author_username,
+                u.display_name AS author_display_name,
+                u.avatar_url AS author_avatar_url,
+                NULL::TEXT AS headline
+            FROM inserted_article a
             JOIN users u ON u.id = $1
         "#,
         user.user_id.as_ref(),
         &payload.title,
         &payload.text,
-        &payload.tags.as_ref().unwrap_or(&empty_tags),
+        &tags,
+        payload.status as ArticleStatus,
+        payload.published_at,
+        slug,
+        search_language,
+        &rendered.html,
+        rendered.word_count,
+        rendered.reading_time_minutes,
     )
-    .fetch_one(pool)
+    .fetch_one(&mut *conn)
     .await
-    .map(|article| article.into())
-    .with_unique_violation(AppError::DuplicatedArticle, "Duplicated article title")
+    .with_unique_violation(AppError::DuplicatedArticle, "Duplicated article slug")?;
+
+    set_article_tags(conn, &article.id, &tags).await?;
+
+    Ok(article.into())
 }
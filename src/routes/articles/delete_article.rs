@@ -0,0 +1,42 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use sqlx::PgPool;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    application::AppCtx,
+    db::DbResultExt,
+    extractors::AuthUser,
+    utils::{
+        jwt::UserData,
+        response::{AppResponse, AppResult},
+    },
+};
+
+use super::update_article::ensure_author;
+
+#[instrument(skip(ctx))]
+pub async fn delete_article(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    Path(id): Path<Uuid>,
+) -> AppResponse {
+    ensure_author(&ctx.db, &id, &user.user_id).await?;
+    remove_article(&ctx.db, &id).await?;
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[instrument(skip(pool))]
+async fn remove_article(pool: &PgPool, id: &Uuid) -> AppResult<()> {
+    sqlx::query!("DELETE FROM articles WHERE id = $1", id)
+        .execute(pool)
+        .await
+        .trace_db("Failed to delete article")?;
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use std::slice;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use sqlx::PgPool;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    application::AppCtx,
+    db::DbResultExt,
+    domains::{article::ArticleStatus, user::UserID},
+    extractors::MaybeAuthUser,
+    utils::{
+        err::AppError,
+        jwt::UserData,
+        response::{AppResponse, AppResult, DataResponse},
+    },
+};
+
+use super::{reactions::attach_reactions, Article, RawArticle};
+
+#[instrument(skip(ctx))]
+pub async fn get_article(
+    ctx: State<AppCtx>,
+    MaybeAuthUser(user): MaybeAuthUser<UserData>,
+    Path(id): Path<Uuid>,
+) -> AppResponse {
+    let viewer = user.map(|user| user.user_id);
+    let mut article = fetch_visible_article(&ctx.db, &id, viewer.as_ref()).await?;
+    attach_reactions(&ctx.db, slice::from_mut(&mut article), viewer.as_ref()).await?;
+
+    Ok((StatusCode::OK, DataResponse::new(article)).into_response())
+}
+
+#[instrument(skip(pool))]
+pub(super) async fn fetch_article(pool: &PgPool, id: &Uuid) -> AppResult<Option<Article>> {
+    let article = sqlx::query_as!(
+        RawArticle,
+        r#"
+            SELECT
+                a.id,
+                a.slug,
+                a.title,
+                a.text,
+                a.html,
+                a.word_count,
+                a.reading_time_minutes,
+                a.tags,
+                a.author_id,
+                a.created_at,
+                a.updated_at,
+                a.version,
+                a.status AS "status: ArticleStatus",
+                a.published_at,
+                a.comment_count,
+                u.username AS author_username,
+                u.display_name AS author_display_name,
+                u.avatar_url AS author_avatar_url,
+                NULL::TEXT AS headline
+            FROM articles a
+            JOIN users u ON a.author_id = u.id
+            WHERE a.id = $1
+        "#,
+        id,
+    )
+    .fetch_optional(pool)
+    .await
+    .trace_db("Failed to fetch article")?;
+
+    Ok(article.map(Article::from))
+}
+
+/// Articles hidden from the viewer are reported as missing rather than forbidden.
+pub(crate) async fn fetch_visible_article(
+    pool: &PgPool,
+    id: &Uuid,
+    viewer: Option<&UserID>,
+) -> AppResult<Article> {
+    fetch_article(pool, id)
+        .await?
+        .filter(|article| article.is_visible_to(viewer))
+        .ok_or_else(|| article_not_found(id))
+}
+
+pub(crate) fn article_not_found(id: &Uuid) -> AppError {
+    AppError::NotFound(format!("Article not found: {id}"))
+}
@@ -5,80 +5,135 @@ use axum::{
     Json,
 };
 use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use sqlx::{postgres::PgArguments, Arguments, PgPool, QueryBuilder};
 use tracing::instrument;
 
 use crate::{
     application::AppCtx,
-    db::{self, DbResultExt},
+    db::{self, DbResultExt, KeysetPage, TimestampOrder},
     domains::user::UserID,
     extractors::AuthUser,
-    routes::articles::RawArticleFullCount,
-    types::SearchType,
+    routes::articles::RawArticle,
+    types::{CountMode, SearchType, SortingDirection},
     utils::{
         jwt::UserData,
         response::{AppResponse, AppResult},
     },
 };
 
-use super::Article;
+use super::{reactions::attach_reactions, Article};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Payload {
     pub user_id: Option<UserID>,
     pub limit: Option<u64>,
     pub offset: Option<u64>,
+    pub after: Option<String>,
+    pub before: Option<String>,
+
+    #[serde(default)]
+    pub count: CountMode,
 }
 
+const ORDER: TimestampOrder = TimestampOrder {
+    signature: "created_at DESC,id DESC",
+    timestamp: "a.created_at",
+    id: "a.id",
+    direction: SortingDirection::DESC,
+};
+
+const SELECT_SQL: &str = r#"
+    SELECT
+        a.id,
+        a.slug,
+        a.title,
+        a.text,
+        a.html,
+        a.word_count,
+        a.reading_time_minutes,
+        a.tags,
+        a.author_id,
+        a.created_at,
+        a.updated_at,
+        a.version,
+        a.status,
+        a.published_at,
+        a.comment_count,
+        u.username AS author_username,
+        u.display_name AS author_display_name,
+        u.avatar_url AS author_avatar_url,
+        NULL::TEXT AS headline
+"#;
+
+const FILTER_SQL: &str = r#"
+    FROM articles a
+    JOIN users u ON a.author_id = u.id
+    JOIN subscriptions s ON s.author_id = a.author_id
+    WHERE
+        s.subscriber_id = $1
+    AND
+        a.status = 'published'
+    AND
+        ($2::UUID IS NULL OR u.id = $2)
+"#;
+
 #[instrument(skip(ctx))]
 pub async fn get_subscribed(
     ctx: State<AppCtx>,
     AuthUser(user): AuthUser<UserData>,
     Query(query): Query<Payload>,
 ) -> AppResponse {
-    let data = get_subscribed_articles(&ctx.db, user.user_id, &query).await?;
+    let mut data = get_subscribed_articles(&ctx.db, user.user_id.clone(), &query).await?;
+    attach_reactions(&ctx.db, &mut data.results, Some(&user.user_id)).await?;
     Ok((StatusCode::OK, Json(data)).into_response())
 }
 
 #[instrument(skip(pool))]
-async fn get_subscribed_articles(
+pub(crate) async fn get_subscribed_articles(
     pool: &PgPool,
     user_id: UserID,
     payload: &Payload,
 ) -> AppResult<SearchType<Article>> {
-    let raw_articles = sqlx::query_as!(
-        RawArticleFullCount,
-        r#"
-            WITH cte AS (
-                SELECT
-                    a.id,
-                    a.title,
-                    a.text,
-                    a.tags,
-                    a.author_id,
-                    a.created_at,
-                    u.username AS author_username
-                FROM articles a
-                JOIN users u ON a.author_id = u.id
-                JOIN subscriptions s ON s.author_id = a.author_id
-                WHERE
-                    s.subscriber_id = $1
-                AND
-                    ($2::UUID IS NULL OR u.id = $2)
-                ORDER BY created_at DESC
-                LIMIT $3 OFFSET $4
-            )
+    let keyset = KeysetPage::from_query(
+        payload.limit.unwrap_or(20),
+        payload.after.as_deref(),
+        payload.before.as_deref(),
+        ORDER,
+    )?
+    .with_offset(payload.offset.unwrap_or(0));
+
+    let filter_args = || {
+        let mut args = PgArguments::default();
+        args.add(user_id.as_ref());
+        args.add(payload.user_id.as_ref().map(|i| i.as_ref()));
+        args
+    };
+
+    let mut builder = QueryBuilder::with_arguments(SELECT_SQL, filter_args());
+    builder.push(FILTER_SQL);
+    keyset.push_sql(&mut builder);
+
+    let raw_articles = builder
+        .build_query_as::<RawArticle>()
+        .fetch_all(pool)
+        .await
+        .trace_db("Failed to fetch list of articles")?;
 
-            SELECT cte.*, (SELECT COUNT(*) FROM cte) AS full_count FROM cte
-        "#,
-        user_id.as_ref(),
-        payload.user_id.as_ref().map(|i| i.as_ref()),
-        payload.limit.unwrap_or(20) as i64,
-        payload.offset.unwrap_or(0) as i64,
-    )
-    .fetch_all(pool)
-    .await
-    .trace_db("Failed to fetch list of articles")?;
+    let count = keyset
+        .page
+        .count(
+            raw_articles.len(),
+            pool,
+            payload.count,
+            FILTER_SQL,
+            filter_args,
+        )
+        .await?;
 
-    Ok(db::into_search_type(raw_articles))
+    Ok(db::into_keyset_search_type(
+        raw_articles,
+        &keyset.page,
+        count,
+        |row| keyset.cursor_of(row.created_at, row.id),
+    ))
 }
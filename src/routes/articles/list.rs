@@ -1,24 +1,29 @@
 use axum::{extract::State, http::StatusCode, response::IntoResponse, Json};
-use parse_display::Display;
-use serde::{Deserialize, Serialize};
-use sqlx::PgPool;
+use chrono::NaiveDateTime;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{json, Value};
+use sqlx::{postgres::PgArguments, Arguments, PgPool, Postgres, QueryBuilder};
 use tracing::instrument;
+use uuid::Uuid;
 use validator::Validate;
 
 use crate::{
     application::AppCtx,
-    db::{self, DbResultExt},
-    domains::user::Username,
+    db::{self, DbResultExt, KeysetPage},
+    domains::user::{UserID, Username},
     extractors::{MaybeAuthUser, ValidateJson},
-    routes::articles::RawArticleFullCount,
-    types::{SearchType, SortingDirection},
+    routes::articles::RawArticle,
+    types::{CountMode, SearchType, SortingDirection},
     utils::{
+        cursor::{invalid_cursor, Cursor},
+        err::AppError,
         jwt::UserData,
         response::{AppResponse, AppResult},
+        tag::{normalize_tag, normalize_tags},
     },
 };
 
-use super::Article;
+use super::{reactions::attach_reactions, Article};
 
 #[derive(Debug, Deserialize, Serialize, Validate, Default)]
 pub struct Payload {
@@ -27,6 +32,12 @@ pub struct Payload {
     #[validate(range(max = 100))]
     pub limit: Option<u64>,
 
+    /// `next_cursor` of the previous response.
+    pub after: Option<String>,
+
+    /// `prev_cursor` of the previous response.
+    pub before: Option<String>,
+
     #[validate(custom = "crate::parsers::user::validate_username")]
     pub author: Option<Username>,
 
@@ -36,34 +47,355 @@ pub struct Payload {
     #[validate(length(min = 1))]
     pub tag: Option<String>,
 
+    /// Articles with at least one of the tags.
+    #[validate(
+        length(min = 1, max = 20),
+        custom = "crate::parsers::article::validate_tag_filter"
+    )]
+    pub tags_any: Option<Vec<String>>,
+
+    /// Articles with every one of the tags.
+    #[validate(
+        length(min = 1, max = 20),
+        custom = "crate::parsers::article::validate_tag_filter"
+    )]
+    pub tags_all: Option<Vec<String>>,
+
+    /// Articles with none of the tags.
+    #[validate(
+        length(min = 1, max = 20),
+        custom = "crate::parsers::article::validate_tag_filter"
+    )]
+    pub exclude_tags: Option<Vec<String>>,
+
+    /// Articles by any of the authors, cannot be combined with `author`.
+    #[validate(
+        length(min = 1, max = 50),
+        custom = "crate::parsers::user::validate_usernames"
+    )]
+    pub authors: Option<Vec<Username>>,
+
+    /// Inclusive lower bound of `created_at`.
+    pub created_after: Option<NaiveDateTime>,
+
+    /// Exclusive upper bound of `created_at`.
+    pub created_before: Option<NaiveDateTime>,
+
+    /// Only articles with (`true`) or without (`false`) any tag.
+    pub has_tags: Option<bool>,
+
+    /// Full-text query in websearch syntax: `"exact phrase"`, `or`, `-excluded`.
+    #[validate(length(min = 1, max = 256))]
+    pub q: Option<String>,
+
     #[validate(length(min = 1))]
     pub order_by: Option<Vec<OrderBy>>,
+
+    #[serde(default)]
+    pub count: CountMode,
 }
 
 impl Payload {
-    fn format_order_by(&self) -> String {
-        let mut to_join = vec![];
+    /// Requested ordering, always ending with `id` so that every row has a distinct key.
+    fn sort_keys(&self) -> Vec<SortKey> {
+        let mut keys = vec![];
 
         if let Some(order_by) = &self.order_by {
             for item in order_by {
-                to_join.push(item.to_string());
+                keys.push(match item {
+                    OrderBy::CreatedAt { created_at } => {
+                        SortKey::new(SortColumn::CreatedAt, *created_at)
+                    }
+                    OrderBy::Username { username } => SortKey::new(SortColumn::Username, *username),
+                    OrderBy::MostLiked { most_liked } => {
+                        SortKey::new(SortColumn::Likes, *most_liked)
+                    }
+                });
             }
         } else {
-            to_join.push("created_at DESC".to_string());
+            if self.q.is_some() {
+                keys.push(SortKey::new(SortColumn::Rank, SortingDirection::DESC));
+            }
+            keys.push(SortKey::new(SortColumn::CreatedAt, SortingDirection::DESC));
+        }
+
+        let last_direction = keys
+            .last()
+            .map(|key| key.direction)
+            .unwrap_or(SortingDirection::DESC);
+        keys.push(SortKey::new(SortColumn::Id, last_direction));
+
+        keys
+    }
+
+    fn check_filters(&self) -> AppResult<()> {
+        if self.author.is_some() && self.authors.is_some() {
+            return Err(AppError::BadRequest(
+                "Only one of author and authors can be set".to_string(),
+            ));
+        }
+
+        match (self.created_after, self.created_before) {
+            (Some(after), Some(before)) if after >= before => Err(AppError::BadRequest(
+                "created_after must be before created_before".to_string(),
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    fn cursor(&self) -> AppResult<Option<&str>> {
+        let cursor = match (&self.after, &self.before) {
+            (Some(_), Some(_)) => {
+                return Err(AppError::BadRequest(
+                    "Only one of after and before can be set".to_string(),
+                ))
+            }
+            (after, before) => after.as_deref().or(before.as_deref()),
+        };
+
+        if cursor.is_some() && self.offset.unwrap_or(0) > 0 {
+            return Err(AppError::BadRequest(
+                "Cursors cannot be combined with offset".to_string(),
+            ));
         }
 
-        to_join.join(", ")
+        Ok(cursor)
     }
 }
 
-#[derive(Debug, Deserialize, Serialize, Display)]
+#[derive(Debug, Deserialize, Serialize)]
 #[serde(untagged)]
 pub enum OrderBy {
-    #[display("created_at {created_at}")]
     CreatedAt { created_at: SortingDirection },
 
-    #[display("username {username}")]
     Username { username: SortingDirection },
+
+    MostLiked { most_liked: SortingDirection },
+}
+
+#[derive(Debug, Clone, Copy)]
+enum SortColumn {
+    CreatedAt,
+    Username,
+    Likes,
+    Rank,
+    Id,
+}
+
+impl SortColumn {
+    fn name(self) -> &'static str {
+        match self {
+            SortColumn::CreatedAt => "created_at",
+            SortColumn::Username => "username",
+            SortColumn::Likes => "like_count",
+            SortColumn::Rank => "rank",
+            SortColumn::Id => "id",
+        }
+    }
+
+    fn expr(self) -> &'static str {
+        match self {
+            SortColumn::CreatedAt => "a.created_at",
+            SortColumn::Username => "u.username",
+            SortColumn::Likes => "a.like_count",
+            SortColumn::Rank => "ts_rank(a.search, search.query)",
+            SortColumn::Id => "a.id",
+        }
+    }
+
+    fn value(self, row: &ArticleRow) -> Value {
+        match self {
+            SortColumn::CreatedAt => json!(row.article.created_at),
+            SortColumn::Username => json!(row.article.author_username),
+            SortColumn::Likes => json!(row.like_count),
+            SortColumn::Rank => json!(row.rank),
+            SortColumn::Id => json!(row.article.id),
+        }
+    }
+
+    fn push_bind(self, builder: &mut QueryBuilder<Postgres>, value: &Value) -> AppResult<()> {
+        match self {
+            SortColumn::CreatedAt => builder.push_bind(decode::<NaiveDateTime>(value)?),
+            SortColumn::Username => builder.push_bind(decode::<String>(value)?),
+            SortColumn::Likes => builder.push_bind(decode::<i32>(value)?),
+            SortColumn::Rank => builder.push_bind(decode::<f32>(value)?),
+            SortColumn::Id => builder.push_bind(decode::<Uuid>(value)?),
+        };
+
+        Ok(())
+    }
+}
+
+fn decode<T: DeserializeOwned>(value: &Value) -> AppResult<T> {
+    T::deserialize(value).map_err(|_| invalid_cursor())
+}
+
+#[derive(Debug, Clone, Copy)]
+struct SortKey {
+    column: SortColumn,
+    direction: SortingDirection,
+}
+
+impl SortKey {
+    fn new(column: SortColumn, direction: SortingDirection) -> Self {
+        Self { column, direction }
+    }
+}
+
+fn order_signature(keys: &[SortKey]) -> String {
+    keys.iter()
+        .map(|key| format!("{} {}", key.column.name(), key.direction))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn push_order_by(builder: &mut QueryBuilder<Postgres>, keys: &[SortKey]) {
+    let mut order_by = builder.separated(", ");
+    for key in keys {
+        order_by.push(key.column.expr());
+        order_by.push_unseparated(match key.direction {
+            SortingDirection::ASC => " ASC",
+            SortingDirection::DESC => " DESC",
+        });
+    }
+}
+
+/// Rows strictly after the cursor in the given ordering, expanded into
+/// `(k1 > $a) OR (k1 = $a AND k2 > $b) OR ...` since the directions can be mixed.
+fn push_keyset(
+    builder: &mut QueryBuilder<Postgres>,
+    keys: &[SortKey],
+    values: &[Value],
+) -> AppResult<()> {
+    for (i, key) in keys.iter().enumerate() {
+        builder.push(if i == 0 { "(" } else { " OR (" });
+
+        for (previous, value) in keys[..i].iter().zip(values) {
+            builder.push(previous.column.expr()).push(" = ");
+            previous.column.push_bind(builder, value)?;
+            builder.push(" AND ");
+        }
+
+        let op = match key.direction {
+            SortingDirection::ASC => " > ",
+            SortingDirection::DESC => " < ",
+        };
+        builder.push(key.column.expr()).push(op);
+        key.column.push_bind(builder, &values[i])?;
+        builder.push(")");
+    }
+
+    Ok(())
+}
+
+/// Filters shared by the page and the count query, which bind `$1`..`$13` the same way.
+const FILTER_SQL: &str = r#"
+    FROM articles a
+    JOIN users u ON a.author_id = u.id
+    CROSS JOIN (
+        SELECT websearch_to_tsquery($6::TEXT::REGCONFIG, COALESCE($5, '')) AS query
+    ) search
+    WHERE
+        ($1::TEXT IS NULL OR u.username = $1)
+    AND
+        ($2::TEXT IS NULL OR a.title LIKE $2 || '%')
+    AND
+        ($3::TEXT IS NULL OR EXISTS (
+            SELECT 1
+            FROM article_tags at
+            JOIN tags t ON t.id = at.tag_id
+            WHERE at.article_id = a.id AND t.name = $3
+        ))
+    AND
+        (a.status = 'published' OR a.author_id = $4)
+    AND
+        ($5::TEXT IS NULL OR a.search @@ search.query)
+    AND
+        ($7::TEXT[] IS NULL OR EXISTS (
+            SELECT 1
+            FROM article_tags at
+            JOIN tags t ON t.id = at.tag_id
+            WHERE at.article_id = a.id AND t.name = ANY($7)
+        ))
+    AND
+        ($8::TEXT[] IS NULL OR cardinality($8) = (
+            SELECT COUNT(*)
+            FROM article_tags at
+            JOIN tags t ON t.id = at.tag_id
+            WHERE at.article_id = a.id AND t.name = ANY($8)
+        ))
+    AND
+        ($9::TEXT[] IS NULL OR NOT EXISTS (
+            SELECT 1
+            FROM article_tags at
+            JOIN tags t ON t.id = at.tag_id
+            WHERE at.article_id = a.id AND t.name = ANY($9)
+        ))
+    AND
+        ($10::TEXT[] IS NULL OR u.username = ANY($10))
+    AND
+        ($11::TIMESTAMP IS NULL OR a.created_at >= $11)
+    AND
+        ($12::TIMESTAMP IS NULL OR a.created_at < $12)
+    AND
+        ($13::BOOLEAN IS NULL OR $13 = EXISTS (
+            SELECT 1 FROM article_tags at WHERE at.article_id = a.id
+        ))
+"#;
+
+/// Columns of `ArticleRow`, followed by `FILTER_SQL` in the page query.
+const SELECT_SQL: &str = r#"
+    SELECT
+        a.id,
+        a.slug,
+        a.title,
+        a.text,
+        a.html,
+        a.word_count,
+        a.reading_time_minutes,
+        a.tags,
+        a.author_id,
+        a.created_at,
+        a.updated_at,
+        a.version,
+        a.status,
+        a.published_at,
+        a.comment_count,
+        a.like_count,
+        u.username AS author_username,
+        u.display_name AS author_display_name,
+        u.avatar_url AS author_avatar_url,
+        CASE WHEN $5::TEXT IS NOT NULL
+            THEN ts_rank(a.search, search.query)
+        END AS rank,
+        CASE WHEN $5::TEXT IS NOT NULL
+            THEN ts_headline(
+                a.search_language,
+                -- Escaped so that only the <mark> tags around matches are markup
+                REPLACE(REPLACE(REPLACE(REPLACE(REPLACE(a.text,
+                    '&', '&amp;'),
+                    '<', '&lt;'),
+                    '>', '&gt;'),
+                    '"', '&quot;'),
+                    '''', '&#39;'),
+                search.query,
+                'MaxFragments=2, StartSel=<mark>, StopSel=</mark>'
+            )
+        END AS headline
+"#;
+
+#[derive(sqlx::FromRow)]
+struct ArticleRow {
+    #[sqlx(flatten)]
+    article: RawArticle,
+    like_count: i32,
+    rank: Option<f32>,
+}
+
+impl From<ArticleRow> for Article {
+    fn from(row: ArticleRow) -> Self {
+        row.article.into()
+    }
 }
 
 #[instrument(skip(ctx))]
@@ -72,49 +404,108 @@ pub async fn list_articles(
     ctx: State<AppCtx>,
     ValidateJson(query): ValidateJson<Payload>,
 ) -> AppResponse {
-    let data = get_articles_list(&query, &ctx.db).await?;
+    let viewer = user.map(|user| user.user_id);
+    let mut data =
+        get_articles_list(&query, viewer.as_ref(), &ctx.search_language, &ctx.db).await?;
+    attach_reactions(&ctx.db, &mut data.results, viewer.as_ref()).await?;
 
     Ok((StatusCode::OK, Json(data)).into_response())
 }
 
 #[instrument(skip(pool))]
-async fn get_articles_list(query: &Payload, pool: &PgPool) -> AppResult<SearchType<Article>> {
-    let order_by = query.format_order_by();
-
-    let raw_articles = sqlx::query_as::<_, RawArticleFullCount>(&format!(
-        r#"
-                WITH cte AS (
-                    SELECT
-                        a.id,
-                        a.title,
-                        a.text,
-                        a.tags,
-                        a.author_id,
-                        a.created_at,
-                        u.username AS author_username
-                    FROM articles a
-                    JOIN users u ON a.author_id = u.id
-                    WHERE
-                        ($1::TEXT IS NULL OR u.username = $1)
-                    AND
-                        ($2::TEXT IS NULL OR a.title LIKE $2 || '%')
-                    AND
-                        ($3::TEXT IS NULL OR a.tags @> array[$3])
-                    ORDER BY {order_by}
-                    LIMIT $4 OFFSET $5
-                )
-
-                SELECT cte.*, (SELECT COUNT(*) FROM cte) AS full_count FROM cte
-            "#,
-    ))
-    .bind(query.author.as_ref().map(|u| u.as_ref()))
-    .bind(&query.title)
-    .bind(&query.tag)
-    .bind(query.limit.unwrap_or(20) as i64)
-    .bind(query.offset.unwrap_or(0) as i64)
-    .fetch_all(pool)
-    .await
-    .trace_db("Failed to fetch list of articles")?;
-
-    Ok(db::into_search_type(raw_articles))
+pub(crate) async fn get_articles_list(
+    query: &Payload,
+    viewer: Option<&UserID>,
+    search_language: &str,
+    pool: &PgPool,
+) -> AppResult<SearchType<Article>> {
+    query.check_filters()?;
+
+    let keys = query.sort_keys();
+    let signature = order_signature(&keys);
+    let cursor = query
+        .cursor()?
+        .map(|cursor| Cursor::decode(cursor, &signature))
+        .transpose()?;
+
+    let page = KeysetPage {
+        limit: query.limit.unwrap_or(20) as usize,
+        backward: query.before.is_some(),
+        has_previous: query.after.is_some() || query.offset.unwrap_or(0) > 0,
+    };
+
+    // Going backward walks the reversed ordering from the cursor, rows are flipped afterwards
+    let effective_keys: Vec<SortKey> = keys
+        .iter()
+        .map(|key| match page.backward {
+            true => SortKey::new(key.column, key.direction.reversed()),
+            false => *key,
+        })
+        .collect();
+
+    let tag = query.tag.as_deref().map(normalize_tag);
+    let tags_any = query.tags_any.as_deref().map(normalize_tags);
+    let tags_all = query.tags_all.as_deref().map(normalize_tags);
+    let exclude_tags = query.exclude_tags.as_deref().map(normalize_tags);
+    let authors = query
+        .authors
+        .as_ref()
+        .map(|authors| authors.iter().map(|u| u.0.as_str()).collect::<Vec<_>>());
+
+    let filter_args = || {
+        let mut args = PgArguments::default();
+        args.add(query.author.as_ref().map(|u| u.as_ref()));
+        args.add(&query.title);
+        args.add(&tag);
+        args.add(viewer.map(|id| id.0));
+        args.add(&query.q);
+        args.add(search_language);
+        args.add(&tags_any);
+        args.add(&tags_all);
+        args.add(&exclude_tags);
+        args.add(&authors);
+        args.add(query.created_after);
+        args.add(query.created_before);
+        args.add(query.has_tags);
+        args
+    };
+
+    let mut builder = QueryBuilder::with_arguments(SELECT_SQL, filter_args());
+    builder.push(FILTER_SQL);
+
+    match &cursor {
+        Some(cursor) if cursor.key.len() == keys.len() => {
+            builder.push(" AND (");
+            push_keyset(&mut builder, &effective_keys, &cursor.key)?;
+            builder.push(")");
+        }
+        Some(_) => return Err(invalid_cursor()),
+        None => (),
+    }
+
+    builder.push(" ORDER BY ");
+    push_order_by(&mut builder, &effective_keys);
+    builder
+        .push(" LIMIT ")
+        .push_bind(page.limit as i64 + 1)
+        .push(" OFFSET ")
+        .push_bind(query.offset.unwrap_or(0) as i64);
+
+    let rows = builder
+        .build_query_as::<ArticleRow>()
+        .fetch_all(pool)
+        .await
+        .trace_db("Failed to fetch list of articles")?;
+
+    let count = page
+        .count(rows.len(), pool, query.count, FILTER_SQL, filter_args)
+        .await?;
+
+    Ok(db::into_keyset_search_type(rows, &page, count, |row| {
+        Cursor {
+            order: signature.clone(),
+            key: keys.iter().map(|key| key.column.value(row)).collect(),
+        }
+        .encode()
+    }))
 }
@@ -1,5 +1,5 @@
 use axum::{
-    routing::{get, post},
+    routing::{delete, get, patch, post, put},
     Router,
 };
 use chrono::NaiveDateTime;
@@ -8,66 +8,130 @@ use uuid::Uuid;
 
 use crate::{
     application::AppCtx,
-    db::Total,
-    domains::user::{UserID, Username},
+    domains::{
+        article::ArticleStatus,
+        user::{UserID, Username},
+    },
+    utils::markdown,
 };
 
+pub mod assets;
+pub mod bookmarks;
+pub mod comments;
 pub mod create_article;
+pub mod delete_article;
+pub mod get_article;
 pub mod get_subscribed;
 pub mod list;
+pub mod reactions;
+pub mod revisions;
+pub mod slugs;
 pub mod subscribe;
+pub mod update_article;
 
 use create_article::create_article;
+use delete_article::delete_article;
+use get_article::get_article;
 use get_subscribed::get_subscribed;
 use list::list_articles;
+use reactions::ReactionCount;
+use update_article::update_article;
 
 #[derive(Deserialize, Serialize)]
 pub struct Article {
     pub id: String,
+    pub slug: String,
     pub author: Author,
     pub text: String,
+    /// `text` rendered from CommonMark and sanitized.
+    pub html: String,
+    pub word_count: i32,
+    pub reading_time_minutes: i32,
     pub title: String,
     pub tags: Vec<String>,
     pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+    pub version: i32,
+    pub status: ArticleStatus,
+    pub published_at: Option<NaiveDateTime>,
+    /// Comments that are not deleted.
+    pub comment_count: i32,
+
+    /// Counts per reaction kind, kinds nobody used are left out.
+    #[serde(default)]
+    pub reactions: Vec<ReactionCount>,
+
+    /// Matching fragments of the text as HTML-escaped text with matches wrapped in `<mark>`,
+    /// only present for full-text searches.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub headline: Option<String>,
+}
+
+impl Article {
+    /// Only published articles are visible to anyone but their author.
+    pub fn is_visible_to(&self, viewer: Option<&UserID>) -> bool {
+        self.status == ArticleStatus::Published || viewer == Some(&self.author.id)
+    }
 }
 
 #[derive(Deserialize, Serialize)]
 pub struct Author {
     pub id: UserID,
     pub username: Username,
+    pub display_name: Option<String>,
+    pub avatar_url: Option<String>,
 }
 
 #[derive(sqlx::FromRow)]
-struct RawArticleFullCount {
-    full_count: Option<i64>,
+struct RawArticle {
     id: Uuid,
+    slug: String,
     text: String,
+    html: Option<String>,
+    word_count: i32,
+    reading_time_minutes: i32,
     title: String,
     tags: Option<Vec<String>>,
     created_at: NaiveDateTime,
+    updated_at: NaiveDateTime,
+    version: i32,
+    status: ArticleStatus,
+    published_at: Option<NaiveDateTime>,
+    comment_count: i32,
     author_id: Uuid,
     author_username: String,
+    author_display_name: Option<String>,
+    author_avatar_url: Option<String>,
+    headline: Option<String>,
 }
 
-impl Into<Article> for RawArticleFullCount {
-    fn into(self) -> Article {
-        return Article {
-            id: self.id.to_string(),
+impl From<RawArticle> for Article {
+    fn from(raw: RawArticle) -> Self {
+        Article {
+            id: raw.id.to_string(),
+            slug: raw.slug,
             author: Author {
-                id: UserID(self.author_id),
-                username: Username(self.author_username),
+                id: UserID(raw.author_id),
+                username: Username(raw.author_username),
+                display_name: raw.author_display_name,
+                avatar_url: raw.author_avatar_url,
             },
-            text: self.text,
-            title: self.title,
-            tags: self.tags.unwrap_or(vec![]),
-            created_at: self.created_at,
-        };
-    }
-}
-
-impl Total for RawArticleFullCount {
-    fn total(&self) -> usize {
-        return self.full_count.unwrap_or(0) as usize;
+            // Rows written before rendering existed are rendered on the fly until backfilled
+            html: raw.html.unwrap_or_else(|| markdown::render(&raw.text).html),
+            text: raw.text,
+            word_count: raw.word_count,
+            reading_time_minutes: raw.reading_time_minutes,
+            title: raw.title,
+            tags: raw.tags.unwrap_or(vec![]),
+            created_at: raw.created_at,
+            updated_at: raw.updated_at,
+            version: raw.version,
+            status: raw.status,
+            published_at: raw.published_at,
+            comment_count: raw.comment_count,
+            reactions: vec![],
+            headline: raw.headline,
+        }
     }
 }
 
@@ -76,5 +140,63 @@ pub fn routes() -> Router<AppCtx> {
         .route("/articles", post(create_article))
         .route("/articles/get-articles", post(list_articles))
         .route("/articles/subscribe", post(subscribe::subscribe))
+        .route(
+            "/articles/subscribe/:author_id",
+            delete(subscribe::unsubscribe),
+        )
         .route("/articles/get-subscribed", get(get_subscribed))
+        .route(
+            "/articles/:id",
+            get(get_article)
+                .patch(update_article)
+                .delete(delete_article),
+        )
+        .route("/articles/by-slug/:author/:slug", get(slugs::get_by_slug))
+        .route("/articles/:id/revisions", get(revisions::list_revisions))
+        .route(
+            "/articles/:id/revisions/diff",
+            get(revisions::diff_revisions),
+        )
+        .route("/articles/:id/revisions/:rev", get(revisions::get_revision))
+        .route(
+            "/articles/:id/revisions/:rev/restore",
+            post(revisions::restore_revision),
+        )
+        .route(
+            "/articles/:id/comments",
+            get(comments::list_comments).post(comments::create_comment),
+        )
+        .route(
+            "/articles/:id/comments/:comment_id",
+            patch(comments::update_comment).delete(comments::delete_comment),
+        )
+        .route(
+            "/articles/:id/reactions/:kind",
+            put(reactions::add_reaction).delete(reactions::remove_reaction),
+        )
+        .route(
+            "/articles/:id/assets",
+            get(assets::list_assets).post(assets::add_asset),
+        )
+        .route(
+            "/articles/:id/assets/:upload_id",
+            delete(assets::remove_asset),
+        )
+        .route(
+            "/articles/:id/bookmark",
+            put(bookmarks::add_bookmark).delete(bookmarks::remove_bookmark),
+        )
+        .route("/me/bookmarks", get(bookmarks::list_bookmarks))
+        .route(
+            "/me/bookmarks/collections",
+            get(bookmarks::list_collections).post(bookmarks::create_collection),
+        )
+        .route(
+            "/me/bookmarks/collections/order",
+            put(bookmarks::reorder_collections),
+        )
+        .route(
+            "/me/bookmarks/collections/:id",
+            patch(bookmarks::rename_collection).delete(bookmarks::delete_collection),
+        )
 }
@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    application::AppCtx,
+    db::DbResultExt,
+    domains::{article::ReactionKind, user::UserID},
+    extractors::AuthUser,
+    utils::{
+        jwt::UserData,
+        response::{AppResponse, AppResult, DataResponse},
+    },
+};
+
+use super::{get_article::fetch_visible_article, Article};
+
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+pub struct ReactionCount {
+    pub kind: ReactionKind,
+    pub count: i64,
+
+    /// Only present when the request is authenticated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reacted_by_me: Option<bool>,
+}
+
+/// Reacting twice with the same kind is a no-op.
+#[instrument(skip(ctx))]
+pub async fn add_reaction(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    Path((id, kind)): Path<(Uuid, ReactionKind)>,
+) -> AppResponse {
+    fetch_visible_article(&ctx.db, &id, Some(&user.user_id)).await?;
+
+    let mut tx = ctx
+        .db
+        .begin()
+        .await
+        .trace_db("Failed to begin transaction")?;
+
+    let added = sqlx::query!(
+        r#"
+            INSERT INTO article_reactions (article_id, user_id, kind)
+            VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING
+        "#,
+        id,
+        user.user_id.as_ref(),
+        kind as ReactionKind,
+    )
+    .execute(&mut *tx)
+    .await
+    .trace_db("Failed to add reaction")?
+    .rows_affected();
+
+    if added > 0 && kind == ReactionKind::Like {
+        sqlx::query!(
+            "UPDATE articles SET like_count = like_count + 1 WHERE id = $1",
+            id,
+        )
+        .execute(&mut *tx)
+        .await
+        .trace_db("Failed to update like count")?;
+    }
+
+    tx.commit().await.trace_db("Failed to commit reaction")?;
+
+    let reactions = fetch_reactions(&ctx.db, &[id], Some(&user.user_id))
+        .await?
+        .remove(&id)
+        .unwrap_or_default();
+
+    Ok((StatusCode::OK, DataResponse::new(reactions)).into_response())
+}
+
+#[instrument(skip(ctx))]
+pub async fn remove_reaction(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    Path((id, kind)): Path<(Uuid, ReactionKind)>,
+) -> AppResponse {
+    fetch_visible_article(&ctx.db, &id, Some(&user.user_id)).await?;
+
+    let mut tx = ctx
+        .db
+        .begin()
+        .await
+        .trace_db("Failed to begin transaction")?;
+
+    let removed = sqlx::query!(
+        r#"
+            DELETE FROM article_reactions
+            WHERE article_id = $1 AND user_id = $2 AND kind = $3
+        "#,
+        id,
+        user.user_id.as_ref(),
+        kind as ReactionKind,
+    )
+    .execute(&mut *tx)
+    .await
+    .trace_db("Failed to remove reaction")?
+    .rows_affected();
+
+    if removed > 0 && kind == ReactionKind::Like {
+        sqlx::query!(
+            "UPDATE articles SET like_count = like_count - 1 WHERE id = $1",
+            id,
+        )
+        .execute(&mut *tx)
+        .await
+        .trace_db("Failed to update like count")?;
+    }
+
+    tx.commit()
+        .await
+        .trace_db("Failed to commit reaction removal")?;
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+/// Fills in `Article::reactions` for all the given articles with a single query.
+pub(crate) async fn attach_reactions(
+    pool: &PgPool,
+    articles: &mut [Article],
+    viewer: Option<&UserID>,
+) -> AppResult<()> {
+    let ids: Vec<Uuid> = articles
+        .iter()
+        .filter_map(|article| article.id.parse().ok())
+        .collect();
+
+    if ids.is_empty() {
+        return Ok(());
+    }
+
+    let mut reactions = fetch_reactions(pool, &ids, viewer).await?;
+
+    for article in articles.iter_mut() {
+        if let Some(counts) = article
+            .id
+            .parse()
+            .ok()
+            .and_then(|id: Uuid| reactions.remove(&id))
+        {
+            article.reactions = counts;
+        }
+    }
+
+    Ok(())
+}
+
+#[instrument(skip(pool))]
+async fn fetch_reactions(
+    pool: &PgPool,
+    ids: &[Uuid],
+    viewer: Option<&UserID>,
+) -> AppResult<HashMap<Uuid, Vec<ReactionCount>>> {
+    let rows = sqlx::query!(
+        r#"
+            SELECT
+                article_id,
+                kind AS "kind: ReactionKind",
+                COUNT(*) AS "count!",
+                BOOL_OR(user_id = $2) AS reacted_by_me
+            FROM article_reactions
+            WHERE article_id = ANY($1)
+            GROUP BY article_id, kind
+            ORDER BY kind
+        "#,
+        ids,
+        viewer.map(|id| id.0),
+    )
+    .fetch_all(pool)
+    .await
+    .trace_db("Failed to fetch reactions")?;
+
+    let mut reactions: HashMap<Uuid, Vec<ReactionCount>> = HashMap::new();
+    for row in rows {
+        reactions
+            .entry(row.article_id)
+            .or_default()
+            .push(ReactionCount {
+                kind: row.kind,
+                count: row.count,
+                reacted_by_me: row.reacted_by_me,
+            });
+    }
+
+    Ok(reactions)
+}
@@ -0,0 +1,263 @@
+use std::slice;
+
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use similar::{ChangeTag, TextDiff};
+use sqlx::PgPool;
+use tracing::instrument;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    application::AppCtx,
+    db::DbResultExt,
+    domains::user::{UserID, Username},
+    extractors::{AuthUser, MaybeAuthUser, ValidateJson},
+    utils::{
+        err::AppError,
+        jwt::UserData,
+        response::{AppResponse, AppResult, DataResponse},
+    },
+};
+
+use super::{
+    get_article::{article_not_found, fetch_article, fetch_visible_article},
+    reactions::attach_reactions,
+    update_article::{apply_update, ensure_author, ArticleChanges},
+    Author,
+};
+
+#[derive(Deserialize, Serialize)]
+pub struct Revision {
+    pub version: i32,
+    pub title: String,
+    pub text: String,
+    pub tags: Vec<String>,
+    pub editor: Author,
+    pub created_at: NaiveDateTime,
+}
+
+struct RawRevision {
+    version: i32,
+    title: String,
+    text: String,
+    tags: Option<Vec<String>>,
+    editor_id: Uuid,
+    editor_username: String,
+    editor_display_name: Option<String>,
+    editor_avatar_url: Option<String>,
+    created_at: NaiveDateTime,
+}
+
+impl From<RawRevision> for Revision {
+    fn from(raw: RawRevision) -> Self {
+        Revision {
+            version: raw.version,
+            title: raw.title,
+            text: raw.text,
+            tags: raw.tags.unwrap_or_default(),
+            editor: Author {
+                id: UserID(raw.editor_id),
+                username: Username(raw.editor_username),
+                display_name: raw.editor_display_name,
+                avatar_url: raw.editor_avatar_url,
+            },
+            created_at: raw.created_at,
+        }
+    }
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct RevisionDiff {
+    pub from: i32,
+    pub to: i32,
+    pub lines: Vec<DiffLine>,
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+pub struct DiffLine {
+    pub kind: DiffKind,
+    pub text: String,
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum DiffKind {
+    Equal,
+    Insert,
+    Delete,
+}
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct DiffQuery {
+    pub from: i32,
+    pub to: i32,
+}
+
+#[derive(Deserialize, Serialize, Validate, Debug)]
+pub struct RestorePayload {
+    /// Current version of the article, as for a regular update.
+    pub version: i32,
+}
+
+#[instrument(skip(ctx))]
+pub async fn list_revisions(
+    ctx: State<AppCtx>,
+    MaybeAuthUser(user): MaybeAuthUser<UserData>,
+    Path(id): Path<Uuid>,
+) -> AppResponse {
+    let viewer = user.map(|user| user.user_id);
+    fetch_visible_article(&ctx.db, &id, viewer.as_ref()).await?;
+
+    let revisions = fetch_revisions(&ctx.db, &id).await?;
+
+    Ok((StatusCode::OK, DataResponse::new(revisions)).into_response())
+}
+
+#[instrument(skip(ctx))]
+pub async fn get_revision(
+    ctx: State<AppCtx>,
+    MaybeAuthUser(user): MaybeAuthUser<UserData>,
+    Path((id, rev)): Path<(Uuid, i32)>,
+) -> AppResponse {
+    let viewer = user.map(|user| user.user_id);
+    fetch_visible_article(&ctx.db, &id, viewer.as_ref()).await?;
+
+    let revision = fetch_revision(&ctx.db, &id, rev).await?;
+
+    Ok((StatusCode::OK, DataResponse::new(revision)).into_response())
+}
+
+#[instrument(skip(ctx))]
+pub async fn diff_revisions(
+    ctx: State<AppCtx>,
+    MaybeAuthUser(user): MaybeAuthUser<UserData>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<DiffQuery>,
+) -> AppResponse {
+    let viewer = user.map(|user| user.user_id);
+    fetch_visible_article(&ctx.db, &id, viewer.as_ref()).await?;
+
+    let from = fetch_revision(&ctx.db, &id, query.from).await?;
+    let to = fetch_revision(&ctx.db, &id, query.to).await?;
+
+    let diff = RevisionDiff {
+        from: from.version,
+        to: to.version,
+        lines: diff_lines(&from.text, &to.text),
+    };
+
+    Ok((StatusCode::OK, DataResponse::new(diff)).into_response())
+}
+
+/// Restoring creates a new revision with the old content, so history is never rewritten.
+#[instrument(skip(ctx))]
+pub async fn restore_revision(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    Path((id, rev)): Path<(Uuid, i32)>,
+    ValidateJson(payload): ValidateJson<RestorePayload>,
+) -> AppResponse {
+    ensure_author(&ctx.db, &id, &user.user_id).await?;
+    let revision = fetch_revision(&ctx.db, &id, rev).await?;
+
+    let mut tx = ctx
+        .db
+        .begin()
+        .await
+        .trace_db("Failed to begin transaction")?;
+    let changes = ArticleChanges {
+        title: Some(&revision.title),
+        text: Some(&revision.text),
+        tags: Some(&revision.tags),
+        ..Default::default()
+    };
+    apply_update(&mut tx, &id, &changes, payload.version, &user.user_id).await?;
+    tx.commit()
+        .await
+        .trace_db("Failed to commit article restore")?;
+
+    let mut article = fetch_article(&ctx.db, &id)
+        .await?
+        .ok_or_else(|| article_not_found(&id))?;
+    attach_reactions(&ctx.db, slice::from_mut(&mut article), Some(&user.user_id)).await?;
+
+    Ok((StatusCode::OK, DataResponse::new(article)).into_response())
+}
+
+fn diff_lines(from: &str, to: &str) -> Vec<DiffLine> {
+    TextDiff::from_lines(from, to)
+        .iter_all_changes()
+        .map(|change| DiffLine {
+            kind: match change.tag() {
+                ChangeTag::Equal => DiffKind::Equal,
+                ChangeTag::Insert => DiffKind::Insert,
+                ChangeTag::Delete => DiffKind::Delete,
+            },
+            text: change.value().trim_end_matches('\n').to_string(),
+        })
+        .collect()
+}
+
+#[instrument(skip(pool))]
+async fn fetch_revisions(pool: &PgPool, id: &Uuid) -> AppResult<Vec<Revision>> {
+    let revisions = sqlx::query_as!(
+        RawRevision,
+        r#"
+            SELECT
+                r.version,
+                r.title,
+                r.text,
+                r.tags,
+                r.editor_id,
+                u.username AS editor_username,
+                u.display_name AS editor_display_name,
+                u.avatar_url AS editor_avatar_url,
+                r.created_at
+            FROM article_revisions r
+            JOIN users u ON u.id = r.editor_id
+            WHERE r.article_id = $1
+            ORDER BY r.version DESC
+        "#,
+        id,
+    )
+    .fetch_all(pool)
+    .await
+    .trace_db("Failed to fetch article revisions")?;
+
+    Ok(revisions.into_iter().map(Revision::from).collect())
+}
+
+#[instrument(skip(pool))]
+async fn fetch_revision(pool: &PgPool, id: &Uuid, version: i32) -> AppResult<Revision> {
+    sqlx::query_as!(
+        RawRevision,
+        r#"
+            SELECT
+                r.version,
+                r.title,
+                r.text,
+                r.tags,
+                r.editor_id,
+                u.username AS editor_username,
+                u.display_name AS editor_display_name,
+                u.avatar_url AS editor_avatar_url,
+                r.created_at
+            FROM article_revisions r
+            JOIN users u ON u.id = r.editor_id
+            WHERE r.article_id = $1 AND r.version = $2
+        "#,
+        id,
+        version,
+    )
+    .fetch_optional(pool)
+    .await
+    .trace_db("Failed to fetch article revision")?
+    .map(Revision::from)
+    .ok_or_else(|| AppError::NotFound(format!("Revision {version} of article {id} not found")))
+}
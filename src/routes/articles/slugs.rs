@@ -0,0 +1,133 @@
+use std::slice;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::{IntoResponse, Redirect},
+};
+use sqlx::{PgConnection, PgPool};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    application::AppCtx,
+    db::DbResultExt,
+    domains::user::Username,
+    extractors::MaybeAuthUser,
+    utils::{
+        err::AppError,
+        jwt::UserData,
+        response::{AppResponse, AppResult, DataResponse},
+        slug::{slugify, unique_slug},
+        url::path_segment,
+    },
+};
+
+use super::{get_article::fetch_visible_article, reactions::attach_reactions};
+
+/// Returns the article for its current slug and redirects from any slug it had before.
+#[instrument(skip(ctx))]
+pub async fn get_by_slug(
+    ctx: State<AppCtx>,
+    MaybeAuthUser(user): MaybeAuthUser<UserData>,
+    Path((author, slug)): Path<(Username, String)>,
+) -> AppResponse {
+    let article_id = find_article_id(&ctx.db, &author, &slug)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Article not found: {author}/{slug}")))?;
+
+    let viewer = user.map(|user| user.user_id);
+    let mut article = fetch_visible_article(&ctx.db, &article_id, viewer.as_ref()).await?;
+
+    if article.slug != slug {
+        let location = format!(
+            "/articles/by-slug/{}/{}",
+            path_segment(author.as_ref()),
+            path_segment(&article.slug)
+        );
+        return Ok(Redirect::permanent(&location).into_response());
+    }
+
+    attach_reactions(&ctx.db, slice::from_mut(&mut article), viewer.as_ref()).await?;
+
+    Ok((StatusCode::OK, DataResponse::new(article)).into_response())
+}
+
+#[instrument(skip(pool))]
+pub(crate) async fn find_article_id(
+    pool: &PgPool,
+    author: &Username,
+    slug: &str,
+) -> AppResult<Option<Uuid>> {
+    let article_id = sqlx::query_scalar!(
+        r#"
+            SELECT s.article_id
+            FROM article_slugs s
+            JOIN users u ON u.id = s.author_id
+            WHERE u.username = $1 AND s.slug = $2
+        "#,
+        author.as_ref(),
+        slug,
+    )
+    .fetch_optional(pool)
+    .await
+    .trace_db("Failed to find article by slug")?;
+
+    Ok(article_id)
+}
+
+/// Picks a slug for `title` that no other article of the author uses or used before.
+/// Slugs the article itself had before may be reused.
+#[instrument(skip(conn))]
+pub(crate) async fn pick_slug(
+    conn: &mut PgConnection,
+    author_id: &Uuid,
+    title: &str,
+    article_id: Option<&Uuid>,
+) -> AppResult<String> {
+    let base = slugify(title);
+
+    let taken = sqlx::query_scalar!(
+        r#"
+            SELECT slug
+            FROM article_slugs
+            WHERE
+                author_id = $1
+            AND
+                (slug = $2 OR slug LIKE $2 || '-%')
+            AND
+                ($3::UUID IS NULL OR article_id <> $3)
+        "#,
+        author_id,
+        &base,
+        article_id,
+    )
+    .fetch_all(&mut *conn)
+    .await
+    .trace_db("Failed to fetch taken slugs")?;
+
+    Ok(unique_slug(&base, &taken))
+}
+
+#[instrument(skip(conn))]
+pub(crate) async fn record_slug(
+    conn: &mut PgConnection,
+    author_id: &Uuid,
+    slug: &str,
+    article_id: &Uuid,
+) -> AppResult<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO article_slugs (author_id, slug, article_id) VALUES ($1, $2, $3)
+            ON CONFLICT DO NOTHING
+        "#,
+        author_id,
+        slug,
+        article_id,
+    )
+    .execute(&mut *conn)
+    .await
+    .trace_db("Failed to record article slug")?;
+
+    Ok(())
+}
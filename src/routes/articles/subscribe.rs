@@ -1,4 +1,8 @@
-use axum::{extract::State, http::StatusCode, response::IntoResponse};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
 use serde::{Deserialize, Serialize};
 use sqlx::PgPool;
 use tracing::instrument;
@@ -38,6 +42,18 @@ pub async fn subscribe(
     Ok((StatusCode::CREATED).into_response())
 }
 
+/// Unsubscribing from an author one does not follow is not an error.
+#[instrument(skip(ctx))]
+pub async fn unsubscribe(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    Path(author_id): Path<UserID>,
+) -> AppResponse {
+    remove_subscription(&ctx.db, &user.user_id, &author_id).await?;
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
 #[instrument(skip(pool))]
 async fn add_subscription(
     pool: &PgPool,
@@ -66,3 +82,24 @@ async fn add_subscription(
 
     Ok(())
 }
+
+#[instrument(skip(pool))]
+async fn remove_subscription(
+    pool: &PgPool,
+    user_id: &UserID,
+    target_user_id: &UserID,
+) -> AppResult<()> {
+    sqlx::query!(
+        r#"
+            DELETE FROM public.subscriptions
+            WHERE subscriber_id = $1 AND author_id = $2
+        "#,
+        user_id.as_ref(),
+        target_user_id.as_ref()
+    )
+    .execute(pool)
+    .await
+    .trace_db("Failed to remove subscription")?;
+
+    Ok(())
+}
@@ -0,0 +1,206 @@
+use std::slice;
+
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::{PgConnection, PgPool};
+use tracing::instrument;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    application::AppCtx,
+    db::DbResultExt,
+    domains::{article::ArticleStatus, user::UserID},
+    extractors::{AuthUser, ValidateJson},
+    routes::tags::set_article_tags,
+    utils::{
+        err::AppError,
+        jwt::UserData,
+        markdown,
+        response::{AppResponse, AppResult, DataResponse},
+        tag::normalize_tags,
+    },
+};
+
+use super::{
+    create_article::validate_schedule,
+    get_article::{article_not_found, fetch_article},
+    reactions::attach_reactions,
+    slugs::{pick_slug, record_slug},
+};
+
+#[derive(Deserialize, Validate, Debug, Serialize, Default)]
+pub struct Payload {
+    #[validate(length(min = 3))]
+    pub title: Option<String>,
+
+    #[validate(length(min = 10))]
+    pub text: Option<String>,
+
+    #[validate(length(min = 1))]
+    pub tags: Option<Vec<String>>,
+
+    pub status: Option<ArticleStatus>,
+
+    /// Required when moving the article to `scheduled`.
+    pub published_at: Option<NaiveDateTime>,
+
+    /// Version the edit is based on; stale versions are rejected with `409`.
+    pub version: i32,
+}
+
+#[instrument(skip(ctx))]
+pub async fn update_article(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    Path(id): Path<Uuid>,
+    ValidateJson(payload): ValidateJson<Payload>,
+) -> AppResponse {
+    match payload.status {
+        Some(status) => validate_schedule(status, payload.published_at)?,
+        None if payload.published_at.is_some() => {
+            return Err(AppError::BadRequest(
+                "published_at can only be set together with status".to_string(),
+            ))
+        }
+        None => (),
+    }
+
+    ensure_author(&ctx.db, &id, &user.user_id).await?;
+
+    let mut tx = ctx
+        .db
+        .begin()
+        .await
+        .trace_db("Failed to begin transaction")?;
+    let changes = ArticleChanges {
+        title: payload.title.as_deref(),
+        text: payload.text.as_deref(),
+        tags: payload.tags.as_deref(),
+        status: payload.status,
+        published_at: payload.published_at,
+    };
+    apply_update(&mut tx, &id, &changes, payload.version, &user.user_id).await?;
+    tx.commit()
+        .await
+        .trace_db("Failed to commit article update")?;
+
+    let mut article = fetch_article(&ctx.db, &id)
+        .await?
+        .ok_or_else(|| article_not_found(&id))?;
+    attach_reactions(&ctx.db, slice::from_mut(&mut article), Some(&user.user_id)).await?;
+
+    Ok((StatusCode::OK, DataResponse::new(article)).into_response())
+}
+
+/// Distinguishes a missing article (404) from someone else's article (403).
+#[instrument(skip(pool))]
+pub(super) async fn ensure_author(pool: &PgPool, id: &Uuid, user_id: &UserID) -> AppResult<()> {
+    let author_id = sqlx::query_scalar!("SELECT author_id FROM articles WHERE id = $1", id)
+        .fetch_optional(pool)
+        .await
+        .trace_db("Failed to fetch article author")?
+        .ok_or_else(|| article_not_found(id))?;
+
+    if &author_id != user_id.as_ref() {
+        return Err(AppError::Forbidden);
+    }
+
+    Ok(())
+}
+
+#[derive(Default)]
+pub(super) struct ArticleChanges<'a> {
+    pub title: Option<&'a str>,
+    pub text: Option<&'a str>,
+    pub tags: Option<&'a [String]>,
+    pub status: Option<ArticleStatus>,
+    pub published_at: Option<NaiveDateTime>,
+}
+
+/// Bumps the version only if it still equals `expected_version` and records the result as a new
+/// revision.
+pub(super) async fn apply_update(
+    conn: &mut PgConnection,
+    id: &Uuid,
+    changes: &ArticleChanges<'_>,
+    expected_version: i32,
+    editor_id: &UserID,
+) -> AppResult<i32> {
+    let rendered = changes.text.map(markdown::render);
+    let tags = changes.tags.map(normalize_tags);
+
+    let updated = sqlx::query!(
+        r#"
+            UPDATE articles SET
+                title = COALESCE($3, title),
+                text = COALESCE($4, text),
+                html = COALESCE($8, html),
+                word_count = COALESCE($9, word_count),
+                reading_time_minutes = COALESCE($10, reading_time_minutes),
+                tags = COALESCE($5, tags),
+                status = COALESCE($6, status),
+                published_at = CASE $6::article_status
+                    WHEN 'published' THEN CASE
+                        WHEN published_at IS NULL OR published_at > NOW() THEN NOW()
+                        ELSE published_at
+                    END
+                    WHEN 'scheduled' THEN $7::TIMESTAMP
+                    WHEN 'draft' THEN NULL
+                    ELSE published_at
+                END,
+                updated_at = NOW(),
+                version = version + 1
+            WHERE id = $1 AND version = $2
+            RETURNING version, author_id
+        "#,
+        id,
+        expected_version,
+        changes.title,
+        changes.text,
+        tags.as_deref(),
+        changes.status as Option<ArticleStatus>,
+        changes.published_at,
+        rendered.as_ref().map(|r| r.html.as_str()),
+        rendered.as_ref().map(|r| r.word_count),
+        rendered.as_ref().map(|r| r.reading_time_minutes),
+    )
+    .fetch_optional(&mut *conn)
+    .await
+    .trace_db("Failed to update article")?
+    .ok_or(AppError::VersionConflict(expected_version))?;
+
+    if let Some(title) = changes.title {
+        let slug = pick_slug(conn, &updated.author_id, title, Some(id)).await?;
+
+        sqlx::query!("UPDATE articles SET slug = $2 WHERE id = $1", id, &slug)
+            .execute(&mut *conn)
+            .await
+            .with_unique_violation(AppError::DuplicatedArticle, "Duplicated article slug")?;
+
+        record_slug(conn, &updated.author_id, &slug, id).await?;
+    }
+
+    if let Some(tags) = &tags {
+        set_article_tags(conn, id, tags).await?;
+    }
+
+    sqlx::query!(
+        r#"
+            INSERT INTO article_revisions (article_id, version, title, text, tags, editor_id, created_at)
+            SELECT id, version, title, text, tags, $2, updated_at FROM articles WHERE id = $1
+        "#,
+        id,
+        editor_id.as_ref(),
+    )
+    .execute(&mut *conn)
+    .await
+    .trace_db("Failed to record article revision")?;
+
+    Ok(updated.version)
+}
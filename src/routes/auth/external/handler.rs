@@ -0,0 +1,195 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::{IntoResponse, Redirect},
+};
+use serde::{Deserialize, Serialize};
+use tracing::{instrument, warn};
+use uuid::Uuid;
+
+use crate::{
+    application::AppCtx,
+    configuration::{OidcProviderConfig, RegistrationMode},
+    domains::user::{Email, Password, UserID, Username},
+    parsers::user::validate_username,
+    routes::auth::login::token_response,
+    utils::{
+        err::AppError,
+        oidc::{self, IdTokenClaims},
+        password::hash_password,
+        response::{AppResponse, AppResult},
+    },
+};
+
+use super::loader::{
+    find_identity, find_user_by_email, insert_external_user, link_identity, save_auth_state,
+    take_auth_state, LinkedUser,
+};
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct CallbackQuery {
+    pub state: String,
+    pub code: Option<String>,
+    pub error: Option<String>,
+}
+
+#[instrument(skip(ctx))]
+pub async fn external_start(
+    State(ctx): State<AppCtx>,
+    Path(provider_name): Path<String>,
+) -> AppResponse {
+    let provider = get_provider(&ctx, &provider_name)?;
+    let request = oidc::authorization_request(provider)?;
+
+    save_auth_state(&ctx.db, &provider_name, &request).await?;
+
+    Ok(Redirect::to(request.url.as_str()).into_response())
+}
+
+#[instrument(skip(ctx))]
+pub async fn external_callback(
+    State(ctx): State<AppCtx>,
+    Path(provider_name): Path<String>,
+    Query(query): Query<CallbackQuery>,
+) -> AppResponse {
+    let provider = get_provider(&ctx, &provider_name)?;
+
+    // The state is consumed even on failure so it can't be replayed
+    let auth_state = take_auth_state(&ctx.db, &provider_name, &query.state)
+        .await?
+        .ok_or(AppError::ExternalAuthFailed)?;
+
+    let code = match (query.code, query.error) {
+        (Some(code), None) => code,
+        (_, error) => {
+            warn!("Provider {provider_name} returned no code: {error:?}");
+            return Err(AppError::ExternalAuthFailed);
+        }
+    };
+
+    let claims = verify_code(
+        &ctx,
+        provider,
+        &code,
+        &auth_state.code_verifier,
+        &auth_state.nonce,
+    )
+    .await
+    .map_err(|e| {
+        warn!("External login via {provider_name} failed: {e:#}");
+        AppError::ExternalAuthFailed
+    })?;
+
+    let user = resolve_user(&ctx, &provider_name, &claims).await?;
+
+    if !user.active {
+        return Err(AppError::InvalidCredentials);
+    }
+
+    token_response(&ctx, user.id)
+}
+
+fn get_provider<'a>(ctx: &'a AppCtx, name: &str) -> AppResult<&'a OidcProviderConfig> {
+    ctx.oidc_providers
+        .get(name)
+        .ok_or_else(|| AppError::NotFound(format!("Unknown identity provider: {name}")))
+}
+
+async fn verify_code(
+    ctx: &AppCtx,
+    provider: &OidcProviderConfig,
+    code: &str,
+    code_verifier: &str,
+    nonce: &str,
+) -> anyhow::Result<IdTokenClaims> {
+    let id_token = oidc::exchange_code(&ctx.http_client, provider, code, code_verifier).await?;
+    oidc::verify_id_token(&ctx.http_client, provider, &id_token, nonce).await
+}
+
+/// Finds the user linked to the external subject. On first login the identity is linked
+/// to the account with the same email, or a new account is created for it. Local
+/// registration does not verify emails, so only accounts whose email is verified are
+/// linked: anyone could otherwise register the address first and receive the login.
+async fn resolve_user(
+    ctx: &AppCtx,
+    provider_name: &str,
+    claims: &IdTokenClaims,
+) -> AppResult<LinkedUser> {
+    if let Some(user) = find_identity(&ctx.db, provider_name, &claims.sub).await? {
+        return Ok(user);
+    }
+
+    let email = match (&claims.email, claims.email_verified) {
+        (Some(email), true) => Email(email.clone()),
+        _ => {
+            return Err(AppError::BadRequest(
+                "External account has no verified email".to_string(),
+            ))
+        }
+    };
+
+    let user = match find_user_by_email(&ctx.db, &email).await? {
+        Some(found) if found.email_verified => found.user,
+        Some(_) => return Err(AppError::UnverifiedAccountExists),
+        None if ctx.registration_mode == RegistrationMode::Open => LinkedUser {
+            id: create_user(ctx, &email, claims).await?,
+            active: true,
+        },
+        None => return Err(AppError::RegistrationClosed),
+    };
+
+    link_identity(&ctx.db, provider_name, &claims.sub, &user.id, &email).await?;
+
+    Ok(user)
+}
+
+async fn create_user(ctx: &AppCtx, email: &Email, claims: &IdTokenClaims) -> AppResult<UserID> {
+    let base = username_base(claims.preferred_username.as_deref(), email);
+
+    // Externally created accounts sign in through the provider until a password is set
+    let password_hash = hash_password(Password(Uuid::new_v4().to_string())).await?;
+
+    let mut candidate = base.clone();
+
+    for _ in 0..3 {
+        let username = Username(candidate);
+        validate_username(&username).map_err(|e| {
+            AppError::InternalServerError(anyhow::anyhow!("Derived invalid username: {e}"))
+        })?;
+
+        match insert_external_user(&ctx.db, &username, email, &password_hash).await {
+            Err(AppError::DuplicatedUser) => {
+                candidate = format!("{base}_{}", &Uuid::new_v4().simple().to_string()[..6]);
+            }
+            result => return result,
+        }
+    }
+
+    Err(AppError::DuplicatedUser)
+}
+
+/// Usernames are limited to 50 bytes, the base leaves room for the `_` and six characters
+/// added when it is taken.
+const MAX_USERNAME_BASE_LEN: usize = 43;
+
+fn username_base(preferred_username: Option<&str>, email: &Email) -> String {
+    let source =
+        preferred_username.unwrap_or_else(|| email.as_ref().split('@').next().unwrap_or_default());
+
+    let mut username = String::new();
+
+    for c in source
+        .chars()
+        .filter(|c| c.is_alphanumeric() || matches!(c, '_' | '-' | '.'))
+    {
+        if username.len() + c.len_utf8() > MAX_USERNAME_BASE_LEN {
+            break;
+        }
+        username.push(c);
+    }
+
+    while username.len() < 3 {
+        username.push('_');
+    }
+
+    username
+}
@@ -0,0 +1,183 @@
+use sqlx::PgPool;
+use tracing::instrument;
+
+use crate::{
+    db::DbResultExt,
+    domains::user::{Email, Password, UserID, Username},
+    utils::{err::AppError, oidc::AuthorizationRequest, response::AppResult},
+};
+
+pub struct AuthState {
+    pub nonce: String,
+    pub code_verifier: String,
+}
+
+pub struct LinkedUser {
+    pub id: UserID,
+    pub active: bool,
+}
+
+/// A local account found by email, only verified emails may be linked automatically.
+pub struct EmailUser {
+    pub user: LinkedUser,
+    pub email_verified: bool,
+}
+
+#[instrument(skip(pool, request))]
+pub async fn save_auth_state(
+    pool: &PgPool,
+    provider: &str,
+    request: &AuthorizationRequest,
+) -> AppResult<()> {
+    sqlx::query!(
+        r#"
+            DELETE FROM external_auth_states
+            WHERE created_at < NOW() - INTERVAL '10 minutes'
+        "#
+    )
+    .execute(pool)
+    .await
+    .trace_db("Failed to clean up expired external auth states")?;
+
+    sqlx::query!(
+        r#"
+            INSERT INTO external_auth_states (state, provider, nonce, code_verifier)
+            VALUES ($1, $2, $3, $4)
+        "#,
+        &request.state,
+        provider,
+        &request.nonce,
+        &request.code_verifier
+    )
+    .execute(pool)
+    .await
+    .trace_db("Failed to save external auth state")?;
+
+    Ok(())
+}
+
+#[instrument(skip(pool))]
+pub async fn take_auth_state(
+    pool: &PgPool,
+    provider: &str,
+    state: &str,
+) -> AppResult<Option<AuthState>> {
+    let result = sqlx::query_as!(
+        AuthState,
+        r#"
+            DELETE FROM external_auth_states
+            WHERE
+                state = $1
+            AND
+                provider = $2
+            AND
+                created_at >= NOW() - INTERVAL '10 minutes'
+            RETURNING nonce, code_verifier
+        "#,
+        state,
+        provider
+    )
+    .fetch_optional(pool)
+    .await
+    .trace_db("Failed to consume external auth state")?;
+
+    Ok(result)
+}
+
+#[instrument(skip(pool))]
+pub async fn find_identity(
+    pool: &PgPool,
+    provider: &str,
+    subject: &str,
+) -> AppResult<Option<LinkedUser>> {
+    let result = sqlx::query!(
+        r#"
+            SELECT u.id, u.active
+            FROM identities i
+            JOIN users u ON u.id = i.user_id
+            WHERE i.provider = $1 AND i.subject = $2
+        "#,
+        provider,
+        subject
+    )
+    .fetch_optional(pool)
+    .await
+    .trace_db("Failed to fetch external identity")?
+    .map(|row| LinkedUser {
+        id: UserID(row.id),
+        active: row.active,
+    });
+
+    Ok(result)
+}
+
+#[instrument(skip(pool))]
+pub async fn find_user_by_email(pool: &PgPool, email: &Email) -> AppResult<Option<EmailUser>> {
+    let result = sqlx::query!(
+        r#"
+            SELECT id, active, email_verified FROM users WHERE LOWER(email) = LOWER($1)
+        "#,
+        email.as_ref()
+    )
+    .fetch_optional(pool)
+    .await
+    .trace_db("Failed to fetch user by email")?
+    .map(|row| EmailUser {
+        user: LinkedUser {
+            id: UserID(row.id),
+            active: row.active,
+        },
+        email_verified: row.email_verified,
+    });
+
+    Ok(result)
+}
+
+#[instrument(skip(pool, password_hash))]
+pub async fn insert_external_user(
+    pool: &PgPool,
+    username: &Username,
+    email: &Email,
+    password_hash: &Password,
+) -> AppResult<UserID> {
+    sqlx::query_scalar!(
+        r#"
+            INSERT INTO users (username, email, password, email_verified)
+            VALUES ($1, $2, $3, TRUE)
+            RETURNING id
+        "#,
+        username.as_ref(),
+        email.as_ref(),
+        password_hash.as_ref()
+    )
+    .fetch_one(pool)
+    .await
+    .with_unique_violation(AppError::DuplicatedUser, "Failed to create external user")
+    .map(UserID)
+}
+
+#[instrument(skip(pool))]
+pub async fn link_identity(
+    pool: &PgPool,
+    provider: &str,
+    subject: &str,
+    user_id: &UserID,
+    email: &Email,
+) -> AppResult<()> {
+    sqlx::query!(
+        r#"
+            INSERT INTO identities (provider, subject, user_id, email)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT DO NOTHING
+        "#,
+        provider,
+        subject,
+        user_id.as_ref(),
+        email.as_ref()
+    )
+    .execute(pool)
+    .await
+    .trace_db("Failed to link external identity")?;
+
+    Ok(())
+}
@@ -0,0 +1,4 @@
+mod handler;
+mod loader;
+
+pub use handler::*;
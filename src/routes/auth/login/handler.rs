@@ -10,9 +10,14 @@ use validator::Validate;
 
 use crate::{
     application::AppCtx,
-    domains::user::{Password, Username},
+    domains::user::{Password, UserID, Username},
     extractors::ValidateJson,
-    utils::{err::AppError, jwt, password::verify_password, response::AppResponse},
+    routes::auth::webauthn::{authentication_challenge, ChallengePurpose},
+    utils::{
+        err::AppError,
+        password::verify_password,
+        response::{AppResponse, AppResult, DataResponse},
+    },
 };
 
 use super::loader::{get_user, UserLoginInfo};
@@ -31,17 +36,40 @@ pub async fn login(
     State(ctx): State<AppCtx>,
     ValidateJson(payload): ValidateJson<Payload>,
 ) -> AppResponse {
-    let UserLoginInfo { password_hash, id } = get_user(&ctx.db, &payload.username)
+    let UserLoginInfo {
+        id,
+        second_factor_required,
+        ..
+    } = check_credentials(&ctx, payload).await?;
+
+    if second_factor_required {
+        let challenge =
+            authentication_challenge(&ctx, Some(&id), ChallengePurpose::SecondFactor).await?;
+
+        return Ok((StatusCode::OK, DataResponse::new(challenge)).into_response());
+    }
+
+    token_response(&ctx, id)
+}
+
+/// Fails with `InvalidCredentials` for unknown users and wrong passwords alike.
+pub(crate) async fn check_credentials(ctx: &AppCtx, payload: Payload) -> AppResult<UserLoginInfo> {
+    let user = get_user(&ctx.db, &payload.username)
         .await?
         .ok_or(AppError::InvalidCredentials)?;
 
-    let is_valid_password = verify_password(payload.password, password_hash).await?;
+    let is_valid_password = verify_password(payload.password, user.password_hash.clone()).await?;
 
     if !is_valid_password {
         return Err(AppError::InvalidCredentials);
     }
 
-    let token = jwt::sign(id)?;
+    Ok(user)
+}
+
+/// Signs a token for the user and returns it in the `Authorization` header.
+pub fn token_response(ctx: &AppCtx, user_id: UserID) -> AppResponse {
+    let token = ctx.tokens.sign(user_id)?;
     let mut headers = HeaderMap::new();
 
     headers.insert(
@@ -10,6 +10,7 @@ use crate::{
 pub struct UserLoginInfo {
     pub id: UserID,
     pub password_hash: Password,
+    pub second_factor_required: bool,
 }
 
 #[instrument(skip(pool))]
@@ -18,11 +19,14 @@ pub async fn get_user(pool: &PgPool, username: &Username) -> AppResult<Option<Us
         r#"
             SELECT
                 id,
-                password
+                password,
+                webauthn_second_factor
             FROM
                 public.users
             WHERE
                 username = $1
+            AND
+                active
         "#,
         username.as_ref()
     )
@@ -33,6 +37,7 @@ pub async fn get_user(pool: &PgPool, username: &Username) -> AppResult<Option<Us
         maybe_row.map(|row| UserLoginInfo {
             id: UserID(row.id),
             password_hash: Password(row.password),
+            second_factor_required: row.webauthn_second_factor,
         })
     })?;
 
@@ -1,4 +1,5 @@
 mod handler;
 mod loader;
 
-pub use handler::login;
+pub(crate) use handler::check_credentials;
+pub use handler::{login, token_response, Payload};
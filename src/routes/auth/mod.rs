@@ -1,12 +1,15 @@
-mod login;
+mod external;
+pub(crate) mod login;
 mod me;
-mod register;
+pub(crate) mod register;
+pub mod webauthn;
 
 use axum::{
-    routing::{get, post},
+    routing::{delete, get, post, put},
     Router,
 };
 
+use external::{external_callback, external_start};
 use login::login;
 use me::me;
 use register::register;
@@ -18,4 +21,25 @@ pub fn routes() -> axum::Router<AppCtx> {
         .route("/auth/register", post(register))
         .route("/auth/login", post(login))
         .route("/auth/me", get(me))
+        .route("/auth/external/:provider/start", get(external_start))
+        .route("/auth/external/:provider/callback", get(external_callback))
+        .route(
+            "/auth/webauthn/register/start",
+            post(webauthn::register_start),
+        )
+        .route(
+            "/auth/webauthn/register/finish",
+            post(webauthn::register_finish),
+        )
+        .route("/auth/webauthn/login/start", post(webauthn::login_start))
+        .route("/auth/webauthn/login/finish", post(webauthn::login_finish))
+        .route("/auth/webauthn/credentials", get(webauthn::get_credentials))
+        .route(
+            "/auth/webauthn/credentials/:id",
+            delete(webauthn::remove_credential),
+        )
+        .route(
+            "/auth/webauthn/second-factor",
+            put(webauthn::update_second_factor),
+        )
 }
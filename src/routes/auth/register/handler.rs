@@ -1,14 +1,17 @@
 use axum::{extract::State, http::StatusCode, response::IntoResponse};
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 use validator::Validate;
 
 use crate::{
     application::AppCtx,
+    configuration::RegistrationMode,
     domains::user::{Email, Password, Username},
     extractors::ValidateJson,
     utils::{
+        err::AppError,
         password::hash_password,
-        response::{DataResponse, AppResponse},
+        response::{AppResponse, AppResult, DataResponse},
     },
 };
 
@@ -24,12 +27,32 @@ pub struct Payload {
 
     #[validate(custom = "crate::parsers::user::validate_email")]
     pub email: Email,
+
+    #[validate(length(min = 1, max = 64))]
+    pub invite_code: Option<String>,
 }
 
 pub async fn register(
     State(ctx): State<AppCtx>,
     ValidateJson(payload): ValidateJson<Payload>,
 ) -> AppResponse {
+    let user_id = register_user(&ctx, payload).await?;
+    Ok((StatusCode::OK, DataResponse::new(user_id.to_string())).into_response())
+}
+
+/// Checks the invite code the registration mode asks for and stores the new user.
+pub(crate) async fn register_user(ctx: &AppCtx, payload: Payload) -> AppResult<Uuid> {
+    let invite_code = match ctx.registration_mode {
+        RegistrationMode::Open => None,
+        RegistrationMode::Closed => return Err(AppError::RegistrationClosed),
+        RegistrationMode::InviteOnly => Some(
+            payload
+                .invite_code
+                .clone()
+                .ok_or(AppError::InvalidInviteCode)?,
+        ),
+    };
+
     let password_hash = hash_password(payload.password).await?;
 
     let payload = Payload {
@@ -37,6 +60,5 @@ pub async fn register(
         ..payload
     };
 
-    let user_id = insert_new_user(&ctx.db, &payload).await?;
-    Ok((StatusCode::OK, DataResponse::new(user_id.to_string())).into_response())
+    insert_new_user(&ctx.db, &payload, invite_code.as_deref()).await
 }
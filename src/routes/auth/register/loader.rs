@@ -1,4 +1,4 @@
-use sqlx::PgPool;
+use sqlx::{PgConnection, PgPool};
 use tracing::instrument;
 use uuid::Uuid;
 
@@ -10,8 +10,21 @@ use crate::{
 use super::Payload;
 
 #[instrument(skip(pool))]
-pub async fn insert_new_user(pool: &PgPool, user: &Payload) -> AppResult<Uuid> {
-    sqlx::query!(
+pub async fn insert_new_user(
+    pool: &PgPool,
+    user: &Payload,
+    invite_code: Option<&str>,
+) -> AppResult<Uuid> {
+    let mut tx = pool
+        .begin()
+        .await
+        .trace_db("Failed to start a transaction")?;
+
+    if let Some(code) = invite_code {
+        consume_invite_code(&mut tx, code).await?;
+    }
+
+    let user_id = sqlx::query!(
         r#"
             INSERT INTO users (username, password, email)
             VALUES ($1, $2, $3)
@@ -21,8 +34,36 @@ pub async fn insert_new_user(pool: &PgPool, user: &Payload) -> AppResult<Uuid> {
         user.password.as_ref(),
         user.email.as_ref()
     )
-    .fetch_one(pool)
+    .fetch_one(&mut *tx)
     .await
     .with_unique_violation(AppError::DuplicatedUser, "Failed create new user")
-    .map(|row| row.id)
+    .map(|row| row.id)?;
+
+    tx.commit().await.trace_db("Failed to commit new user")?;
+
+    Ok(user_id)
+}
+
+#[instrument(skip(connection))]
+async fn consume_invite_code(connection: &mut PgConnection, code: &str) -> AppResult<()> {
+    sqlx::query!(
+        r#"
+            UPDATE invite_codes
+            SET uses = uses + 1
+            WHERE
+                code = $1
+            AND
+                uses < max_uses
+            AND
+                (expires_at IS NULL OR expires_at > NOW())
+            RETURNING code
+        "#,
+        code
+    )
+    .fetch_optional(connection)
+    .await
+    .trace_db("Failed to consume invite code")?
+    .ok_or(AppError::InvalidInviteCode)?;
+
+    Ok(())
 }
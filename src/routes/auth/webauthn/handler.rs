@@ -0,0 +1,399 @@
+use anyhow::{ensure, Context};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tracing::{instrument, warn};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    application::AppCtx,
+    configuration::WebAuthnConfig,
+    domains::user::{UserID, Username},
+    extractors::{AuthUser, ValidateJson},
+    routes::auth::login::token_response,
+    utils::{
+        err::AppError,
+        jwt::UserData,
+        response::{AppResponse, AppResult, DataResponse},
+        webauthn::{self, AttestedCredential, AuthenticatorData, COSE_ALG_ES256},
+    },
+};
+
+use super::loader::{
+    create_challenge, delete_credential, get_credential, get_user_credentials, get_user_id,
+    get_username, insert_credential, list_credentials, set_second_factor, take_challenge,
+    update_sign_count, ChallengePurpose, StoredChallenge, StoredCredential,
+};
+
+const CHALLENGE_TIMEOUT_MS: u64 = 5 * 60 * 1000;
+
+/// Options to pass to `navigator.credentials.create()`/`get()` as `publicKey`,
+/// together with the ID to send back when finishing the ceremony.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct ChallengeResponse {
+    pub challenge_id: Uuid,
+    pub public_key: Value,
+}
+
+#[derive(Serialize, Deserialize, Debug, Validate)]
+pub struct RegisterFinishPayload {
+    pub challenge_id: Uuid,
+
+    #[validate(length(min = 1, max = 100))]
+    pub name: Option<String>,
+
+    pub credential: RegistrationCredential,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RegistrationCredential {
+    pub raw_id: String,
+    pub response: AttestationResponse,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AttestationResponse {
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: String,
+    pub attestation_object: String,
+
+    #[serde(default)]
+    pub transports: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Validate)]
+pub struct LoginStartPayload {
+    #[validate(custom = "crate::parsers::user::validate_username")]
+    pub username: Option<Username>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Validate)]
+pub struct LoginFinishPayload {
+    pub challenge_id: Uuid,
+    pub credential: AuthenticationCredential,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AuthenticationCredential {
+    pub raw_id: String,
+    pub response: AssertionResponse,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AssertionResponse {
+    #[serde(rename = "clientDataJSON")]
+    pub client_data_json: String,
+    pub authenticator_data: String,
+    pub signature: String,
+    pub user_handle: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Validate)]
+pub struct SecondFactorPayload {
+    pub enabled: bool,
+}
+
+#[instrument(skip(ctx))]
+pub async fn register_start(
+    State(ctx): State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+) -> AppResponse {
+    let username = get_username(&ctx.db, &user.user_id)
+        .await?
+        .ok_or(AppError::Unauthorized)?;
+
+    let existing = get_user_credentials(&ctx.db, &user.user_id).await?;
+    let challenge = webauthn::new_challenge();
+
+    let challenge_id = create_challenge(
+        &ctx.db,
+        &challenge,
+        Some(&user.user_id),
+        ChallengePurpose::Registration,
+    )
+    .await?;
+
+    let public_key = json!({
+        "challenge": webauthn::encode(&challenge),
+        "rp": { "id": &ctx.webauthn.rp_id, "name": &ctx.webauthn.rp_name },
+        "user": {
+            "id": webauthn::encode(user.user_id.as_ref().as_bytes()),
+            "name": &username,
+            "displayName": &username,
+        },
+        "pubKeyCredParams": [{ "type": "public-key", "alg": COSE_ALG_ES256 }],
+        "timeout": CHALLENGE_TIMEOUT_MS,
+        "attestation": "none",
+        "excludeCredentials": credential_descriptors(&existing),
+        "authenticatorSelection": {
+            "residentKey": "preferred",
+            "userVerification": "preferred",
+        },
+    });
+
+    let response = ChallengeResponse {
+        challenge_id,
+        public_key,
+    };
+
+    Ok((StatusCode::OK, DataResponse::new(response)).into_response())
+}
+
+#[instrument(skip(ctx))]
+pub async fn register_finish(
+    State(ctx): State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    ValidateJson(payload): ValidateJson<RegisterFinishPayload>,
+) -> AppResponse {
+    let stored = take_challenge(&ctx.db, &payload.challenge_id)
+        .await?
+        .ok_or(AppError::WebAuthnFailed)?;
+
+    if stored.purpose != ChallengePurpose::Registration
+        || stored.user_id.as_ref() != Some(user.user_id.as_ref())
+    {
+        return Err(AppError::WebAuthnFailed);
+    }
+
+    let (attested, sign_count) =
+        verify_registration(&ctx.webauthn, &stored.challenge, &payload.credential).map_err(
+            |e| {
+                warn!("Passkey registration failed: {e:#}");
+                AppError::WebAuthnFailed
+            },
+        )?;
+
+    let credential = insert_credential(
+        &ctx.db,
+        &user.user_id,
+        &attested.credential_id,
+        &attested.public_key,
+        sign_count,
+        &payload.credential.response.transports,
+        payload.name.as_deref(),
+    )
+    .await?;
+
+    Ok((StatusCode::CREATED, DataResponse::new(credential)).into_response())
+}
+
+#[instrument(skip(ctx))]
+pub async fn login_start(
+    State(ctx): State<AppCtx>,
+    ValidateJson(payload): ValidateJson<LoginStartPayload>,
+) -> AppResponse {
+    let user_id = match &payload.username {
+        Some(username) => get_user_id(&ctx.db, username).await?,
+        None => None,
+    };
+
+    let response =
+        authentication_challenge(&ctx, user_id.as_ref(), ChallengePurpose::Passwordless).await?;
+
+    Ok((StatusCode::OK, DataResponse::new(response)).into_response())
+}
+
+#[instrument(skip(ctx))]
+pub async fn login_finish(
+    State(ctx): State<AppCtx>,
+    ValidateJson(payload): ValidateJson<LoginFinishPayload>,
+) -> AppResponse {
+    let stored = take_challenge(&ctx.db, &payload.challenge_id)
+        .await?
+        .ok_or(AppError::WebAuthnFailed)?;
+
+    if stored.purpose == ChallengePurpose::Registration {
+        return Err(AppError::WebAuthnFailed);
+    }
+
+    let credential_id =
+        webauthn::decode(&payload.credential.raw_id).map_err(|_| AppError::WebAuthnFailed)?;
+
+    let credential = get_credential(&ctx.db, &credential_id)
+        .await?
+        .ok_or(AppError::WebAuthnFailed)?;
+
+    let sign_count = verify_assertion(&ctx.webauthn, &stored, &credential, &payload.credential)
+        .map_err(|e| {
+            warn!("Passkey assertion failed: {e:#}");
+            AppError::WebAuthnFailed
+        })?;
+
+    // Deactivated users are turned away before their credential is touched
+    if !credential.active {
+        return Err(AppError::InvalidCredentials);
+    }
+
+    update_sign_count(&ctx.db, &credential.id, sign_count).await?;
+
+    token_response(&ctx, UserID(credential.user_id))
+}
+
+#[instrument(skip(ctx))]
+pub async fn get_credentials(
+    State(ctx): State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+) -> AppResponse {
+    let credentials = list_credentials(&ctx.db, &user.user_id).await?;
+    Ok((StatusCode::OK, DataResponse::new(credentials)).into_response())
+}
+
+#[instrument(skip(ctx))]
+pub async fn remove_credential(
+    State(ctx): State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    Path(id): Path<Uuid>,
+) -> AppResponse {
+    if !delete_credential(&ctx.db, &user.user_id, &id).await? {
+        return Err(AppError::NotFound("Credential not found".to_string()));
+    }
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+#[instrument(skip(ctx))]
+pub async fn update_second_factor(
+    State(ctx): State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    ValidateJson(payload): ValidateJson<SecondFactorPayload>,
+) -> AppResponse {
+    if payload.enabled
+        && get_user_credentials(&ctx.db, &user.user_id)
+            .await?
+            .is_empty()
+    {
+        return Err(AppError::BadRequest(
+            "Register a passkey before enabling it as a second factor".to_string(),
+        ));
+    }
+
+    set_second_factor(&ctx.db, &user.user_id, payload.enabled).await?;
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+/// Starts an assertion ceremony. When the user is known only their credentials are allowed.
+pub async fn authentication_challenge(
+    ctx: &AppCtx,
+    user_id: Option<&UserID>,
+    purpose: ChallengePurpose,
+) -> AppResult<ChallengeResponse> {
+    let credentials = match user_id {
+        Some(user_id) => get_user_credentials(&ctx.db, user_id).await?,
+        None => vec![],
+    };
+
+    let challenge = webauthn::new_challenge();
+    let challenge_id = create_challenge(&ctx.db, &challenge, user_id, purpose).await?;
+
+    let user_verification = match purpose {
+        ChallengePurpose::Passwordless => "required",
+        _ => "preferred",
+    };
+
+    let public_key = json!({
+        "challenge": webauthn::encode(&challenge),
+        "rpId": &ctx.webauthn.rp_id,
+        "timeout": CHALLENGE_TIMEOUT_MS,
+        "userVerification": user_verification,
+        "allowCredentials": credential_descriptors(&credentials),
+    });
+
+    Ok(ChallengeResponse {
+        challenge_id,
+        public_key,
+    })
+}
+
+fn credential_descriptors(credentials: &[StoredCredential]) -> Vec<Value> {
+    credentials
+        .iter()
+        .map(|c| {
+            json!({
+                "type": "public-key",
+                "id": webauthn::encode(&c.credential_id),
+                "transports": &c.transports,
+            })
+        })
+        .collect()
+}
+
+fn verify_registration(
+    config: &WebAuthnConfig,
+    challenge: &[u8],
+    credential: &RegistrationCredential,
+) -> anyhow::Result<(AttestedCredential, u32)> {
+    let client_data = webauthn::decode(&credential.response.client_data_json)?;
+    webauthn::verify_client_data(&client_data, "webauthn.create", challenge, config)?;
+
+    let attestation_object = webauthn::decode(&credential.response.attestation_object)?;
+    let auth_data = webauthn::parse_attestation_object(&attestation_object)?;
+    auth_data.verify(config, false)?;
+
+    let attested = auth_data
+        .attested_credential
+        .context("Attestation has no credential data")?;
+
+    ensure!(
+        attested.credential_id == webauthn::decode(&credential.raw_id)?,
+        "Credential ID mismatch"
+    );
+
+    Ok((attested, auth_data.sign_count))
+}
+
+/// Verifies the assertion and returns the authenticator's new signature counter.
+fn verify_assertion(
+    config: &WebAuthnConfig,
+    challenge: &StoredChallenge,
+    credential: &StoredCredential,
+    assertion: &AuthenticationCredential,
+) -> anyhow::Result<u32> {
+    if let Some(user_id) = challenge.user_id {
+        ensure!(
+            user_id == credential.user_id,
+            "Credential belongs to another user"
+        );
+    }
+
+    if let Some(user_handle) = &assertion.response.user_handle {
+        ensure!(
+            webauthn::decode(user_handle)? == credential.user_id.as_bytes(),
+            "User handle mismatch"
+        );
+    }
+
+    let client_data = webauthn::decode(&assertion.response.client_data_json)?;
+    webauthn::verify_client_data(&client_data, "webauthn.get", &challenge.challenge, config)?;
+
+    let raw_auth_data = webauthn::decode(&assertion.response.authenticator_data)?;
+    let auth_data = AuthenticatorData::parse(&raw_auth_data)?;
+    auth_data.verify(config, challenge.purpose == ChallengePurpose::Passwordless)?;
+
+    webauthn::verify_signature(
+        &credential.public_key,
+        &raw_auth_data,
+        &client_data,
+        &webauthn::decode(&assertion.response.signature)?,
+    )?;
+
+    // A counter that doesn't grow hints at a cloned authenticator. Zero means unsupported.
+    let stored_count = credential.sign_count as u32;
+    if auth_data.sign_count != 0 || stored_count != 0 {
+        ensure!(
+            auth_data.sign_count > stored_count,
+            "Signature counter did not increase"
+        );
+    }
+
+    Ok(auth_data.sign_count)
+}
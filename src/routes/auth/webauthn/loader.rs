@@ -0,0 +1,343 @@
+use chrono::NaiveDateTime;
+use parse_display::{Display, FromStr};
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    db::DbResultExt,
+    domains::user::{UserID, Username},
+    utils::{err::AppError, response::AppResult, webauthn},
+};
+
+#[derive(Debug, Display, FromStr, PartialEq, Clone, Copy)]
+#[display(style = "snake_case")]
+pub enum ChallengePurpose {
+    Registration,
+    Passwordless,
+    SecondFactor,
+}
+
+pub struct StoredChallenge {
+    pub challenge: Vec<u8>,
+    pub user_id: Option<Uuid>,
+    pub purpose: ChallengePurpose,
+}
+
+pub struct StoredCredential {
+    pub id: Uuid,
+    pub credential_id: Vec<u8>,
+    pub user_id: Uuid,
+    pub public_key: Vec<u8>,
+    pub sign_count: i64,
+    pub transports: Vec<String>,
+    pub active: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct CredentialInfo {
+    pub id: Uuid,
+    pub credential_id: String,
+    pub name: Option<String>,
+    pub transports: Vec<String>,
+    pub created_at: NaiveDateTime,
+    pub last_used_at: Option<NaiveDateTime>,
+}
+
+struct CredentialInfoRow {
+    id: Uuid,
+    credential_id: Vec<u8>,
+    name: Option<String>,
+    transports: Vec<String>,
+    created_at: NaiveDateTime,
+    last_used_at: Option<NaiveDateTime>,
+}
+
+impl From<CredentialInfoRow> for CredentialInfo {
+    fn from(row: CredentialInfoRow) -> Self {
+        CredentialInfo {
+            id: row.id,
+            credential_id: webauthn::encode(&row.credential_id),
+            name: row.name,
+            transports: row.transports,
+            created_at: row.created_at,
+            last_used_at: row.last_used_at,
+        }
+    }
+}
+
+#[instrument(skip(pool, challenge))]
+pub async fn create_challenge(
+    pool: &PgPool,
+    challenge: &[u8],
+    user_id: Option<&UserID>,
+    purpose: ChallengePurpose,
+) -> AppResult<Uuid> {
+    sqlx::query!(
+        r#"
+            DELETE FROM webauthn_challenges
+            WHERE created_at < NOW() - INTERVAL '5 minutes'
+        "#
+    )
+    .execute(pool)
+    .await
+    .trace_db("Failed to clean up expired WebAuthn challenges")?;
+
+    let id = sqlx::query_scalar!(
+        r#"
+            INSERT INTO webauthn_challenges (challenge, user_id, purpose)
+            VALUES ($1, $2, $3)
+            RETURNING id
+        "#,
+        challenge,
+        user_id.map(|id| id.as_ref()),
+        purpose.to_string()
+    )
+    .fetch_one(pool)
+    .await
+    .trace_db("Failed to create WebAuthn challenge")?;
+
+    Ok(id)
+}
+
+#[instrument(skip(pool))]
+pub async fn take_challenge(pool: &PgPool, id: &Uuid) -> AppResult<Option<StoredChallenge>> {
+    let row = sqlx::query!(
+        r#"
+            DELETE FROM webauthn_challenges
+            WHERE id = $1 AND created_at >= NOW() - INTERVAL '5 minutes'
+            RETURNING challenge, user_id, purpose
+        "#,
+        id
+    )
+    .fetch_optional(pool)
+    .await
+    .trace_db("Failed to consume WebAuthn challenge")?;
+
+    let Some(row) = row else {
+        return Ok(None);
+    };
+
+    let purpose = row
+        .purpose
+        .parse()
+        .map_err(|_| anyhow::anyhow!("Unknown challenge purpose: {}", row.purpose))?;
+
+    Ok(Some(StoredChallenge {
+        challenge: row.challenge,
+        user_id: row.user_id,
+        purpose,
+    }))
+}
+
+#[instrument(skip(pool))]
+pub async fn get_username(pool: &PgPool, user_id: &UserID) -> AppResult<Option<Username>> {
+    let username =
+        sqlx::query_scalar!("SELECT username FROM users WHERE id = $1", user_id.as_ref())
+            .fetch_optional(pool)
+            .await
+            .trace_db("Failed to fetch username")?;
+
+    Ok(username.map(Username))
+}
+
+#[instrument(skip(pool))]
+pub async fn get_user_id(pool: &PgPool, username: &Username) -> AppResult<Option<UserID>> {
+    let id = sqlx::query_scalar!(
+        "SELECT id FROM users WHERE username = $1 AND active",
+        username.as_ref()
+    )
+    .fetch_optional(pool)
+    .await
+    .trace_db("Failed to fetch user id")?;
+
+    Ok(id.map(UserID))
+}
+
+#[instrument(skip(pool))]
+pub async fn get_user_credentials(
+    pool: &PgPool,
+    user_id: &UserID,
+) -> AppResult<Vec<StoredCredential>> {
+    let credentials = sqlx::query_as!(
+        StoredCredential,
+        r#"
+            SELECT
+                c.id,
+                c.credential_id,
+                c.user_id,
+                c.public_key,
+                c.sign_count,
+                c.transports,
+                u.active
+            FROM webauthn_credentials c
+            JOIN users u ON u.id = c.user_id
+            WHERE c.user_id = $1
+        "#,
+        user_id.as_ref()
+    )
+    .fetch_all(pool)
+    .await
+    .trace_db("Failed to fetch user credentials")?;
+
+    Ok(credentials)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_credential(
+    pool: &PgPool,
+    credential_id: &[u8],
+) -> AppResult<Option<StoredCredential>> {
+    let credential = sqlx::query_as!(
+        StoredCredential,
+        r#"
+            SELECT
+                c.id,
+                c.credential_id,
+                c.user_id,
+                c.public_key,
+                c.sign_count,
+                c.transports,
+                u.active
+            FROM webauthn_credentials c
+            JOIN users u ON u.id = c.user_id
+            WHERE c.credential_id = $1
+        "#,
+        credential_id
+    )
+    .fetch_optional(pool)
+    .await
+    .trace_db("Failed to fetch credential")?;
+
+    Ok(credential)
+}
+
+#[instrument(skip(pool, public_key))]
+pub async fn insert_credential(
+    pool: &PgPool,
+    user_id: &UserID,
+    credential_id: &[u8],
+    public_key: &[u8],
+    sign_count: u32,
+    transports: &[String],
+    name: Option<&str>,
+) -> AppResult<CredentialInfo> {
+    sqlx::query_as!(
+        CredentialInfoRow,
+        r#"
+            INSERT INTO webauthn_credentials (
+                user_id,
+                credential_id,
+                public_key,
+                sign_count,
+                transports,
+                name
+            ) VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, credential_id, name, transports, created_at, last_used_at
+        "#,
+        user_id.as_ref(),
+        credential_id,
+        public_key,
+        sign_count as i64,
+        transports,
+        name
+    )
+    .fetch_one(pool)
+    .await
+    .map(CredentialInfo::from)
+    .with_unique_violation(
+        AppError::BadRequest("Credential is already registered".to_string()),
+        "Duplicated WebAuthn credential",
+    )
+}
+
+#[instrument(skip(pool))]
+pub async fn update_sign_count(pool: &PgPool, id: &Uuid, sign_count: u32) -> AppResult<()> {
+    sqlx::query!(
+        r#"
+            UPDATE webauthn_credentials
+            SET sign_count = $2, last_used_at = NOW()
+            WHERE id = $1
+        "#,
+        id,
+        sign_count as i64
+    )
+    .execute(pool)
+    .await
+    .trace_db("Failed to update credential sign count")?;
+
+    Ok(())
+}
+
+#[instrument(skip(pool))]
+pub async fn list_credentials(pool: &PgPool, user_id: &UserID) -> AppResult<Vec<CredentialInfo>> {
+    let credentials = sqlx::query_as!(
+        CredentialInfoRow,
+        r#"
+            SELECT id, credential_id, name, transports, created_at, last_used_at
+            FROM webauthn_credentials
+            WHERE user_id = $1
+            ORDER BY created_at
+        "#,
+        user_id.as_ref()
+    )
+    .fetch_all(pool)
+    .await
+    .trace_db("Failed to list credentials")?;
+
+    Ok(credentials.into_iter().map(CredentialInfo::from).collect())
+}
+
+/// Deletes the credential and turns off the second factor once no credentials remain.
+#[instrument(skip(pool))]
+pub async fn delete_credential(pool: &PgPool, user_id: &UserID, id: &Uuid) -> AppResult<bool> {
+    let mut tx = pool
+        .begin()
+        .await
+        .trace_db("Failed to start a transaction")?;
+
+    let result = sqlx::query!(
+        "DELETE FROM webauthn_credentials WHERE id = $1 AND user_id = $2",
+        id,
+        user_id.as_ref()
+    )
+    .execute(&mut *tx)
+    .await
+    .trace_db("Failed to delete credential")?;
+
+    sqlx::query!(
+        r#"
+            UPDATE users
+            SET webauthn_second_factor = FALSE
+            WHERE
+                id = $1
+            AND
+                NOT EXISTS (SELECT 1 FROM webauthn_credentials WHERE user_id = $1)
+        "#,
+        user_id.as_ref()
+    )
+    .execute(&mut *tx)
+    .await
+    .trace_db("Failed to reset second factor")?;
+
+    tx.commit()
+        .await
+        .trace_db("Failed to commit credential removal")?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+#[instrument(skip(pool))]
+pub async fn set_second_factor(pool: &PgPool, user_id: &UserID, enabled: bool) -> AppResult<()> {
+    sqlx::query!(
+        "UPDATE users SET webauthn_second_factor = $2 WHERE id = $1",
+        user_id.as_ref(),
+        enabled
+    )
+    .execute(pool)
+    .await
+    .trace_db("Failed to update second factor setting")?;
+
+    Ok(())
+}
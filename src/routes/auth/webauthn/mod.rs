@@ -0,0 +1,5 @@
+mod handler;
+mod loader;
+
+pub use handler::*;
+pub use loader::ChallengePurpose;
@@ -0,0 +1,57 @@
+use chrono::{NaiveDateTime, SecondsFormat};
+
+use crate::routes::{articles::Article, pages::article_url};
+
+use super::{escape, Feed};
+
+pub fn render(feed: &Feed, articles: &[Article], base_url: &str) -> String {
+    let mut xml = String::from(r#"<?xml version="1.0" encoding="utf-8"?>"#);
+    xml.push_str("\n<feed xmlns=\"http://www.w3.org/2005/Atom\">\n");
+    xml.push_str(&format!(
+        "<id>{0}{1}</id>\n<title>{2}</title>\n<updated>{3}</updated>\n\
+         <link rel=\"self\" href=\"{0}{1}\"/>\n<link rel=\"alternate\" type=\"text/html\" href=\"{0}{4}\"/>\n",
+        base_url,
+        escape(&feed.self_path),
+        escape(&feed.title),
+        timestamp(feed.updated),
+        escape(&feed.alternate_path),
+    ));
+
+    for article in articles {
+        let author = article
+            .author
+            .display_name
+            .as_deref()
+            .unwrap_or(article.author.username.as_ref());
+
+        xml.push_str(&format!(
+            "<entry>\n<id>urn:uuid:{}</id>\n<title>{}</title>\n\
+             <link rel=\"alternate\" type=\"text/html\" href=\"{}{}\"/>\n\
+             <published>{}</published>\n<updated>{}</updated>\n\
+             <author><name>{}</name></author>\n",
+            article.id,
+            escape(&article.title),
+            base_url,
+            escape(&article_url(article)),
+            timestamp(article.published_at.unwrap_or(article.created_at)),
+            timestamp(article.updated_at),
+            escape(author),
+        ));
+
+        for tag in &article.tags {
+            xml.push_str(&format!("<category term=\"{}\"/>\n", escape(tag)));
+        }
+
+        xml.push_str(&format!(
+            "<content type=\"html\">{}</content>\n</entry>\n",
+            escape(&article.html)
+        ));
+    }
+
+    xml.push_str("</feed>\n");
+    xml
+}
+
+fn timestamp(at: NaiveDateTime) -> String {
+    at.and_utc().to_rfc3339_opts(SecondsFormat::Secs, true)
+}
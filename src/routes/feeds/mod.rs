@@ -0,0 +1,259 @@
+pub mod atom;
+pub mod rss;
+pub mod subscribed;
+pub mod tokens;
+
+use axum::{
+    extract::{Path, State},
+    http::{
+        header::{CONTENT_TYPE, ETAG, IF_NONE_MATCH, LAST_MODIFIED},
+        HeaderMap, StatusCode,
+    },
+    response::{IntoResponse, Response},
+    routing::get,
+    Router,
+};
+use chrono::{DateTime, NaiveDateTime};
+use sha2::{Digest, Sha256};
+use tracing::instrument;
+use validator::Validate;
+
+use crate::{
+    application::AppCtx,
+    domains::user::Username,
+    routes::{
+        articles::{list, Article},
+        pages::{author_url, with_query},
+        users::profile::fetch_profile,
+    },
+    types::CountMode,
+    utils::{
+        err::AppError,
+        response::{AppResponse, AppResult},
+        url::path_segment,
+    },
+};
+
+const FEED_SIZE: u64 = 20;
+const ATOM_CONTENT_TYPE: &str = "application/atom+xml; charset=utf-8";
+const RSS_CONTENT_TYPE: &str = "application/rss+xml; charset=utf-8";
+
+pub fn routes() -> Router<AppCtx> {
+    Router::new()
+        .route("/feeds/articles.atom", get(site_feed))
+        .route("/feeds/authors/:file", get(author_feed))
+        .route("/feeds/tags/:file", get(tag_feed))
+        .route("/feeds/subscribed/:file", get(subscribed::subscribed_feed))
+        .route(
+            "/me/feed-token",
+            get(tokens::get_feed_token)
+                .post(tokens::regenerate_feed_token)
+                .delete(tokens::revoke_feed_token),
+        )
+}
+
+/// Feed metadata, paths are relative to `app.public_url`.
+pub struct Feed {
+    pub title: String,
+    pub self_path: String,
+    pub alternate_path: String,
+    /// Latest edit or publication of the entries. Scheduled articles are published without
+    /// an edit, so `updated_at` alone would miss them.
+    pub updated: NaiveDateTime,
+}
+
+impl Feed {
+    pub fn new(
+        title: String,
+        self_path: String,
+        alternate_path: String,
+        articles: &[Article],
+    ) -> Self {
+        let updated = articles
+            .iter()
+            .map(|article| {
+                article
+                    .updated_at
+                    .max(article.published_at.unwrap_or_default())
+            })
+            .max()
+            .unwrap_or(DateTime::UNIX_EPOCH.naive_utc());
+
+        Self {
+            title,
+            self_path,
+            alternate_path,
+            updated,
+        }
+    }
+}
+
+#[instrument(skip(ctx))]
+pub async fn site_feed(ctx: State<AppCtx>, headers: HeaderMap) -> AppResponse {
+    let articles = published_articles(&ctx, list::Payload::default()).await?;
+    let feed = Feed::new(
+        "Articles".to_string(),
+        "/feeds/articles.atom".to_string(),
+        "/".to_string(),
+        &articles,
+    );
+    let body = atom::render(&feed, &articles, &ctx.public_url);
+
+    Ok(feed_response(
+        &headers,
+        ATOM_CONTENT_TYPE,
+        body,
+        feed.updated,
+    ))
+}
+
+#[instrument(skip(ctx))]
+pub async fn author_feed(
+    ctx: State<AppCtx>,
+    Path(file): Path<String>,
+    headers: HeaderMap,
+) -> AppResponse {
+    let username = Username(feed_name(&file, ".atom")?);
+    let profile = fetch_profile(&ctx.db, &username, None)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("User not found: {username}")))?;
+
+    let payload = list::Payload {
+        author: Some(profile.username.clone()),
+        ..Default::default()
+    };
+    let articles = published_articles(&ctx, payload).await?;
+
+    let name = profile
+        .display_name
+        .as_deref()
+        .unwrap_or(profile.username.as_ref());
+    let feed = Feed::new(
+        format!("Articles by {name}"),
+        format!("/feeds/authors/{}.atom", path_segment(username.as_ref())),
+        author_url(&profile.username),
+        &articles,
+    );
+    let body = atom::render(&feed, &articles, &ctx.public_url);
+
+    Ok(feed_response(
+        &headers,
+        ATOM_CONTENT_TYPE,
+        body,
+        feed.updated,
+    ))
+}
+
+#[instrument(skip(ctx))]
+pub async fn tag_feed(
+    ctx: State<AppCtx>,
+    Path(file): Path<String>,
+    headers: HeaderMap,
+) -> AppResponse {
+    let tag = feed_name(&file, ".rss")?;
+
+    let payload = list::Payload {
+        tag: Some(tag.clone()),
+        ..Default::default()
+    };
+    let articles = published_articles(&ctx, payload).await?;
+
+    let feed = Feed::new(
+        format!("Articles tagged {tag}"),
+        format!("/feeds/tags/{}.rss", path_segment(&tag)),
+        with_query("/", &[("tag", &tag)]),
+        &articles,
+    );
+    let body = rss::render(&feed, &articles, &ctx.public_url);
+
+    Ok(feed_response(
+        &headers,
+        RSS_CONTENT_TYPE,
+        body,
+        feed.updated,
+    ))
+}
+
+/// The latest published articles in the order `list::get_articles_list` returns them.
+async fn published_articles(ctx: &AppCtx, payload: list::Payload) -> AppResult<Vec<Article>> {
+    let payload = list::Payload {
+        limit: Some(FEED_SIZE),
+        count: CountMode::None,
+        ..payload
+    };
+    payload.validate()?;
+
+    let articles = list::get_articles_list(&payload, None, &ctx.search_language, &ctx.db).await?;
+
+    Ok(articles.results)
+}
+
+/// Strips the extension from a path segment like `rust.rss`.
+fn feed_name(file: &str, extension: &str) -> AppResult<String> {
+    file.strip_suffix(extension)
+        .filter(|name| !name.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| AppError::NotFound(format!("Feed not found: {file}")))
+}
+
+/// Tags the body with an `ETag` and `Last-Modified` and answers `If-None-Match` with `304`.
+/// `If-Modified-Since` is not enough on its own: removing an entry changes the body without
+/// moving `Last-Modified` forward, while the `ETag` covers the whole body.
+pub(crate) fn feed_response(
+    headers: &HeaderMap,
+    content_type: &'static str,
+    body: String,
+    updated: NaiveDateTime,
+) -> Response {
+    let digest = Sha256::digest(body.as_bytes());
+    let etag = format!(
+        "\"{}\"",
+        digest[..16]
+            .iter()
+            .map(|b| format!("{b:02x}"))
+            .collect::<String>()
+    );
+    let last_modified = updated
+        .and_utc()
+        .format("%a, %d %b %Y %H:%M:%S GMT")
+        .to_string();
+
+    let header = |name| headers.get(name).and_then(|value| value.to_str().ok());
+    let not_modified = header(IF_NONE_MATCH).is_some_and(|tags| {
+        tags.split(',')
+            .map(|tag| tag.trim().trim_start_matches("W/"))
+            .any(|tag| tag == etag || tag == "*")
+    });
+
+    let validators = [(ETAG, etag), (LAST_MODIFIED, last_modified)];
+
+    if not_modified {
+        return (StatusCode::NOT_MODIFIED, validators).into_response();
+    }
+
+    (
+        StatusCode::OK,
+        [(CONTENT_TYPE, content_type)],
+        validators,
+        body,
+    )
+        .into_response()
+}
+
+/// Escapes text for XML element content and attribute values.
+pub(crate) fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped
+}
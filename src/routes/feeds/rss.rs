@@ -0,0 +1,53 @@
+use crate::routes::{articles::Article, pages::article_url};
+
+use super::{escape, Feed};
+
+pub fn render(feed: &Feed, articles: &[Article], base_url: &str) -> String {
+    let mut xml = String::from(r#"<?xml version="1.0" encoding="utf-8"?>"#);
+    xml.push_str(concat!(
+        "\n<rss version=\"2.0\" xmlns:atom=\"http://www.w3.org/2005/Atom\" ",
+        "xmlns:dc=\"http://purl.org/dc/elements/1.1/\">\n<channel>\n"
+    ));
+    xml.push_str(&format!(
+        "<title>{2}</title>\n<link>{0}{3}</link>\n<description>{2}</description>\n\
+         <lastBuildDate>{4}</lastBuildDate>\n\
+         <atom:link rel=\"self\" type=\"application/rss+xml\" href=\"{0}{1}\"/>\n",
+        base_url,
+        escape(&feed.self_path),
+        escape(&feed.title),
+        escape(&feed.alternate_path),
+        feed.updated.and_utc().to_rfc2822(),
+    ));
+
+    for article in articles {
+        let author = article
+            .author
+            .display_name
+            .as_deref()
+            .unwrap_or(article.author.username.as_ref());
+        let published = article.published_at.unwrap_or(article.created_at);
+
+        xml.push_str(&format!(
+            "<item>\n<guid isPermaLink=\"false\">urn:uuid:{}</guid>\n<title>{}</title>\n\
+             <link>{}{}</link>\n<pubDate>{}</pubDate>\n<dc:creator>{}</dc:creator>\n",
+            article.id,
+            escape(&article.title),
+            base_url,
+            escape(&article_url(article)),
+            published.and_utc().to_rfc2822(),
+            escape(author),
+        ));
+
+        for tag in &article.tags {
+            xml.push_str(&format!("<category>{}</category>\n", escape(tag)));
+        }
+
+        xml.push_str(&format!(
+            "<description>{}</description>\n</item>\n",
+            escape(&article.html)
+        ));
+    }
+
+    xml.push_str("</channel>\n</rss>\n");
+    xml
+}
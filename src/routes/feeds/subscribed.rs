@@ -0,0 +1,57 @@
+use axum::{
+    extract::{Path, State},
+    http::HeaderMap,
+};
+use tracing::instrument;
+
+use crate::{
+    application::AppCtx,
+    domains::user::UserID,
+    routes::articles::get_subscribed::{self, get_subscribed_articles},
+    types::CountMode,
+    utils::{err::AppError, response::AppResponse},
+};
+
+use super::{
+    atom, feed_name, feed_response, tokens::find_token_owner, Feed, ATOM_CONTENT_TYPE, FEED_SIZE,
+};
+
+/// The articles `GET /articles/get-subscribed` returns, for feed readers that cannot send a JWT.
+#[instrument(skip_all)]
+pub async fn subscribed_feed(
+    ctx: State<AppCtx>,
+    Path(file): Path<String>,
+    headers: HeaderMap,
+) -> AppResponse {
+    let token = feed_name(&file, ".atom")?;
+    let user_id = find_token_owner(&ctx.db, &token)
+        .await?
+        .ok_or_else(|| AppError::NotFound("Feed not found".to_string()))?;
+
+    let payload = get_subscribed::Payload {
+        user_id: None,
+        limit: Some(FEED_SIZE),
+        offset: None,
+        after: None,
+        before: None,
+        count: CountMode::None,
+    };
+    let articles = get_subscribed_articles(&ctx.db, UserID(user_id), &payload)
+        .await?
+        .results;
+
+    let feed = Feed::new(
+        "Subscribed articles".to_string(),
+        format!("/feeds/subscribed/{file}"),
+        "/".to_string(),
+        &articles,
+    );
+    let body = atom::render(&feed, &articles, &ctx.public_url);
+
+    Ok(feed_response(
+        &headers,
+        ATOM_CONTENT_TYPE,
+        body,
+        feed.updated,
+    ))
+}
@@ -0,0 +1,132 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::PgPool;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    application::AppCtx,
+    db::DbResultExt,
+    extractors::AuthUser,
+    utils::{
+        err::AppError,
+        jwt::UserData,
+        oidc::random_token,
+        response::{AppResponse, AppResult, DataResponse},
+    },
+};
+
+#[derive(Deserialize, Serialize)]
+pub struct FeedToken {
+    /// Only returned when the token is created, it cannot be read again later.
+    pub token: String,
+    pub url: String,
+    pub created_at: NaiveDateTime,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct FeedTokenInfo {
+    pub created_at: NaiveDateTime,
+}
+
+/// Creates the private feed token, replacing the previous one so that its URL stops working.
+#[instrument(skip(ctx))]
+pub async fn regenerate_feed_token(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+) -> AppResponse {
+    let token = random_token();
+
+    let created_at = sqlx::query_scalar!(
+        r#"
+            INSERT INTO feed_tokens (user_id, token_hash) VALUES ($1, $2)
+            ON CONFLICT (user_id) DO UPDATE SET
+                token_hash = EXCLUDED.token_hash,
+                created_at = NOW()
+            RETURNING created_at
+        "#,
+        user.user_id.as_ref(),
+        hash_token(&token),
+    )
+    .fetch_one(&ctx.db)
+    .await
+    .trace_db("Failed to store feed token")?;
+
+    let feed_token = FeedToken {
+        url: format!("{}/feeds/subscribed/{token}.atom", ctx.public_url),
+        token,
+        created_at,
+    };
+
+    Ok((StatusCode::CREATED, DataResponse::new(feed_token)).into_response())
+}
+
+#[instrument(skip(ctx))]
+pub async fn get_feed_token(ctx: State<AppCtx>, AuthUser(user): AuthUser<UserData>) -> AppResponse {
+    let created_at = sqlx::query_scalar!(
+        "SELECT created_at FROM feed_tokens WHERE user_id = $1",
+        user.user_id.as_ref(),
+    )
+    .fetch_optional(&ctx.db)
+    .await
+    .trace_db("Failed to fetch feed token")?
+    .ok_or_else(feed_token_not_found)?;
+
+    Ok((
+        StatusCode::OK,
+        DataResponse::new(FeedTokenInfo { created_at }),
+    )
+        .into_response())
+}
+
+#[instrument(skip(ctx))]
+pub async fn revoke_feed_token(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+) -> AppResponse {
+    let revoked = sqlx::query!(
+        "DELETE FROM feed_tokens WHERE user_id = $1",
+        user.user_id.as_ref(),
+    )
+    .execute(&ctx.db)
+    .await
+    .trace_db("Failed to revoke feed token")?
+    .rows_affected();
+
+    if revoked == 0 {
+        return Err(feed_token_not_found());
+    }
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+/// Owner of the feed token, only active users count.
+#[instrument(skip_all)]
+pub(super) async fn find_token_owner(pool: &PgPool, token: &str) -> AppResult<Option<Uuid>> {
+    let user_id = sqlx::query_scalar!(
+        r#"
+            SELECT t.user_id
+            FROM feed_tokens t
+            JOIN users u ON u.id = t.user_id
+            WHERE t.token_hash = $1 AND u.active
+        "#,
+        hash_token(token),
+    )
+    .fetch_optional(pool)
+    .await
+    .trace_db("Failed to find feed token")?;
+
+    Ok(user_id)
+}
+
+/// Tokens are random, so a plain digest is enough to keep them unreadable at rest.
+fn hash_token(token: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(token.as_bytes()))
+}
+
+fn feed_token_not_found() -> AppError {
+    AppError::NotFound("Feed token not found".to_string())
+}
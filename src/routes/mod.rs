@@ -1,10 +1,25 @@
+pub mod admin;
 pub mod articles;
 pub mod auth;
+pub mod feeds;
+pub mod pages;
+pub mod scim;
+pub mod tags;
+pub mod uploads;
+pub mod users;
 
 use axum::Router;
 
 use crate::application::AppCtx;
 
 pub fn routes() -> Router<AppCtx> {
-    auth::routes().merge(articles::routes())
+    auth::routes()
+        .merge(articles::routes())
+        .merge(admin::routes())
+        .merge(scim::routes())
+        .merge(uploads::routes())
+        .merge(users::routes())
+        .merge(pages::routes())
+        .merge(feeds::routes())
+        .merge(tags::routes())
 }
@@ -0,0 +1,177 @@
+use axum::{
+    extract::{Path, Query, State},
+    response::{IntoResponse, Redirect, Response},
+};
+use maud::html;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+use validator::Validate;
+
+use crate::{
+    application::AppCtx,
+    domains::user::Username,
+    extractors::MaybeSessionUser,
+    routes::{
+        articles::{get_article::fetch_visible_article, list, slugs::find_article_id},
+        users::profile::fetch_profile,
+    },
+    types::CountMode,
+    utils::{err::AppError, jwt::UserData, markdown},
+};
+
+use super::{article_body, article_list, article_url, author_url, layout, with_query, PageResult};
+
+const PAGE_SIZE: u64 = 20;
+const DESCRIPTION_LEN: usize = 160;
+
+/// Filters of the index page. Empty form fields are treated as unset.
+#[derive(Deserialize, Serialize, Debug, Default)]
+pub struct IndexQuery {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub after: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PageQuery {
+    pub after: Option<String>,
+}
+
+#[instrument(skip(ctx))]
+pub async fn index(
+    ctx: State<AppCtx>,
+    MaybeSessionUser(session): MaybeSessionUser<UserData>,
+    Query(query): Query<IndexQuery>,
+) -> PageResult {
+    let filters = IndexQuery {
+        tag: non_empty(query.tag),
+        author: non_empty(query.author),
+        after: None,
+    };
+    let payload = list::Payload {
+        limit: Some(PAGE_SIZE),
+        after: non_empty(query.after),
+        tag: filters.tag.clone(),
+        author: filters.author.clone().map(Username),
+        count: CountMode::None,
+        ..Default::default()
+    };
+    payload.validate().map_err(AppError::from)?;
+
+    let viewer = session.as_ref().map(|session| &session.user_id);
+    let articles = list::get_articles_list(&payload, viewer, &ctx.search_language, &ctx.db).await?;
+
+    let content = html! {
+        h1 { "Articles" }
+        form method="get" action="/" {
+            label { "Tag " input type="text" name="tag" value=[filters.tag.as_deref()]; }
+            " "
+            label { "Author " input type="text" name="author" value=[filters.author.as_deref()]; }
+            " "
+            button type="submit" { "Filter" }
+        }
+        (article_list(&articles, |cursor| {
+            let next = IndexQuery {
+                after: Some(cursor.to_string()),
+                tag: filters.tag.clone(),
+                author: filters.author.clone(),
+            };
+            with_query("/", &next)
+        }))
+    };
+
+    Ok(layout("Articles", None, session.as_ref(), content))
+}
+
+#[instrument(skip(ctx))]
+pub async fn author_page(
+    ctx: State<AppCtx>,
+    MaybeSessionUser(session): MaybeSessionUser<UserData>,
+    Path(username): Path<Username>,
+    Query(query): Query<PageQuery>,
+) -> PageResult {
+    let viewer = session.as_ref().map(|session| &session.user_id);
+    let profile = fetch_profile(&ctx.db, &username, viewer)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("User not found: {username}")))?;
+
+    let payload = list::Payload {
+        limit: Some(PAGE_SIZE),
+        after: non_empty(query.after),
+        author: Some(profile.username.clone()),
+        count: CountMode::None,
+        ..Default::default()
+    };
+    let articles = list::get_articles_list(&payload, viewer, &ctx.search_language, &ctx.db).await?;
+
+    let name = profile
+        .display_name
+        .as_deref()
+        .unwrap_or(profile.username.as_ref());
+    let url = author_url(&profile.username);
+
+    let content = html! {
+        header {
+            @if let Some(avatar_url) = &profile.avatar_url {
+                img src=(avatar_url) alt="" width="96" height="96";
+            }
+            h1 { (name) }
+            @if let Some(bio) = &profile.bio {
+                p { (bio) }
+            }
+            @if let Some(website) = &profile.website {
+                p { a href=(website) rel="nofollow ugc noopener" { (website) } }
+            }
+            p {
+                (profile.follower_count) " followers · "
+                (profile.following_count) " following"
+            }
+        }
+        (article_list(&articles, |cursor| with_query(&url, &[("after", cursor)])))
+    };
+
+    Ok(layout(
+        name,
+        profile.bio.as_deref(),
+        session.as_ref(),
+        content,
+    ))
+}
+
+/// Former slugs redirect to the current one, like `GET /articles/by-slug/:author/:slug`.
+#[instrument(skip(ctx))]
+pub async fn article_page(
+    ctx: State<AppCtx>,
+    MaybeSessionUser(session): MaybeSessionUser<UserData>,
+    Path((author, slug)): Path<(Username, String)>,
+) -> PageResult<Response> {
+    let article_id = find_article_id(&ctx.db, &author, &slug)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Article not found: {author}/{slug}")))?;
+
+    let viewer = session.as_ref().map(|session| &session.user_id);
+    let article = fetch_visible_article(&ctx.db, &article_id, viewer).await?;
+
+    if article.slug != slug {
+        return Ok(Redirect::permanent(&article_url(&article)).into_response());
+    }
+
+    let description = markdown::summary(&article.text, DESCRIPTION_LEN);
+    let page = layout(
+        &article.title,
+        Some(&description),
+        session.as_ref(),
+        article_body(&article),
+    );
+
+    Ok(page.into_response())
+}
+
+fn non_empty(value: Option<String>) -> Option<String> {
+    value.filter(|value| !value.trim().is_empty())
+}
@@ -0,0 +1,188 @@
+pub mod articles;
+pub mod session;
+
+use axum::{
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Router,
+};
+use maud::{html, Markup, PreEscaped, DOCTYPE};
+use serde::Serialize;
+use tracing::error;
+
+use crate::{
+    application::AppCtx,
+    domains::user::Username,
+    parsers::format_errors,
+    routes::articles::Article,
+    types::SearchType,
+    utils::{err::AppError, jwt::UserData, url::path_segment},
+};
+
+/// Server-rendered pages for readers without JavaScript, built from the same loaders as the
+/// JSON routes.
+pub fn routes() -> Router<AppCtx> {
+    Router::new()
+        .route("/", get(articles::index))
+        .route("/authors/:username", get(articles::author_page))
+        .route("/authors/:username/:slug", get(articles::article_page))
+        .route("/login", get(session::login_form).post(session::login))
+        .route(
+            "/register",
+            get(session::register_form).post(session::register),
+        )
+        .route("/logout", post(session::logout))
+}
+
+pub type PageResult<T = Markup> = Result<T, PageError>;
+
+/// Renders an `AppError` as an HTML page instead of JSON.
+pub struct PageError(AppError);
+
+impl From<AppError> for PageError {
+    fn from(err: AppError) -> Self {
+        Self(err)
+    }
+}
+
+impl IntoResponse for PageError {
+    fn into_response(self) -> Response {
+        let status = self.0.status_code();
+        let title = status.canonical_reason().unwrap_or("Error");
+
+        let content = html! {
+            h1 { (title) }
+            p { (error_message(&self.0)) }
+            p { a href="/" { "Back to the articles" } }
+        };
+
+        (status, layout(title, None, None, content)).into_response()
+    }
+}
+
+/// What a reader may see of `err`, internal details are only traced.
+fn error_message(err: &AppError) -> String {
+    match err {
+        AppError::ValidationError(e) => format_errors(e).join("; "),
+        AppError::DbError(_) => "Internal Server Error".to_string(),
+        AppError::InternalServerError(e) => {
+            error!("Internal server error: {}", e);
+            "Internal Server Error".to_string()
+        }
+        _ => err.to_string(),
+    }
+}
+
+fn layout(
+    title: &str,
+    description: Option<&str>,
+    session: Option<&UserData>,
+    content: Markup,
+) -> Markup {
+    html! {
+        (DOCTYPE)
+        html lang="en" {
+            head {
+                meta charset="utf-8";
+                meta name="viewport" content="width=device-width, initial-scale=1";
+                title { (title) }
+                link rel="alternate" type="application/atom+xml" title="Articles" href="/feeds/articles.atom";
+                @if let Some(description) = description {
+                    meta name="description" content=(description);
+                }
+            }
+            body {
+                header {
+                    nav {
+                        a href="/" { "Articles" }
+                        " "
+                        @if session.is_some() {
+                            form method="post" action="/logout" {
+                                button type="submit" { "Log out" }
+                            }
+                        } @else {
+                            a href="/login" { "Log in" }
+                            " "
+                            a href="/register" { "Register" }
+                        }
+                    }
+                }
+                main { (content) }
+            }
+        }
+    }
+}
+
+pub(crate) fn author_url(username: &Username) -> String {
+    format!("/authors/{}", path_segment(username.as_ref()))
+}
+
+pub(crate) fn article_url(article: &Article) -> String {
+    format!(
+        "{}/{}",
+        author_url(&article.author.username),
+        path_segment(&article.slug)
+    )
+}
+
+fn author_name(article: &Article) -> &str {
+    article
+        .author
+        .display_name
+        .as_deref()
+        .unwrap_or(article.author.username.as_ref())
+}
+
+/// Article teasers followed by a link to the next page, which keeps the current filters.
+fn article_list(articles: &SearchType<Article>, next_page: impl Fn(&str) -> String) -> Markup {
+    html! {
+        @if articles.results.is_empty() {
+            p { "No articles yet." }
+        }
+        @for article in &articles.results {
+            article {
+                h2 { a href=(article_url(article)) { (article.title) } }
+                p {
+                    "by "
+                    a href=(author_url(&article.author.username)) { (author_name(article)) }
+                    " · "
+                    time datetime=(article.created_at.format("%Y-%m-%dT%H:%M:%S")) {
+                        (article.created_at.format("%B %-d, %Y"))
+                    }
+                    " · " (article.reading_time_minutes) " min read"
+                }
+                @if !article.tags.is_empty() {
+                    ul {
+                        @for tag in &article.tags {
+                            li { a href=(with_query("/", &[("tag", tag)])) { (tag) } }
+                        }
+                    }
+                }
+            }
+        }
+        @if let Some(cursor) = &articles.next_cursor {
+            nav { a rel="next" href=(next_page(cursor)) { "Older articles" } }
+        }
+    }
+}
+
+fn article_body(article: &Article) -> Markup {
+    html! {
+        article {
+            h1 { (article.title) }
+            p {
+                "by "
+                a href=(author_url(&article.author.username)) { (author_name(article)) }
+                " · " (article.reading_time_minutes) " min read"
+            }
+            (PreEscaped(&article.html))
+        }
+    }
+}
+
+pub(crate) fn with_query(path: &str, query: &impl Serialize) -> String {
+    match serde_urlencoded::to_string(query) {
+        Ok(query) if !query.is_empty() => format!("{path}?{query}"),
+        _ => path.to_string(),
+    }
+}
@@ -0,0 +1,183 @@
+use axum::{
+    extract::State,
+    http::header::SET_COOKIE,
+    response::{IntoResponse, Redirect, Response},
+    Form,
+};
+use maud::{html, Markup};
+use serde::Deserialize;
+use tracing::instrument;
+use validator::Validate;
+
+use crate::{
+    application::AppCtx,
+    configuration::RegistrationMode,
+    domains::user::{Email, Password, UserID, Username},
+    extractors::{MaybeSessionUser, SameOrigin, SESSION_COOKIE},
+    routes::auth::{login, register},
+    utils::{err::AppError, jwt::UserData, response::AppResult},
+};
+
+use super::{error_message, layout};
+
+#[derive(Deserialize)]
+pub struct LoginForm {
+    pub username: String,
+    pub password: String,
+}
+
+#[derive(Deserialize)]
+pub struct RegisterForm {
+    pub username: String,
+    pub email: String,
+    pub password: String,
+
+    #[serde(default)]
+    pub invite_code: String,
+}
+
+pub async fn login_form(MaybeSessionUser(session): MaybeSessionUser<UserData>) -> Response {
+    if session.is_some() {
+        return Redirect::to("/").into_response();
+    }
+
+    login_page("", None).into_response()
+}
+
+#[instrument(skip_all)]
+pub async fn login(ctx: State<AppCtx>, _: SameOrigin, Form(form): Form<LoginForm>) -> Response {
+    let username = form.username.clone();
+
+    match sign_in(&ctx, form).await {
+        Ok(response) => response,
+        Err(err) => (err.status_code(), login_page(&username, Some(&err))).into_response(),
+    }
+}
+
+async fn sign_in(ctx: &AppCtx, form: LoginForm) -> AppResult<Response> {
+    let payload = login::Payload {
+        username: Username(form.username),
+        password: Password(form.password),
+    };
+    payload.validate()?;
+
+    let user = login::check_credentials(ctx, payload).await?;
+
+    // The pages have no passkey flow, such accounts have to sign in through the API
+    if user.second_factor_required {
+        return Err(AppError::BadRequest(
+            "This account requires a passkey, sign in with an app that supports it".to_string(),
+        ));
+    }
+
+    start_session(ctx, user.id)
+}
+
+pub async fn register_form(
+    ctx: State<AppCtx>,
+    MaybeSessionUser(session): MaybeSessionUser<UserData>,
+) -> Response {
+    if session.is_some() {
+        return Redirect::to("/").into_response();
+    }
+
+    register_page(ctx.registration_mode, "", "", None).into_response()
+}
+
+#[instrument(skip_all)]
+pub async fn register(
+    ctx: State<AppCtx>,
+    _: SameOrigin,
+    Form(form): Form<RegisterForm>,
+) -> Response {
+    let (username, email) = (form.username.clone(), form.email.clone());
+
+    match sign_up(&ctx, form).await {
+        Ok(response) => response,
+        Err(err) => {
+            let page = register_page(ctx.registration_mode, &username, &email, Some(&err));
+            (err.status_code(), page).into_response()
+        }
+    }
+}
+
+async fn sign_up(ctx: &AppCtx, form: RegisterForm) -> AppResult<Response> {
+    let payload = register::Payload {
+        username: Username(form.username),
+        password: Password(form.password),
+        email: Email(form.email),
+        invite_code: Some(form.invite_code).filter(|code| !code.is_empty()),
+    };
+    payload.validate()?;
+
+    let user_id = register::register_user(ctx, payload).await?;
+
+    start_session(ctx, UserID(user_id))
+}
+
+pub async fn logout(ctx: State<AppCtx>, _: SameOrigin) -> Response {
+    let cookie = session_cookie("", 0, ctx.secure_cookies);
+    ([(SET_COOKIE, cookie)], Redirect::to("/")).into_response()
+}
+
+/// Stores a fresh access token in the session cookie, which expires together with the token.
+fn start_session(ctx: &AppCtx, user_id: UserID) -> AppResult<Response> {
+    let token = ctx.tokens.sign(user_id)?;
+    let cookie = session_cookie(&token, ctx.tokens.ttl().as_secs(), ctx.secure_cookies);
+
+    Ok(([(SET_COOKIE, cookie)], Redirect::to("/")).into_response())
+}
+
+/// `SameSite=Lax` keeps the cookie out of requests other sites make in the background.
+/// It doesn't stop a cross-site form from setting a session, which is what `SameOrigin` is for.
+fn session_cookie(value: &str, max_age: u64, secure: bool) -> String {
+    let secure = if secure { "; Secure" } else { "" };
+    format!("{SESSION_COOKIE}={value}; Path=/; Max-Age={max_age}; HttpOnly; SameSite=Lax{secure}")
+}
+
+fn login_page(username: &str, error: Option<&AppError>) -> Markup {
+    let content = html! {
+        h1 { "Log in" }
+        @if let Some(error) = error {
+            p role="alert" { (error_message(error)) }
+        }
+        form method="post" action="/login" {
+            p { label { "Username " input type="text" name="username" value=(username) required; } }
+            p { label { "Password " input type="password" name="password" required; } }
+            button type="submit" { "Log in" }
+        }
+        p { "No account yet? " a href="/register" { "Register" } }
+    };
+
+    layout("Log in", None, None, content)
+}
+
+fn register_page(
+    mode: RegistrationMode,
+    username: &str,
+    email: &str,
+    error: Option<&AppError>,
+) -> Markup {
+    let content = html! {
+        h1 { "Register" }
+        @if mode == RegistrationMode::Closed {
+            p { "Registration is closed." }
+        } @else {
+            @if let Some(error) = error {
+                p role="alert" { (error_message(error)) }
+            }
+            form method="post" action="/register" {
+                p { label { "Username " input type="text" name="username" value=(username) required; } }
+                p { label { "Email " input type="email" name="email" value=(email) required; } }
+                p { label { "Password " input type="password" name="password" required; } }
+                @if mode == RegistrationMode::InviteOnly {
+                    p { label { "Invite code " input type="text" name="invite_code" required; } }
+                }
+                button type="submit" { "Register" }
+            }
+        }
+        p { "Already registered? " a href="/login" { "Log in" } }
+    };
+
+    layout("Register", None, None, content)
+}
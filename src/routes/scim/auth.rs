@@ -0,0 +1,47 @@
+use axum::{
+    async_trait,
+    extract::{FromRef, FromRequestParts},
+    http::{header::AUTHORIZATION, request::Parts},
+};
+
+use crate::{application::AppCtx, utils::err::AppError};
+
+use super::ScimError;
+
+/// Marks a request authenticated with the SCIM provisioning bearer token.
+pub struct ProvisioningClient;
+
+#[async_trait]
+impl<S> FromRequestParts<S> for ProvisioningClient
+where
+    S: Sync + Send,
+    AppCtx: FromRef<S>,
+{
+    type Rejection = ScimError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let ctx = AppCtx::from_ref(state);
+        let expected = ctx.scim_token.as_ref().ok_or(AppError::Unauthorized)?;
+
+        let provided = parts
+            .headers
+            .get(AUTHORIZATION)
+            .and_then(|header| header.to_str().ok())
+            .and_then(|header| header.strip_prefix("Bearer "))
+            .ok_or(AppError::Unauthorized)?;
+
+        if !constant_time_eq(provided.as_bytes(), expected.as_bytes()) {
+            return Err(AppError::Unauthorized.into());
+        }
+
+        Ok(Self)
+    }
+}
+
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+
+    a.iter().zip(b).fold(0, |acc, (x, y)| acc | (x ^ y)) == 0
+}
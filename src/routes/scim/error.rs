@@ -0,0 +1,105 @@
+use axum::{
+    http::{header::CONTENT_TYPE, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use tracing::error;
+
+use crate::{parsers::format_errors, utils::err::AppError};
+
+use super::{ERROR_SCHEMA, SCIM_CONTENT_TYPE};
+
+/// Error rendered in the SCIM error format (RFC 7644, section 3.12).
+#[derive(Debug)]
+pub struct ScimError {
+    status: StatusCode,
+    scim_type: Option<&'static str>,
+    detail: String,
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct ErrorBody {
+    schemas: [&'static str; 1],
+    status: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    scim_type: Option<&'static str>,
+    detail: String,
+}
+
+impl ScimError {
+    pub fn bad_request(scim_type: &'static str, detail: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::BAD_REQUEST,
+            scim_type: Some(scim_type),
+            detail: detail.into(),
+        }
+    }
+
+    /// Error for a request the extractors could not parse.
+    pub(super) fn rejected(
+        status: StatusCode,
+        scim_type: Option<&'static str>,
+        detail: String,
+    ) -> Self {
+        Self {
+            status,
+            scim_type,
+            detail,
+        }
+    }
+
+    pub fn not_found(id: &str) -> Self {
+        Self {
+            status: StatusCode::NOT_FOUND,
+            scim_type: None,
+            detail: format!("User {id} not found"),
+        }
+    }
+}
+
+impl From<AppError> for ScimError {
+    fn from(err: AppError) -> Self {
+        match err {
+            AppError::ValidationError(e) => {
+                Self::bad_request("invalidValue", format_errors(&e).join("; "))
+            }
+
+            AppError::DuplicatedUser => Self {
+                status: StatusCode::CONFLICT,
+                scim_type: Some("uniqueness"),
+                detail: err.to_string(),
+            },
+
+            AppError::DbError(_) | AppError::InternalServerError(_) => {
+                error!("SCIM request failed: {}", err);
+
+                Self {
+                    status: StatusCode::INTERNAL_SERVER_ERROR,
+                    scim_type: None,
+                    detail: "Internal Server Error".to_string(),
+                }
+            }
+
+            _ => Self {
+                status: err.status_code(),
+                scim_type: None,
+                detail: err.to_string(),
+            },
+        }
+    }
+}
+
+impl IntoResponse for ScimError {
+    fn into_response(self) -> Response {
+        let body = ErrorBody {
+            schemas: [ERROR_SCHEMA],
+            status: self.status.as_u16().to_string(),
+            scim_type: self.scim_type,
+            detail: self.detail,
+        };
+
+        (self.status, [(CONTENT_TYPE, SCIM_CONTENT_TYPE)], Json(body)).into_response()
+    }
+}
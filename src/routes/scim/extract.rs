@@ -0,0 +1,71 @@
+use axum::{
+    async_trait,
+    extract::{
+        rejection::{JsonRejection, QueryRejection},
+        FromRequest, FromRequestParts, Query, Request,
+    },
+    http::{request::Parts, StatusCode},
+    Json,
+};
+use serde::de::DeserializeOwned;
+
+use super::ScimError;
+
+/// `Json` whose rejections are rendered as SCIM errors.
+pub struct ScimJson<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ScimJson<T>
+where
+    S: Sync + Send,
+    T: DeserializeOwned,
+{
+    type Rejection = ScimError;
+
+    async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(req, state).await?;
+
+        Ok(ScimJson(value))
+    }
+}
+
+/// `Query` whose rejections are rendered as SCIM errors.
+pub struct ScimQuery<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ScimQuery<T>
+where
+    S: Sync + Send,
+    T: DeserializeOwned,
+{
+    type Rejection = ScimError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let Query(value) = Query::<T>::from_request_parts(parts, state).await?;
+
+        Ok(ScimQuery(value))
+    }
+}
+
+impl From<JsonRejection> for ScimError {
+    fn from(rejection: JsonRejection) -> Self {
+        // SCIM reports bodies that do not match the schema as 400 rather than axum's 422
+        let (status, scim_type) = match rejection {
+            JsonRejection::JsonSyntaxError(_) => (StatusCode::BAD_REQUEST, Some("invalidSyntax")),
+            JsonRejection::JsonDataError(_) => (StatusCode::BAD_REQUEST, Some("invalidValue")),
+            _ => (rejection.status(), None),
+        };
+
+        Self::rejected(status, scim_type, rejection.body_text())
+    }
+}
+
+impl From<QueryRejection> for ScimError {
+    fn from(rejection: QueryRejection) -> Self {
+        Self::rejected(
+            rejection.status(),
+            Some("invalidValue"),
+            rejection.body_text(),
+        )
+    }
+}
@@ -0,0 +1,190 @@
+use chrono::NaiveDateTime;
+use sqlx::PgPool;
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    db::DbResultExt,
+    domains::user::{Email, Password, Username},
+    utils::{err::AppError, response::AppResult},
+};
+
+use super::ScimUser;
+
+pub struct ScimUserRow {
+    pub id: Uuid,
+    pub username: String,
+    pub email: String,
+    pub external_id: Option<String>,
+    pub active: bool,
+    pub created_at: NaiveDateTime,
+    pub updated_at: NaiveDateTime,
+}
+
+impl From<ScimUserRow> for ScimUser {
+    fn from(row: ScimUserRow) -> Self {
+        ScimUser::new(
+            row.id,
+            Username(row.username),
+            Email(row.email),
+            row.external_id,
+            row.active,
+            row.created_at,
+            row.updated_at,
+        )
+    }
+}
+
+/// Attributes of a user as they should be stored after a create or patch.
+#[derive(Debug)]
+pub struct ScimUserChanges {
+    pub username: Username,
+    pub email: Email,
+    pub external_id: Option<String>,
+    pub active: bool,
+    /// Raw password while the request is processed, replaced with its hash before storing.
+    pub password: Option<Password>,
+}
+
+impl From<ScimUserRow> for ScimUserChanges {
+    fn from(row: ScimUserRow) -> Self {
+        ScimUserChanges {
+            username: Username(row.username),
+            email: Email(row.email),
+            external_id: row.external_id,
+            active: row.active,
+            password: None,
+        }
+    }
+}
+
+#[instrument(skip(pool))]
+pub async fn count_users(pool: &PgPool, username: Option<&Username>) -> AppResult<i64> {
+    let count = sqlx::query_scalar!(
+        r#"
+            SELECT COUNT(*) AS "count!"
+            FROM public.users
+            WHERE ($1::TEXT IS NULL OR username = $1)
+        "#,
+        username.map(|u| u.as_ref())
+    )
+    .fetch_one(pool)
+    .await
+    .trace_db("Failed to count SCIM users")?;
+
+    Ok(count)
+}
+
+#[instrument(skip(pool))]
+pub async fn list_users(
+    pool: &PgPool,
+    username: Option<&Username>,
+    limit: i64,
+    offset: i64,
+) -> AppResult<Vec<ScimUserRow>> {
+    let users = sqlx::query_as!(
+        ScimUserRow,
+        r#"
+            SELECT id, username, email, external_id, active, created_at, updated_at
+            FROM public.users
+            WHERE ($1::TEXT IS NULL OR username = $1)
+            ORDER BY created_at, id
+            LIMIT $2 OFFSET $3
+        "#,
+        username.map(|u| u.as_ref()),
+        limit,
+        offset
+    )
+    .fetch_all(pool)
+    .await
+    .trace_db("Failed to list SCIM users")?;
+
+    Ok(users)
+}
+
+#[instrument(skip(pool))]
+pub async fn get_user(pool: &PgPool, id: &Uuid) -> AppResult<Option<ScimUserRow>> {
+    let user = sqlx::query_as!(
+        ScimUserRow,
+        r#"
+            SELECT id, username, email, external_id, active, created_at, updated_at
+            FROM public.users
+            WHERE id = $1
+        "#,
+        id
+    )
+    .fetch_optional(pool)
+    .await
+    .trace_db("Failed to fetch SCIM user")?;
+
+    Ok(user)
+}
+
+#[instrument(skip(pool))]
+pub async fn insert_user(pool: &PgPool, user: &ScimUserChanges) -> AppResult<ScimUserRow> {
+    sqlx::query_as!(
+        ScimUserRow,
+        r#"
+            INSERT INTO public.users (username, email, password, external_id, active, email_verified)
+            VALUES ($1, $2, $3, $4, $5, TRUE)
+            RETURNING id, username, email, external_id, active, created_at, updated_at
+        "#,
+        user.username.as_ref(),
+        user.email.as_ref(),
+        user.password.as_ref().map(|p| p.as_ref()),
+        user.external_id,
+        user.active
+    )
+    .fetch_one(pool)
+    .await
+    .with_unique_violation(AppError::DuplicatedUser, "Failed to provision SCIM user")
+}
+
+#[instrument(skip(pool))]
+pub async fn update_user(
+    pool: &PgPool,
+    id: &Uuid,
+    user: &ScimUserChanges,
+) -> AppResult<Option<ScimUserRow>> {
+    sqlx::query_as!(
+        ScimUserRow,
+        r#"
+            UPDATE public.users
+            SET
+                username = $2,
+                email = $3,
+                external_id = $4,
+                active = $5,
+                password = COALESCE($6, password),
+                updated_at = NOW()
+            WHERE id = $1
+            RETURNING id, username, email, external_id, active, created_at, updated_at
+        "#,
+        id,
+        user.username.as_ref(),
+        user.email.as_ref(),
+        user.external_id,
+        user.active,
+        user.password.as_ref().map(|p| p.as_ref())
+    )
+    .fetch_optional(pool)
+    .await
+    .with_unique_violation(AppError::DuplicatedUser, "Failed to update SCIM user")
+}
+
+#[instrument(skip(pool))]
+pub async fn deactivate_user(pool: &PgPool, id: &Uuid) -> AppResult<bool> {
+    let result = sqlx::query!(
+        r#"
+            UPDATE public.users
+            SET active = FALSE, updated_at = NOW()
+            WHERE id = $1
+        "#,
+        id
+    )
+    .execute(pool)
+    .await
+    .trace_db("Failed to deactivate SCIM user")?;
+
+    Ok(result.rows_affected() > 0)
+}
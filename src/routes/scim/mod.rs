@@ -0,0 +1,28 @@
+mod auth;
+mod error;
+mod extract;
+mod loader;
+mod schema;
+mod users;
+
+use axum::{routing::get, Router};
+
+use crate::application::AppCtx;
+
+pub use auth::ProvisioningClient;
+pub use error::ScimError;
+pub use schema::*;
+
+pub fn routes() -> Router<AppCtx> {
+    Router::new()
+        .route(
+            "/scim/v2/Users",
+            get(users::list_users).post(users::create_user),
+        )
+        .route(
+            "/scim/v2/Users/:id",
+            get(users::get_user)
+                .patch(users::patch_user)
+                .delete(users::delete_user),
+        )
+}
@@ -0,0 +1,171 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::domains::user::{Email, Password, Username};
+
+pub const SCIM_CONTENT_TYPE: &str = "application/scim+json";
+pub const USER_SCHEMA: &str = "urn:ietf:params:scim:schemas:core:2.0:User";
+pub const LIST_RESPONSE_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:ListResponse";
+pub const PATCH_OP_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:PatchOp";
+pub const ERROR_SCHEMA: &str = "urn:ietf:params:scim:api:messages:2.0:Error";
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ScimUser {
+    pub schemas: Vec<String>,
+    pub id: Uuid,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub external_id: Option<String>,
+    pub user_name: Username,
+    pub emails: Vec<ScimEmail>,
+    pub active: bool,
+    pub meta: Meta,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ScimEmail {
+    pub value: Email,
+
+    #[serde(default)]
+    pub primary: bool,
+
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub kind: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Meta {
+    pub resource_type: String,
+    pub created: DateTime<Utc>,
+    pub last_modified: DateTime<Utc>,
+    pub location: String,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct ListResponse<T> {
+    pub schemas: Vec<String>,
+    pub total_results: u64,
+    pub start_index: u64,
+    pub items_per_page: u64,
+    #[serde(rename = "Resources")]
+    pub resources: Vec<T>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CreateUserPayload {
+    pub user_name: Username,
+    pub external_id: Option<String>,
+
+    #[serde(default)]
+    pub emails: Vec<ScimEmail>,
+
+    pub password: Option<Password>,
+
+    #[serde(default = "default_active", deserialize_with = "deserialize_bool")]
+    pub active: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PatchPayload {
+    #[serde(rename = "Operations")]
+    pub operations: Vec<PatchOperation>,
+}
+
+#[derive(Serialize, Deserialize, Debug)]
+pub struct PatchOperation {
+    pub op: PatchOp,
+    pub path: Option<String>,
+    pub value: Option<Value>,
+}
+
+#[derive(Serialize, Debug, PartialEq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum PatchOp {
+    Add,
+    Replace,
+    Remove,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ListQuery {
+    pub filter: Option<String>,
+    pub start_index: Option<u64>,
+    pub count: Option<u64>,
+}
+
+impl ScimUser {
+    pub fn new(
+        id: Uuid,
+        user_name: Username,
+        email: Email,
+        external_id: Option<String>,
+        active: bool,
+        created_at: NaiveDateTime,
+        updated_at: NaiveDateTime,
+    ) -> Self {
+        Self {
+            schemas: vec![USER_SCHEMA.to_string()],
+            id,
+            external_id,
+            user_name,
+            emails: vec![ScimEmail {
+                value: email,
+                primary: true,
+                kind: Some("work".to_string()),
+            }],
+            active,
+            meta: Meta {
+                resource_type: "User".to_string(),
+                created: created_at.and_utc(),
+                last_modified: updated_at.and_utc(),
+                location: format!("/scim/v2/Users/{id}"),
+            },
+        }
+    }
+}
+
+/// Picks the primary address, falling back to the first one listed.
+pub fn primary_email(emails: &[ScimEmail]) -> Option<&Email> {
+    emails
+        .iter()
+        .find(|e| e.primary)
+        .or(emails.first())
+        .map(|e| &e.value)
+}
+
+fn default_active() -> bool {
+    true
+}
+
+/// Some identity providers send booleans as `"True"`/`"False"` strings.
+pub fn value_as_bool(value: &Value) -> Option<bool> {
+    match value {
+        Value::Bool(b) => Some(*b),
+        Value::String(s) => s.to_lowercase().parse().ok(),
+        _ => None,
+    }
+}
+
+fn deserialize_bool<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    let value = Value::deserialize(deserializer)?;
+    value_as_bool(&value).ok_or_else(|| serde::de::Error::custom("expected a boolean"))
+}
+
+impl<'de> Deserialize<'de> for PatchOp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let op = String::deserialize(deserializer)?;
+
+        match op.to_lowercase().as_str() {
+            "add" => Ok(PatchOp::Add),
+            "replace" => Ok(PatchOp::Replace),
+            "remove" => Ok(PatchOp::Remove),
+            _ => Err(serde::de::Error::custom(format!("unknown operation: {op}"))),
+        }
+    }
+}
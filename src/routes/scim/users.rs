@@ -0,0 +1,318 @@
+use axum::{
+    extract::{Path, State},
+    http::{
+        header::{CONTENT_TYPE, LOCATION},
+        StatusCode,
+    },
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::Serialize;
+use serde_json::Value;
+use tracing::instrument;
+use uuid::Uuid;
+use validator::ValidationErrors;
+
+use crate::{
+    application::AppCtx,
+    domains::user::{Email, Password, Username},
+    parsers::user::{validate_email, validate_password, validate_username},
+    utils::{err::AppError, password::hash_password},
+};
+
+use super::{
+    extract::{ScimJson, ScimQuery},
+    loader::{self, ScimUserChanges},
+    primary_email, value_as_bool, CreateUserPayload, ListQuery, ListResponse, PatchOp,
+    PatchOperation, PatchPayload, ProvisioningClient, ScimEmail, ScimError, ScimUser,
+    LIST_RESPONSE_SCHEMA, SCIM_CONTENT_TYPE,
+};
+
+type ScimResponse = Result<Response, ScimError>;
+
+const DEFAULT_PAGE_SIZE: u64 = 100;
+const MAX_PAGE_SIZE: u64 = 1000;
+
+#[instrument(skip(ctx, _client))]
+pub async fn list_users(
+    _client: ProvisioningClient,
+    ctx: State<AppCtx>,
+    ScimQuery(query): ScimQuery<ListQuery>,
+) -> ScimResponse {
+    let username = query.filter.as_deref().map(parse_filter).transpose()?;
+
+    let start_index = query.start_index.unwrap_or(1).max(1);
+    let count = query.count.unwrap_or(DEFAULT_PAGE_SIZE).min(MAX_PAGE_SIZE);
+
+    let total = loader::count_users(&ctx.db, username.as_ref()).await?;
+    let users = loader::list_users(
+        &ctx.db,
+        username.as_ref(),
+        count as i64,
+        (start_index - 1) as i64,
+    )
+    .await?;
+
+    let resources: Vec<ScimUser> = users.into_iter().map(ScimUser::from).collect();
+
+    let body = ListResponse {
+        schemas: vec![LIST_RESPONSE_SCHEMA.to_string()],
+        total_results: total as u64,
+        start_index,
+        items_per_page: resources.len() as u64,
+        resources,
+    };
+
+    Ok(scim_json(StatusCode::OK, body))
+}
+
+#[instrument(skip(ctx, _client))]
+pub async fn get_user(
+    _client: ProvisioningClient,
+    ctx: State<AppCtx>,
+    Path(id): Path<String>,
+) -> ScimResponse {
+    let user = find_user(&ctx, &id).await?;
+    Ok(scim_json(StatusCode::OK, ScimUser::from(user)))
+}
+
+#[instrument(skip(ctx, _client))]
+pub async fn create_user(
+    _client: ProvisioningClient,
+    ctx: State<AppCtx>,
+    ScimJson(payload): ScimJson<CreateUserPayload>,
+) -> ScimResponse {
+    let email = primary_email(&payload.emails)
+        .cloned()
+        .ok_or_else(|| ScimError::bad_request("invalidValue", "At least one email is required"))?;
+
+    // Provisioned users usually sign in elsewhere, so fall back to an unguessable password
+    let password = payload
+        .password
+        .unwrap_or_else(|| Password(Uuid::new_v4().to_string()));
+
+    let mut user = ScimUserChanges {
+        username: payload.user_name,
+        email,
+        external_id: payload.external_id,
+        active: payload.active,
+        password: Some(password),
+    };
+
+    prepare_changes(&mut user).await?;
+
+    let created = ScimUser::from(loader::insert_user(&ctx.db, &user).await?);
+    let location = created.meta.location.clone();
+
+    let mut response = scim_json(StatusCode::CREATED, created);
+    if let Ok(location) = location.parse() {
+        response.headers_mut().insert(LOCATION, location);
+    }
+
+    Ok(response)
+}
+
+#[instrument(skip(ctx, _client))]
+pub async fn patch_user(
+    _client: ProvisioningClient,
+    ctx: State<AppCtx>,
+    Path(id): Path<String>,
+    ScimJson(payload): ScimJson<PatchPayload>,
+) -> ScimResponse {
+    let current = find_user(&ctx, &id).await?;
+    let user_id = current.id;
+    let mut user = ScimUserChanges::from(current);
+
+    for operation in payload.operations {
+        apply_operation(&mut user, operation)?;
+    }
+
+    prepare_changes(&mut user).await?;
+
+    let updated = loader::update_user(&ctx.db, &user_id, &user)
+        .await?
+        .ok_or_else(|| ScimError::not_found(&id))?;
+
+    Ok(scim_json(StatusCode::OK, ScimUser::from(updated)))
+}
+
+#[instrument(skip(ctx, _client))]
+pub async fn delete_user(
+    _client: ProvisioningClient,
+    ctx: State<AppCtx>,
+    Path(id): Path<String>,
+) -> ScimResponse {
+    let user_id = parse_id(&id)?;
+
+    if !loader::deactivate_user(&ctx.db, &user_id).await? {
+        return Err(ScimError::not_found(&id));
+    }
+
+    Ok(StatusCode::NO_CONTENT.into_response())
+}
+
+async fn find_user(ctx: &AppCtx, id: &str) -> Result<loader::ScimUserRow, ScimError> {
+    loader::get_user(&ctx.db, &parse_id(id)?)
+        .await?
+        .ok_or_else(|| ScimError::not_found(id))
+}
+
+fn parse_id(id: &str) -> Result<Uuid, ScimError> {
+    Uuid::parse_str(id).map_err(|_| ScimError::not_found(id))
+}
+
+/// Validates the resulting user and hashes a newly provided password.
+async fn prepare_changes(user: &mut ScimUserChanges) -> Result<(), ScimError> {
+    let mut errors = ValidationErrors::new();
+
+    if let Err(e) = validate_username(&user.username) {
+        errors.add("userName", e);
+    }
+
+    if let Err(e) = validate_email(&user.email) {
+        errors.add("emails", e);
+    }
+
+    if let Some(password) = &user.password {
+        if let Err(e) = validate_password(password) {
+            errors.add("password", e);
+        }
+    }
+
+    if !errors.is_empty() {
+        return Err(AppError::from(errors).into());
+    }
+
+    if let Some(password) = user.password.take() {
+        let hash = hash_password(password).await.map_err(AppError::from)?;
+        user.password = Some(hash);
+    }
+
+    Ok(())
+}
+
+/// Only equality on `userName` is supported, e.g. `userName eq "john"`.
+fn parse_filter(filter: &str) -> Result<Username, ScimError> {
+    let invalid = || {
+        ScimError::bad_request(
+            "invalidFilter",
+            r#"Only filters of form `userName eq "value"` are supported"#,
+        )
+    };
+
+    let mut parts = filter.trim().splitn(3, ' ');
+
+    let (Some(attribute), Some(operator), Some(value)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(invalid());
+    };
+
+    if !attribute.eq_ignore_ascii_case("userName") || !operator.eq_ignore_ascii_case("eq") {
+        return Err(invalid());
+    }
+
+    let value = value
+        .trim()
+        .strip_prefix('"')
+        .and_then(|v| v.strip_suffix('"'))
+        .ok_or_else(invalid)?;
+
+    Ok(Username(value.replace(r#"\""#, r#"""#)))
+}
+
+fn apply_operation(user: &mut ScimUserChanges, operation: PatchOperation) -> Result<(), ScimError> {
+    match (operation.op, operation.path) {
+        (PatchOp::Remove, Some(path)) => remove_attribute(user, &path),
+
+        (PatchOp::Remove, None) => Err(ScimError::bad_request(
+            "noTarget",
+            "Remove operation requires a path",
+        )),
+
+        (PatchOp::Add | PatchOp::Replace, Some(path)) => {
+            let value = operation.value.ok_or_else(|| {
+                ScimError::bad_request("invalidValue", "Operation requires a value")
+            })?;
+
+            set_attribute(user, &path, value)
+        }
+
+        (PatchOp::Add | PatchOp::Replace, None) => match operation.value {
+            Some(Value::Object(attributes)) => {
+                for (path, value) in attributes {
+                    set_attribute(user, &path, value)?;
+                }
+
+                Ok(())
+            }
+
+            _ => Err(ScimError::bad_request(
+                "invalidValue",
+                "Operation without a path requires an object value",
+            )),
+        },
+    }
+}
+
+fn set_attribute(user: &mut ScimUserChanges, path: &str, value: Value) -> Result<(), ScimError> {
+    let path = path.to_lowercase();
+
+    match path.as_str() {
+        "username" => user.username = Username(value_as_string(value)?),
+        "externalid" => user.external_id = Some(value_as_string(value)?),
+        "password" => user.password = Some(Password(value_as_string(value)?)),
+
+        "active" => {
+            user.active = value_as_bool(&value).ok_or_else(|| {
+                ScimError::bad_request("invalidValue", "`active` must be a boolean")
+            })?;
+        }
+
+        "emails" => {
+            let emails: Vec<ScimEmail> = serde_json::from_value(value).map_err(|e| {
+                ScimError::bad_request("invalidValue", format!("Invalid emails: {e}"))
+            })?;
+
+            user.email = primary_email(&emails).cloned().ok_or_else(|| {
+                ScimError::bad_request("invalidValue", "At least one email is required")
+            })?;
+        }
+
+        // The single stored email stands for any email filter, e.g. `emails[type eq "work"].value`
+        p if p.starts_with("emails[") && p.ends_with("].value") => {
+            user.email = Email(value_as_string(value)?);
+        }
+
+        _ => {
+            return Err(ScimError::bad_request(
+                "invalidPath",
+                format!("Unsupported attribute: {path}"),
+            ))
+        }
+    }
+
+    Ok(())
+}
+
+fn remove_attribute(user: &mut ScimUserChanges, path: &str) -> Result<(), ScimError> {
+    if path.eq_ignore_ascii_case("externalId") {
+        user.external_id = None;
+        return Ok(());
+    }
+
+    Err(ScimError::bad_request(
+        "mutability",
+        format!("Attribute {path} cannot be removed"),
+    ))
+}
+
+fn value_as_string(value: Value) -> Result<String, ScimError> {
+    match value {
+        Value::String(s) => Ok(s),
+        _ => Err(ScimError::bad_request("invalidValue", "Expected a string")),
+    }
+}
+
+fn scim_json<T: Serialize>(status: StatusCode, body: T) -> Response {
+    (status, [(CONTENT_TYPE, SCIM_CONTENT_TYPE)], Json(body)).into_response()
+}
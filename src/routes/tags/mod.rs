@@ -0,0 +1,192 @@
+use axum::{extract::State, http::StatusCode, response::IntoResponse, routing::get, Router};
+use serde::{Deserialize, Serialize};
+use sqlx::{PgConnection, PgPool};
+use tracing::instrument;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    application::AppCtx,
+    db::DbResultExt,
+    extractors::ValidateQuery,
+    utils::{
+        response::{AppResponse, AppResult, DataResponse},
+        tag::normalize_tag,
+    },
+};
+
+pub fn routes() -> Router<AppCtx> {
+    Router::new()
+        .route("/tags", get(list_tags))
+        .route("/tags/autocomplete", get(autocomplete_tags))
+}
+
+#[derive(Deserialize, Serialize, Debug, PartialEq)]
+pub struct TagCount {
+    pub name: String,
+    /// Published articles with the tag.
+    pub article_count: i64,
+}
+
+#[derive(Deserialize, Serialize, Validate, Debug)]
+pub struct ListQuery {
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<i64>,
+
+    pub offset: Option<i64>,
+}
+
+#[derive(Deserialize, Serialize, Validate, Debug)]
+pub struct AutocompleteQuery {
+    #[validate(length(min = 1, max = 64))]
+    pub prefix: String,
+
+    #[validate(range(min = 1, max = 50))]
+    pub limit: Option<i64>,
+}
+
+/// Tags in use, the most used first.
+#[instrument(skip(ctx))]
+pub async fn list_tags(
+    ctx: State<AppCtx>,
+    ValidateQuery(query): ValidateQuery<ListQuery>,
+) -> AppResponse {
+    let tags = sqlx::query_as!(
+        TagCount,
+        r#"
+            SELECT t.name, COUNT(*) AS "article_count!"
+            FROM tags t
+            JOIN article_tags at ON at.tag_id = t.id
+            JOIN articles a ON a.id = at.article_id AND a.status = 'published'
+            GROUP BY t.id
+            ORDER BY COUNT(*) DESC, t.name
+            LIMIT $1 OFFSET $2
+        "#,
+        query.limit.unwrap_or(50),
+        query.offset.unwrap_or(0),
+    )
+    .fetch_all(&ctx.db)
+    .await
+    .trace_db("Failed to fetch tags")?;
+
+    Ok((StatusCode::OK, DataResponse::new(tags)).into_response())
+}
+
+/// Tags in use starting with the normalized prefix, the most used first.
+#[instrument(skip(ctx))]
+pub async fn autocomplete_tags(
+    ctx: State<AppCtx>,
+    ValidateQuery(query): ValidateQuery<AutocompleteQuery>,
+) -> AppResponse {
+    let prefix = escape_like(&normalize_tag(&query.prefix));
+
+    let tags = sqlx::query_as!(
+        TagCount,
+        r#"
+            SELECT t.name, COUNT(*) AS "article_count!"
+            FROM tags t
+            JOIN article_tags at ON at.tag_id = t.id
+            JOIN articles a ON a.id = at.article_id AND a.status = 'published'
+            WHERE t.name LIKE $1 || '%'
+            GROUP BY t.id
+            ORDER BY COUNT(*) DESC, t.name
+            LIMIT $2
+        "#,
+        prefix,
+        query.limit.unwrap_or(10),
+    )
+    .fetch_all(&ctx.db)
+    .await
+    .trace_db("Failed to autocomplete tags")?;
+
+    Ok((StatusCode::OK, DataResponse::new(tags)).into_response())
+}
+
+/// Replaces the tags of an article with already normalized `tags`, in the given order.
+#[instrument(skip(conn))]
+pub(crate) async fn set_article_tags(
+    conn: &mut PgConnection,
+    article_id: &Uuid,
+    tags: &[String],
+) -> AppResult<()> {
+    sqlx::query!(
+        "INSERT INTO tags (name) SELECT * FROM UNNEST($1::TEXT[]) ON CONFLICT DO NOTHING",
+        tags,
+    )
+    .execute(&mut *conn)
+    .await
+    .trace_db("Failed to insert tags")?;
+
+    sqlx::query!("DELETE FROM article_tags WHERE article_id = $1", article_id)
+        .execute(&mut *conn)
+        .await
+        .trace_db("Failed to remove article tags")?;
+
+    sqlx::query!(
+        r#"
+            INSERT INTO article_tags (article_id, tag_id, position)
+            SELECT $1, t.id, n.position
+            FROM UNNEST($2::TEXT[]) WITH ORDINALITY AS n(name, position)
+            JOIN tags t ON t.name = n.name
+        "#,
+        article_id,
+        tags,
+    )
+    .execute(&mut *conn)
+    .await
+    .trace_db("Failed to insert article tags")?;
+
+    Ok(())
+}
+
+/// Rewrites the `articles.tags` copy, which feeds the search vector, for articles with the tag.
+#[instrument(skip(conn))]
+pub(crate) async fn refresh_tag_arrays(conn: &mut PgConnection, tag_id: &Uuid) -> AppResult<()> {
+    sqlx::query!(
+        r#"
+            UPDATE articles a SET tags = ARRAY(
+                SELECT t.name
+                FROM article_tags at
+                JOIN tags t ON t.id = at.tag_id
+                WHERE at.article_id = a.id
+                ORDER BY at.position
+            )
+            WHERE a.id IN (SELECT article_id FROM article_tags WHERE tag_id = $1)
+        "#,
+        tag_id,
+    )
+    .execute(&mut *conn)
+    .await
+    .trace_db("Failed to refresh article tags")?;
+
+    Ok(())
+}
+
+#[instrument(skip(pool))]
+pub(crate) async fn fetch_tag_count(pool: &PgPool, tag_id: &Uuid) -> AppResult<TagCount> {
+    let tag = sqlx::query_as!(
+        TagCount,
+        r#"
+            SELECT t.name, COUNT(a.id) AS "article_count!"
+            FROM tags t
+            LEFT JOIN article_tags at ON at.tag_id = t.id
+            LEFT JOIN articles a ON a.id = at.article_id AND a.status = 'published'
+            WHERE t.id = $1
+            GROUP BY t.id
+        "#,
+        tag_id,
+    )
+    .fetch_one(pool)
+    .await
+    .trace_db("Failed to fetch tag")?;
+
+    Ok(tag)
+}
+
+/// Makes `%`, `_` and `\` match literally in a `LIKE` pattern.
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
@@ -0,0 +1,144 @@
+use axum::{
+    extract::{Multipart, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use bytes::{Bytes, BytesMut};
+use tracing::{error, instrument};
+use uuid::Uuid;
+
+use crate::{
+    application::AppCtx,
+    db::DbResultExt,
+    domains::user::UserID,
+    extractors::AuthUser,
+    utils::{
+        err::AppError,
+        jwt::UserData,
+        response::{AppResponse, AppResult, DataResponse},
+    },
+};
+
+use super::{original_key, process::process_image, thumbnail_key, RawUpload, Upload};
+
+/// Expects the image in a multipart field named `file`.
+#[instrument(skip(ctx, multipart))]
+pub async fn create_upload(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    multipart: Multipart,
+) -> AppResponse {
+    let data = read_file_field(multipart, ctx.storage.max_upload_bytes).await?;
+    let upload = store_upload(&ctx, &user.user_id, data).await?;
+
+    Ok((StatusCode::CREATED, DataResponse::new(upload)).into_response())
+}
+
+/// Stores the image like any other upload and points the profile avatar at its thumbnail.
+/// The avatar URL is absolute so that it passes the profile's URL validation when re-sent.
+#[instrument(skip(ctx, multipart))]
+pub async fn upload_avatar(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    multipart: Multipart,
+) -> AppResponse {
+    let data = read_file_field(multipart, ctx.storage.max_upload_bytes).await?;
+    let upload = store_upload(&ctx, &user.user_id, data).await?;
+
+    sqlx::query!(
+        "UPDATE users SET avatar_url = $2 WHERE id = $1",
+        user.user_id.as_ref(),
+        format!("{}{}", ctx.public_url, upload.thumbnail_url),
+    )
+    .execute(&ctx.db)
+    .await
+    .trace_db("Failed to update avatar")?;
+
+    Ok((StatusCode::CREATED, DataResponse::new(upload)).into_response())
+}
+
+/// Reads the body chunk by chunk so that oversized uploads are rejected without being
+/// buffered whole.
+async fn read_file_field(mut multipart: Multipart, max_bytes: usize) -> AppResult<Bytes> {
+    while let Some(mut field) = multipart.next_field().await.map_err(invalid_multipart)? {
+        if field.name() != Some("file") {
+            continue;
+        }
+
+        let mut data = BytesMut::new();
+        while let Some(chunk) = field.chunk().await.map_err(invalid_multipart)? {
+            if data.len() + chunk.len() > max_bytes {
+                return Err(AppError::UploadTooLarge(max_bytes));
+            }
+            data.extend_from_slice(&chunk);
+        }
+
+        return Ok(data.freeze());
+    }
+
+    Err(AppError::BadRequest("Missing file field".to_string()))
+}
+
+fn invalid_multipart(e: axum::extract::multipart::MultipartError) -> AppError {
+    AppError::BadRequest(format!("Invalid multipart body: {e}"))
+}
+
+#[instrument(skip(ctx, data))]
+async fn store_upload(ctx: &AppCtx, owner_id: &UserID, data: Bytes) -> AppResult<Upload> {
+    let storage = ctx.storage.clone();
+    let image = {
+        let data = data.clone();
+        tokio::task::spawn_blocking(move || {
+            process_image(&data, storage.max_image_dimension, storage.thumbnail_size)
+        })
+        .await
+        .map_err(|e| AppError::InternalServerError(e.into()))??
+    };
+
+    let id = Uuid::new_v4();
+    let size = data.len() as i64;
+
+    let keys = [original_key(&id), thumbnail_key(&id)];
+    let stored: AppResult<RawUpload> = async {
+        ctx.blobs.put(&keys[0], data, image.content_type).await?;
+        ctx.blobs
+            .put(&keys[1], Bytes::from(image.thumbnail), "image/png")
+            .await?;
+
+        let upload = sqlx::query_as!(
+            RawUpload,
+            r#"
+                INSERT INTO uploads (id, owner_id, content_type, size, width, height)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                RETURNING id, owner_id, content_type, size, width, height, created_at
+            "#,
+            id,
+            owner_id.as_ref(),
+            image.content_type,
+            size,
+            image.width as i32,
+            image.height as i32,
+        )
+        .fetch_one(&ctx.db)
+        .await
+        .trace_db("Failed to record upload")?;
+
+        Ok(upload)
+    }
+    .await;
+
+    // Without a row nothing can reach the files, so they are removed again
+    let upload = match stored {
+        Ok(upload) => upload,
+        Err(e) => {
+            for key in &keys {
+                if let Err(e) = ctx.blobs.delete(key).await {
+                    error!("Failed to delete orphaned blob {key}: {e}");
+                }
+            }
+            return Err(e);
+        }
+    };
+
+    Ok(upload.into())
+}
@@ -0,0 +1,92 @@
+pub mod create_upload;
+pub mod process;
+pub mod serve;
+
+use axum::{
+    extract::DefaultBodyLimit,
+    routing::{get, post},
+    Router,
+};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::{application::AppCtx, db::DbResultExt, utils::response::AppResult};
+
+#[derive(Deserialize, Serialize, Debug)]
+pub struct Upload {
+    pub id: Uuid,
+    pub content_type: String,
+    pub size: i64,
+    pub width: i32,
+    pub height: i32,
+    pub url: String,
+    pub thumbnail_url: String,
+    pub created_at: NaiveDateTime,
+}
+
+pub(crate) struct RawUpload {
+    pub id: Uuid,
+    pub owner_id: Uuid,
+    pub content_type: String,
+    pub size: i64,
+    pub width: i32,
+    pub height: i32,
+    pub created_at: NaiveDateTime,
+}
+
+impl From<RawUpload> for Upload {
+    fn from(raw: RawUpload) -> Self {
+        Upload {
+            id: raw.id,
+            content_type: raw.content_type,
+            size: raw.size,
+            width: raw.width,
+            height: raw.height,
+            url: format!("/uploads/{}", raw.id),
+            thumbnail_url: format!("/uploads/{}/thumbnail", raw.id),
+            created_at: raw.created_at,
+        }
+    }
+}
+
+fn original_key(id: &Uuid) -> String {
+    format!("uploads/{id}/original")
+}
+
+fn thumbnail_key(id: &Uuid) -> String {
+    format!("uploads/{id}/thumbnail")
+}
+
+pub(crate) async fn fetch_upload(pool: &PgPool, id: &Uuid) -> AppResult<Option<RawUpload>> {
+    let upload = sqlx::query_as!(
+        RawUpload,
+        r#"
+            SELECT id, owner_id, content_type, size, width, height, created_at
+            FROM uploads
+            WHERE id = $1
+        "#,
+        id,
+    )
+    .fetch_optional(pool)
+    .await
+    .trace_db("Failed to fetch upload")?;
+
+    Ok(upload)
+}
+
+/// The size limit is enforced while the multipart body is read, see `create_upload`.
+pub fn routes() -> Router<AppCtx> {
+    Router::new()
+        .route(
+            "/uploads",
+            post(create_upload::create_upload).layer(DefaultBodyLimit::disable()),
+        )
+        .route(
+            "/me/avatar",
+            post(create_upload::upload_avatar).layer(DefaultBodyLimit::disable()),
+        )
+        .route("/uploads/:id", get(serve::get_original))
+        .route("/uploads/:id/thumbnail", get(serve::get_thumbnail))
+}
@@ -0,0 +1,61 @@
+use std::io::Cursor;
+
+use image::{ImageFormat, ImageReader};
+
+use crate::utils::{err::AppError, response::AppResult};
+
+pub struct ProcessedImage {
+    pub content_type: &'static str,
+    pub width: u32,
+    pub height: u32,
+    /// Always encoded as PNG.
+    pub thumbnail: Vec<u8>,
+}
+
+/// Detects the format from the file contents, whatever the client claims, and checks the
+/// dimensions from the header before decoding the whole image.
+pub fn process_image(
+    data: &[u8],
+    max_dimension: u32,
+    thumbnail_size: u32,
+) -> AppResult<ProcessedImage> {
+    let format = image::guess_format(data).map_err(|_| AppError::UnsupportedMediaType)?;
+    let content_type = match format {
+        ImageFormat::Png => "image/png",
+        ImageFormat::Jpeg => "image/jpeg",
+        ImageFormat::Gif => "image/gif",
+        ImageFormat::WebP => "image/webp",
+        _ => return Err(AppError::UnsupportedMediaType),
+    };
+
+    let (width, height) = ImageReader::with_format(Cursor::new(data), format)
+        .into_dimensions()
+        .map_err(|_| invalid_image())?;
+
+    if width > max_dimension || height > max_dimension {
+        return Err(AppError::BadRequest(format!(
+            "Image is {width}x{height}, the largest allowed side is {max_dimension} pixels"
+        )));
+    }
+
+    let image = ImageReader::with_format(Cursor::new(data), format)
+        .decode()
+        .map_err(|_| invalid_image())?;
+
+    let mut thumbnail = Cursor::new(vec![]);
+    image
+        .thumbnail(thumbnail_size, thumbnail_size)
+        .write_to(&mut thumbnail, ImageFormat::Png)
+        .map_err(|e| AppError::InternalServerError(e.into()))?;
+
+    Ok(ProcessedImage {
+        content_type,
+        width,
+        height,
+        thumbnail: thumbnail.into_inner(),
+    })
+}
+
+fn invalid_image() -> AppError {
+    AppError::BadRequest("Image could not be decoded".to_string())
+}
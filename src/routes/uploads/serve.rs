@@ -0,0 +1,95 @@
+use axum::{
+    body::Body,
+    extract::{Path, State},
+    http::{
+        header::{CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_NONE_MATCH, X_CONTENT_TYPE_OPTIONS},
+        HeaderMap, StatusCode,
+    },
+    response::IntoResponse,
+};
+use tracing::instrument;
+use uuid::Uuid;
+
+use crate::{
+    application::AppCtx,
+    utils::{err::AppError, response::AppResponse},
+};
+
+use super::{fetch_upload, original_key, thumbnail_key};
+
+#[instrument(skip(ctx))]
+pub async fn get_original(
+    ctx: State<AppCtx>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> AppResponse {
+    let upload = fetch_upload(&ctx.db, &id)
+        .await?
+        .ok_or_else(|| upload_not_found(&id))?;
+
+    serve_blob(&ctx, &original_key(&id), &upload.content_type, &headers).await
+}
+
+#[instrument(skip(ctx))]
+pub async fn get_thumbnail(
+    ctx: State<AppCtx>,
+    Path(id): Path<Uuid>,
+    headers: HeaderMap,
+) -> AppResponse {
+    fetch_upload(&ctx.db, &id)
+        .await?
+        .ok_or_else(|| upload_not_found(&id))?;
+
+    serve_blob(&ctx, &thumbnail_key(&id), "image/png", &headers).await
+}
+
+/// Stored files never change, so they are cached for as long as the configuration allows
+/// and revalidated by key alone. `nosniff` keeps browsers from reading them as anything
+/// but their stored content type.
+async fn serve_blob(
+    ctx: &AppCtx,
+    key: &str,
+    content_type: &str,
+    headers: &HeaderMap,
+) -> AppResponse {
+    let etag = format!("\"{}\"", key.replace('/', "-"));
+    let cache_control = format!(
+        "public, max-age={}, immutable",
+        ctx.storage.cache_max_age_seconds
+    );
+
+    let is_cached = headers
+        .get(IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.split(',').any(|tag| tag.trim() == etag));
+
+    if is_cached {
+        return Ok((
+            StatusCode::NOT_MODIFIED,
+            [(ETAG, etag), (CACHE_CONTROL, cache_control)],
+        )
+            .into_response());
+    }
+
+    let data = ctx
+        .blobs
+        .get(key)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("File not found: {key}")))?;
+
+    Ok((
+        StatusCode::OK,
+        [
+            (CONTENT_TYPE, content_type.to_string()),
+            (ETAG, etag),
+            (CACHE_CONTROL, cache_control),
+            (X_CONTENT_TYPE_OPTIONS, "nosniff".to_string()),
+        ],
+        Body::from(data),
+    )
+        .into_response())
+}
+
+fn upload_not_found(id: &Uuid) -> AppError {
+    AppError::NotFound(format!("Upload not found: {id}"))
+}
@@ -0,0 +1,172 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+    Json,
+};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::{postgres::PgArguments, Arguments, PgPool, QueryBuilder};
+use tracing::instrument;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    application::AppCtx,
+    db::{self, DbResultExt, KeysetPage, TimestampOrder},
+    domains::user::{UserID, Username},
+    extractors::ValidateQuery,
+    types::{CountMode, SearchType, SortingDirection},
+    utils::{
+        err::AppError,
+        response::{AppResponse, AppResult},
+    },
+};
+
+#[derive(Deserialize, Serialize, Validate, Debug, Default)]
+pub struct ListQuery {
+    #[validate(range(min = 1, max = 100))]
+    pub limit: Option<u64>,
+    pub after: Option<String>,
+    pub before: Option<String>,
+
+    #[serde(default)]
+    pub count: CountMode,
+}
+
+#[derive(Deserialize, Serialize)]
+pub struct Follow {
+    pub id: UserID,
+    pub username: Username,
+    pub followed_at: NaiveDateTime,
+}
+
+#[derive(sqlx::FromRow)]
+struct RawFollow {
+    id: Uuid,
+    username: String,
+    followed_at: NaiveDateTime,
+}
+
+impl From<RawFollow> for Follow {
+    fn from(raw: RawFollow) -> Self {
+        Follow {
+            id: UserID(raw.id),
+            username: Username(raw.username),
+            followed_at: raw.followed_at,
+        }
+    }
+}
+
+/// Which side of `subscriptions` is listed for the given user.
+#[derive(Debug, Clone, Copy)]
+enum Relation {
+    Followers,
+    Following,
+}
+
+impl Relation {
+    /// Column holding the listed users and the one matching the given user.
+    fn columns(self) -> (&'static str, &'static str) {
+        match self {
+            Relation::Followers => ("subscriber_id", "author_id"),
+            Relation::Following => ("author_id", "subscriber_id"),
+        }
+    }
+}
+
+const ORDER: TimestampOrder = TimestampOrder {
+    signature: "followed_at DESC,id DESC",
+    timestamp: "s.created_at",
+    id: "u.id",
+    direction: SortingDirection::DESC,
+};
+
+#[instrument(skip(ctx))]
+pub async fn list_followers(
+    ctx: State<AppCtx>,
+    Path(user_id): Path<UserID>,
+    ValidateQuery(query): ValidateQuery<ListQuery>,
+) -> AppResponse {
+    let data = fetch_follows(&ctx.db, &user_id, Relation::Followers, &query).await?;
+
+    Ok((StatusCode::OK, Json(data)).into_response())
+}
+
+#[instrument(skip(ctx))]
+pub async fn list_following(
+    ctx: State<AppCtx>,
+    Path(user_id): Path<UserID>,
+    ValidateQuery(query): ValidateQuery<ListQuery>,
+) -> AppResponse {
+    let data = fetch_follows(&ctx.db, &user_id, Relation::Following, &query).await?;
+
+    Ok((StatusCode::OK, Json(data)).into_response())
+}
+
+#[instrument(skip(pool))]
+async fn fetch_follows(
+    pool: &PgPool,
+    user_id: &UserID,
+    relation: Relation,
+    query: &ListQuery,
+) -> AppResult<SearchType<Follow>> {
+    let exists = sqlx::query_scalar!(
+        "SELECT EXISTS(SELECT 1 FROM users WHERE id = $1 AND active) AS \"exists!\"",
+        user_id.as_ref(),
+    )
+    .fetch_one(pool)
+    .await
+    .trace_db("Failed to fetch user")?;
+
+    if !exists {
+        return Err(AppError::NotFound(format!("User not found: {user_id}")));
+    }
+
+    let keyset = KeysetPage::from_query(
+        query.limit.unwrap_or(20),
+        query.after.as_deref(),
+        query.before.as_deref(),
+        ORDER,
+    )?;
+
+    let (listed, target) = relation.columns();
+    let from_sql = format!(
+        r#"
+            FROM subscriptions s
+            JOIN users u ON u.id = s.{listed}
+            WHERE s.{target} = $1
+        "#
+    );
+
+    let args = || {
+        let mut args = PgArguments::default();
+        args.add(user_id.as_ref());
+        args
+    };
+
+    let mut builder = QueryBuilder::with_arguments(
+        "SELECT u.id, u.username, s.created_at AS followed_at ",
+        args(),
+    );
+    builder.push(&from_sql);
+    keyset.push_sql(&mut builder);
+
+    let rows = builder
+        .build_query_as::<RawFollow>()
+        .fetch_all(pool)
+        .await
+        .trace_db("Failed to fetch follows")?;
+
+    let count = keyset
+        .page
+        .count(rows.len(), pool, query.count, &from_sql, args)
+        .await?;
+
+    Ok(db::into_keyset_search_type(
+        rows,
+        &keyset.page,
+        count,
+        |row| keyset.cursor_of(row.followed_at, row.id),
+    ))
+}
@@ -0,0 +1,17 @@
+pub mod follows;
+pub mod profile;
+
+use axum::{
+    routing::{get, patch},
+    Router,
+};
+
+use crate::application::AppCtx;
+
+pub fn routes() -> Router<AppCtx> {
+    Router::new()
+        .route("/users/:user", get(profile::get_profile))
+        .route("/users/:user/followers", get(follows::list_followers))
+        .route("/users/:user/following", get(follows::list_following))
+        .route("/me/profile", patch(profile::update_profile))
+}
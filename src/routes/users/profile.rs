@@ -0,0 +1,198 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    response::IntoResponse,
+};
+use serde::{Deserialize, Deserializer, Serialize};
+use sqlx::PgPool;
+use tracing::instrument;
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    application::AppCtx,
+    db::DbResultExt,
+    domains::user::{UserID, Username},
+    extractors::{AuthUser, MaybeAuthUser, ValidateJson},
+    utils::{
+        err::AppError,
+        jwt::UserData,
+        response::{AppResponse, AppResult, DataResponse},
+    },
+};
+
+#[derive(Deserialize, Serialize)]
+pub struct Profile {
+    pub id: UserID,
+    pub username: Username,
+    pub display_name: Option<String>,
+    pub bio: Option<String>,
+    pub avatar_url: Option<String>,
+    pub website: Option<String>,
+    pub follower_count: i64,
+    pub following_count: i64,
+
+    /// Only present when the request is authenticated.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub is_following: Option<bool>,
+}
+
+struct RawProfile {
+    id: Uuid,
+    username: String,
+    display_name: Option<String>,
+    bio: Option<String>,
+    avatar_url: Option<String>,
+    website: Option<String>,
+    follower_count: i64,
+    following_count: i64,
+    is_following: Option<bool>,
+}
+
+impl From<RawProfile> for Profile {
+    fn from(raw: RawProfile) -> Self {
+        Profile {
+            id: UserID(raw.id),
+            username: Username(raw.username),
+            display_name: raw.display_name,
+            bio: raw.bio,
+            avatar_url: raw.avatar_url,
+            website: raw.website,
+            follower_count: raw.follower_count,
+            following_count: raw.following_count,
+            is_following: raw.is_following,
+        }
+    }
+}
+
+/// Fields left out are kept as they are, fields set to `null` are cleared.
+#[derive(Deserialize, Serialize, Validate, Debug, Default)]
+pub struct ProfilePayload {
+    #[serde(
+        default,
+        deserialize_with = "deserialize_some",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[validate(length(min = 1, max = 100))]
+    pub display_name: Option<Option<String>>,
+
+    #[serde(
+        default,
+        deserialize_with = "deserialize_some",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[validate(length(max = 1000))]
+    pub bio: Option<Option<String>>,
+
+    #[serde(
+        default,
+        deserialize_with = "deserialize_some",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[validate(custom = "crate::parsers::user::validate_web_url")]
+    pub avatar_url: Option<Option<String>>,
+
+    #[serde(
+        default,
+        deserialize_with = "deserialize_some",
+        skip_serializing_if = "Option::is_none"
+    )]
+    #[validate(custom = "crate::parsers::user::validate_web_url")]
+    pub website: Option<Option<String>>,
+}
+
+/// Tells an explicit `null` apart from a missing field.
+fn deserialize_some<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+#[instrument(skip(ctx))]
+pub async fn get_profile(
+    ctx: State<AppCtx>,
+    MaybeAuthUser(user): MaybeAuthUser<UserData>,
+    Path(username): Path<Username>,
+) -> AppResponse {
+    let viewer = user.map(|user| user.user_id);
+    let profile = fetch_profile(&ctx.db, &username, viewer.as_ref())
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("User not found: {username}")))?;
+
+    Ok((StatusCode::OK, DataResponse::new(profile)).into_response())
+}
+
+#[instrument(skip(ctx))]
+pub async fn update_profile(
+    ctx: State<AppCtx>,
+    AuthUser(user): AuthUser<UserData>,
+    ValidateJson(payload): ValidateJson<ProfilePayload>,
+) -> AppResponse {
+    let username = sqlx::query_scalar!(
+        r#"
+            UPDATE users SET
+                display_name = CASE WHEN $2 THEN $3 ELSE display_name END,
+                bio = CASE WHEN $4 THEN $5 ELSE bio END,
+                avatar_url = CASE WHEN $6 THEN $7 ELSE avatar_url END,
+                website = CASE WHEN $8 THEN $9 ELSE website END
+            WHERE id = $1
+            RETURNING username
+        "#,
+        user.user_id.as_ref(),
+        payload.display_name.is_some(),
+        payload.display_name.clone().flatten(),
+        payload.bio.is_some(),
+        payload.bio.clone().flatten(),
+        payload.avatar_url.is_some(),
+        payload.avatar_url.clone().flatten(),
+        payload.website.is_some(),
+        payload.website.clone().flatten(),
+    )
+    .fetch_optional(&ctx.db)
+    .await
+    .trace_db("Failed to update profile")?
+    .ok_or(AppError::Unauthorized)?;
+
+    let username = Username(username);
+    let profile = fetch_profile(&ctx.db, &username, Some(&user.user_id))
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("User not found: {username}")))?;
+
+    Ok((StatusCode::OK, DataResponse::new(profile)).into_response())
+}
+
+#[instrument(skip(pool))]
+pub(crate) async fn fetch_profile(
+    pool: &PgPool,
+    username: &Username,
+    viewer: Option<&UserID>,
+) -> AppResult<Option<Profile>> {
+    let profile = sqlx::query_as!(
+        RawProfile,
+        r#"
+            SELECT
+                u.id,
+                u.username,
+                u.display_name,
+                u.bio,
+                u.avatar_url,
+                u.website,
+                (SELECT COUNT(*) FROM subscriptions WHERE author_id = u.id) AS "follower_count!",
+                (SELECT COUNT(*) FROM subscriptions WHERE subscriber_id = u.id) AS "following_count!",
+                CASE WHEN $2::UUID IS NOT NULL THEN EXISTS(
+                    SELECT 1 FROM subscriptions WHERE subscriber_id = $2 AND author_id = u.id
+                ) END AS is_following
+            FROM users u
+            WHERE u.username = $1 AND u.active
+        "#,
+        username.as_ref(),
+        viewer.map(|id| id.0),
+    )
+    .fetch_optional(pool)
+    .await
+    .trace_db("Failed to fetch profile")?;
+
+    Ok(profile.map(Profile::from))
+}
@@ -0,0 +1,68 @@
+use std::{io::ErrorKind, path::PathBuf};
+
+use anyhow::{bail, Context};
+use axum::async_trait;
+use bytes::Bytes;
+
+use super::BlobStore;
+
+pub struct LocalStore {
+    root: PathBuf,
+}
+
+impl LocalStore {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    fn path(&self, key: &str) -> anyhow::Result<PathBuf> {
+        if key
+            .split('/')
+            .any(|part| part.is_empty() || part == "." || part == "..")
+        {
+            bail!("Invalid blob key: {key}");
+        }
+
+        Ok(self.root.join(key))
+    }
+}
+
+#[async_trait]
+impl BlobStore for LocalStore {
+    async fn put(&self, key: &str, data: Bytes, _content_type: &str) -> anyhow::Result<()> {
+        let path = self.path(key)?;
+
+        if let Some(parent) = path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .context("Failed to create blob directory")?;
+        }
+
+        // Written under a temporary name first so that readers never see a partial file
+        let tmp_path = path.with_extension("tmp");
+        tokio::fs::write(&tmp_path, &data)
+            .await
+            .context("Failed to write blob")?;
+        tokio::fs::rename(&tmp_path, &path)
+            .await
+            .context("Failed to move blob in place")?;
+
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<Bytes>> {
+        match tokio::fs::read(self.path(key)?).await {
+            Ok(data) => Ok(Some(Bytes::from(data))),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e).context("Failed to read blob"),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> anyhow::Result<()> {
+        match tokio::fs::remove_file(self.path(key)?).await {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(e).context("Failed to delete blob"),
+        }
+    }
+}
@@ -0,0 +1,45 @@
+mod local;
+mod s3;
+
+use std::sync::Arc;
+
+use axum::async_trait;
+use bytes::Bytes;
+
+use crate::configuration::StorageBackend;
+
+pub use local::LocalStore;
+pub use s3::S3Store;
+
+/// Where uploaded files are kept. Keys are relative paths such as `uploads/{id}/original`;
+/// metadata like the content type lives in the database.
+#[async_trait]
+pub trait BlobStore: Send + Sync {
+    async fn put(&self, key: &str, data: Bytes, content_type: &str) -> anyhow::Result<()>;
+
+    /// Returns `None` when nothing is stored under the key.
+    async fn get(&self, key: &str) -> anyhow::Result<Option<Bytes>>;
+
+    /// Deleting a missing key is not an error.
+    async fn delete(&self, key: &str) -> anyhow::Result<()>;
+}
+
+pub fn from_config(backend: &StorageBackend, http_client: reqwest::Client) -> Arc<dyn BlobStore> {
+    match backend {
+        StorageBackend::Local { root } => Arc::new(LocalStore::new(root.clone())),
+        StorageBackend::S3 {
+            endpoint,
+            bucket,
+            region,
+            access_key,
+            secret_key,
+        } => Arc::new(S3Store::new(
+            http_client,
+            endpoint,
+            bucket,
+            region,
+            access_key,
+            secret_key,
+        )),
+    }
+}
@@ -0,0 +1,160 @@
+use anyhow::{bail, Context};
+use axum::async_trait;
+use bytes::Bytes;
+use chrono::Utc;
+use reqwest::{Method, StatusCode, Url};
+use ring::hmac;
+use sha2::{Digest, Sha256};
+
+use super::BlobStore;
+
+/// Talks to S3-compatible services with requests signed by AWS Signature Version 4.
+pub struct S3Store {
+    client: reqwest::Client,
+    endpoint: String,
+    bucket: String,
+    region: String,
+    access_key: String,
+    secret_key: String,
+}
+
+impl S3Store {
+    pub fn new(
+        client: reqwest::Client,
+        endpoint: &str,
+        bucket: &str,
+        region: &str,
+        access_key: &str,
+        secret_key: &str,
+    ) -> Self {
+        Self {
+            client,
+            endpoint: endpoint.trim_end_matches('/').to_string(),
+            bucket: bucket.to_string(),
+            region: region.to_string(),
+            access_key: access_key.to_string(),
+            secret_key: secret_key.to_string(),
+        }
+    }
+
+    fn request(
+        &self,
+        method: Method,
+        key: &str,
+        body: Bytes,
+    ) -> anyhow::Result<reqwest::RequestBuilder> {
+        let path = format!("/{}/{}", uri_encode(&self.bucket), uri_encode(key));
+        let url = Url::parse(&format!("{}{}", self.endpoint, path)).context("Invalid S3 URL")?;
+        let host = match (url.host_str(), url.port()) {
+            (Some(host), Some(port)) => format!("{host}:{port}"),
+            (Some(host), None) => host.to_string(),
+            _ => bail!("S3 endpoint has no host"),
+        };
+
+        let now = Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let payload_hash = hex(&Sha256::digest(&body));
+
+        let signed_headers = "host;x-amz-content-sha256;x-amz-date";
+        let canonical_request = format!(
+            "{method}\n{path}\n\nhost:{host}\nx-amz-content-sha256:{payload_hash}\nx-amz-date:{amz_date}\n\n{signed_headers}\n{payload_hash}"
+        );
+
+        let scope = format!("{date}/{}/s3/aws4_request", self.region);
+        let string_to_sign = format!(
+            "AWS4-HMAC-SHA256\n{amz_date}\n{scope}\n{}",
+            hex(&Sha256::digest(canonical_request.as_bytes()))
+        );
+
+        let signing_key = [date.as_str(), self.region.as_str(), "s3", "aws4_request"]
+            .iter()
+            .fold(
+                format!("AWS4{}", self.secret_key).into_bytes(),
+                |key, part| sign(&key, part.as_bytes()),
+            );
+        let signature = hex(&sign(&signing_key, string_to_sign.as_bytes()));
+
+        let authorization = format!(
+            "AWS4-HMAC-SHA256 Credential={}/{scope}, SignedHeaders={signed_headers}, Signature={signature}",
+            self.access_key
+        );
+
+        Ok(self
+            .client
+            .request(method, url)
+            .header("x-amz-date", amz_date)
+            .header("x-amz-content-sha256", payload_hash)
+            .header("authorization", authorization)
+            .body(body))
+    }
+}
+
+#[async_trait]
+impl BlobStore for S3Store {
+    async fn put(&self, key: &str, data: Bytes, content_type: &str) -> anyhow::Result<()> {
+        let response = self
+            .request(Method::PUT, key, data)?
+            .header("content-type", content_type)
+            .send()
+            .await
+            .context("Failed to reach S3")?;
+
+        if !response.status().is_success() {
+            bail!("S3 rejected upload of {key}: {}", response.status());
+        }
+
+        Ok(())
+    }
+
+    async fn get(&self, key: &str) -> anyhow::Result<Option<Bytes>> {
+        let response = self
+            .request(Method::GET, key, Bytes::new())?
+            .send()
+            .await
+            .context("Failed to reach S3")?;
+
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(None),
+            status if status.is_success() => Ok(Some(
+                response.bytes().await.context("Failed to read S3 object")?,
+            )),
+            status => bail!("S3 rejected download of {key}: {status}"),
+        }
+    }
+
+    async fn delete(&self, key: &str) -> anyhow::Result<()> {
+        let response = self
+            .request(Method::DELETE, key, Bytes::new())?
+            .send()
+            .await
+            .context("Failed to reach S3")?;
+
+        if !response.status().is_success() && response.status() != StatusCode::NOT_FOUND {
+            bail!("S3 rejected deletion of {key}: {}", response.status());
+        }
+
+        Ok(())
+    }
+}
+
+fn sign(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let key = hmac::Key::new(hmac::HMAC_SHA256, key);
+    hmac::sign(&key, data).as_ref().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+/// Percent-encodes everything but unreserved characters, keeping `/` between segments.
+fn uri_encode(path: &str) -> String {
+    path.bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                (b as char).to_string()
+            }
+            _ => format!("%{b:02X}"),
+        })
+        .collect()
+}
@@ -4,11 +4,44 @@ use serde::{Deserialize, Serialize};
 #[derive(Serialize, Deserialize)]
 pub struct SearchType<T> {
     pub results: Vec<T>,
-    pub total: usize,
+
+    /// Number of matching rows, `None` when counting was skipped.
+    pub total: Option<usize>,
+
+    /// `false` when `total` is the planner's estimate or was skipped.
+    pub total_is_exact: bool,
+
+    /// Pass as `after` to fetch the following page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_cursor: Option<String>,
+
+    /// Pass as `before` to fetch the preceding page.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prev_cursor: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize, Display)]
+#[derive(Debug, Serialize, Deserialize, Display, Clone, Copy, PartialEq)]
 pub enum SortingDirection {
     ASC,
     DESC,
 }
+
+impl SortingDirection {
+    pub fn reversed(self) -> Self {
+        match self {
+            SortingDirection::ASC => SortingDirection::DESC,
+            SortingDirection::DESC => SortingDirection::ASC,
+        }
+    }
+}
+
+/// How `total` is computed for a listing. Exact counts get expensive on large tables.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum CountMode {
+    #[default]
+    Exact,
+    /// Row estimate from the query planner.
+    Estimated,
+    None,
+}
@@ -0,0 +1,57 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{err::AppError, response::AppResult};
+
+/// Position in a keyset-paginated listing: the sort key of a row plus the ordering it belongs
+/// to. Clients only ever see it base64-encoded.
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Cursor {
+    /// Signature of the ordering, cursors from a different ordering are rejected.
+    pub order: String,
+    /// Values of the sort columns, the row id being the last one.
+    pub key: Vec<Value>,
+}
+
+impl Cursor {
+    pub fn encode(&self) -> String {
+        let json = serde_json::to_vec(self).expect("Cursor is always serializable");
+        URL_SAFE_NO_PAD.encode(json)
+    }
+
+    pub fn decode(cursor: &str, order: &str) -> AppResult<Self> {
+        let cursor: Cursor = URL_SAFE_NO_PAD
+            .decode(cursor)
+            .ok()
+            .and_then(|json| serde_json::from_slice(&json).ok())
+            .ok_or_else(invalid_cursor)?;
+
+        if cursor.order != order {
+            return Err(invalid_cursor());
+        }
+
+        Ok(cursor)
+    }
+}
+
+/// Decodes a cursor over two sort columns, typically a timestamp and the row id.
+pub fn decode_pair<A, B>(cursor: &str, order: &str) -> AppResult<(A, B)>
+where
+    A: DeserializeOwned,
+    B: DeserializeOwned,
+{
+    let cursor = Cursor::decode(cursor, order)?;
+
+    match cursor.key.as_slice() {
+        [a, b] => Ok((
+            serde_json::from_value(a.clone()).map_err(|_| invalid_cursor())?,
+            serde_json::from_value(b.clone()).map_err(|_| invalid_cursor())?,
+        )),
+        _ => Err(invalid_cursor()),
+    }
+}
+
+pub fn invalid_cursor() -> AppError {
+    AppError::BadRequest("Invalid cursor".to_string())
+}
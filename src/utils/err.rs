@@ -32,12 +32,18 @@ pub enum AppError {
     #[error("User with given username or email already exists")]
     DuplicatedUser,
 
-    #[error("Article with given title already exists")]
+    #[error("Article with the same slug already exists")]
     DuplicatedArticle,
 
+    #[error("Tag with given name already exists")]
+    DuplicatedTag,
+
     #[error("{0}")]
     NotFound(String),
 
+    #[error("Article was modified since version {0}, reload it and try again")]
+    VersionConflict(i32),
+
     #[error("{0}")]
     BadRequest(String),
 
@@ -46,13 +52,45 @@ pub enum AppError {
 
     #[error("Unauthorized")]
     Unauthorized,
+
+    #[error("Forbidden")]
+    Forbidden,
+
+    #[error("Registration is closed")]
+    RegistrationClosed,
+
+    #[error("Invite code is invalid, expired or already used")]
+    InvalidInviteCode,
+
+    #[error("External authentication failed")]
+    ExternalAuthFailed,
+
+    #[error("An account with this email already exists, sign in with its password instead")]
+    UnverifiedAccountExists,
+
+    #[error("Passkey verification failed")]
+    WebAuthnFailed,
+
+    #[error("Upload exceeds the limit of {0} bytes")]
+    UploadTooLarge(usize),
+
+    #[error("Unsupported file type, expected a PNG, JPEG, GIF or WebP image")]
+    UnsupportedMediaType,
 }
 
 impl AppError {
     pub fn status_code(&self) -> StatusCode {
         match self {
-            AppError::Unauthorized => StatusCode::UNAUTHORIZED,
+            AppError::Unauthorized | AppError::ExternalAuthFailed | AppError::WebAuthnFailed => {
+                StatusCode::UNAUTHORIZED
+            }
+            AppError::Forbidden | AppError::RegistrationClosed => StatusCode::FORBIDDEN,
             AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::VersionConflict(_) | AppError::UnverifiedAccountExists => {
+                StatusCode::CONFLICT
+            }
+            AppError::UploadTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            AppError::UnsupportedMediaType => StatusCode::UNSUPPORTED_MEDIA_TYPE,
             AppError::ValidationError(_) => StatusCode::BAD_REQUEST,
             AppError::AxumJsonRejection(_) | AppError::AxumQueryRejection(_) => {
                 StatusCode::UNPROCESSABLE_ENTITY
@@ -60,7 +98,9 @@ impl AppError {
             AppError::DuplicatedUser => StatusCode::BAD_REQUEST,
             AppError::BadRequest(_) => StatusCode::BAD_REQUEST,
             AppError::DuplicatedArticle => StatusCode::BAD_REQUEST,
+            AppError::DuplicatedTag => StatusCode::BAD_REQUEST,
             AppError::InvalidCredentials => StatusCode::BAD_REQUEST,
+            AppError::InvalidInviteCode => StatusCode::BAD_REQUEST,
             AppError::InternalServerError(_) | AppError::DbError(_) => {
                 StatusCode::INTERNAL_SERVER_ERROR
             }
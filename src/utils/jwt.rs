@@ -1,14 +1,26 @@
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use anyhow::Context;
-use jsonwebtoken::{encode, Algorithm, DecodingKey, EncodingKey, Header, TokenData, Validation};
+use anyhow::{ensure, Context};
+use jsonwebtoken::{
+    decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, TokenData, Validation,
+};
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
 
-use crate::domains::user::UserID;
+use crate::{configuration::JwtConfig, domains::user::UserID};
+
+const MIN_SECRET_LEN: usize = 32;
 
 #[derive(Serialize, Deserialize)]
 struct Claims<T> {
+    sub: String,
+    iss: String,
+    aud: String,
+    iat: u64,
+    nbf: u64,
     exp: u64,
+    jti: String,
     data: T,
 }
 
@@ -17,43 +29,118 @@ pub struct UserData {
     pub user_id: UserID,
 }
 
-pub fn sign(user_id: UserID) -> anyhow::Result<String> {
-    let now = SystemTime::now();
-    let exp = now
-        .duration_since(UNIX_EPOCH)
-        .context("Failed to count time from unix epoch")?;
+/// Signs and verifies access tokens. Built once at startup from `JwtConfig`.
+///
+/// Tokens are signed with the current secret and carry its key ID, so tokens issued
+/// before a rotation keep working while the old secret stays in `previous_secrets`.
+pub struct TokenService {
+    config: JwtConfig,
+    key_id: String,
+    encoding_key: EncodingKey,
+    decoding_keys: Vec<(String, DecodingKey)>,
+    validation: Validation,
+}
+
+impl TokenService {
+    /// Fails when the secret is missing (neither `jwt.secret` nor `JWT_SECRET` set) or too short.
+    pub fn new(config: &JwtConfig) -> anyhow::Result<Self> {
+        let secret = match &config.secret {
+            Some(secret) => secret.clone(),
+            None => std::env::var("JWT_SECRET").context("JWT secret is not configured")?,
+        };
 
-    let exp = exp + Duration::from_secs(60 * 60 * 24 * 2); // 48 hours
+        for secret in std::iter::once(&secret).chain(&config.previous_secrets) {
+            ensure!(
+                secret.len() >= MIN_SECRET_LEN,
+                "JWT secret must be at least {MIN_SECRET_LEN} bytes long"
+            );
+        }
 
-    let claims = Claims {
-        exp: exp.as_secs(),
-        data: UserData { user_id },
-    };
+        let decoding_keys = std::iter::once(&secret)
+            .chain(&config.previous_secrets)
+            .map(|secret| (key_id(secret), DecodingKey::from_secret(secret.as_bytes())))
+            .collect();
 
-    let header = Header {
-        alg: Algorithm::HS512,
-        ..Default::default()
-    };
+        let mut validation = Validation::new(Algorithm::HS512);
+        validation.set_issuer(&[&config.issuer]);
+        validation.set_audience(&[&config.audience]);
+        validation.set_required_spec_claims(&["exp", "nbf", "iss", "aud", "sub"]);
+        validation.validate_nbf = true;
+        validation.leeway = config.leeway_seconds;
 
-    let secret = std::env::var("JWT_SECRET")?;
+        Ok(Self {
+            config: config.clone(),
+            key_id: key_id(&secret),
+            encoding_key: EncodingKey::from_secret(secret.as_bytes()),
+            decoding_keys,
+            validation,
+        })
+    }
 
-    encode(
-        &header,
-        &claims,
-        &EncodingKey::from_secret(secret.as_bytes()),
-    )
-    .context("Failed to sign jwt")
-}
+    /// How long newly signed tokens stay valid.
+    pub fn ttl(&self) -> Duration {
+        Duration::from_secs(self.config.ttl_seconds)
+    }
+
+    pub fn sign(&self, user_id: UserID) -> anyhow::Result<String> {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .context("Failed to count time from unix epoch")?;
+
+        let exp = now + self.ttl();
+
+        let claims = Claims {
+            sub: user_id.to_string(),
+            iss: self.config.issuer.clone(),
+            aud: self.config.audience.clone(),
+            iat: now.as_secs(),
+            nbf: now.as_secs(),
+            exp: exp.as_secs(),
+            jti: Uuid::new_v4().to_string(),
+            data: UserData { user_id },
+        };
 
-pub fn verify<T: DeserializeOwned>(token: &str) -> anyhow::Result<T> {
-    let secret = std::env::var("JWT_SECRET")?;
+        let header = Header {
+            alg: Algorithm::HS512,
+            kid: Some(self.key_id.clone()),
+            ..Default::default()
+        };
 
-    let token_data: TokenData<Claims<T>> = jsonwebtoken::decode(
-        token,
-        &DecodingKey::from_secret(secret.as_bytes()),
-        &Validation::new(Algorithm::HS512),
-    )
-    .context("Failed to validate token")?;
+        encode(&header, &claims, &self.encoding_key).context("Failed to sign jwt")
+    }
+
+    pub fn verify<T: DeserializeOwned>(&self, token: &str) -> anyhow::Result<T> {
+        let kid = decode_header(token).context("Malformed token")?.kid;
+
+        // Tokens without a known key ID are tried against every accepted key
+        let mut keys: Vec<&DecodingKey> = self
+            .decoding_keys
+            .iter()
+            .filter(|(id, _)| Some(id) == kid.as_ref())
+            .map(|(_, key)| key)
+            .collect();
+
+        if keys.is_empty() {
+            keys = self.decoding_keys.iter().map(|(_, key)| key).collect();
+        }
+
+        let mut last_error = None;
+
+        for key in keys {
+            match jsonwebtoken::decode::<Claims<T>>(token, key, &self.validation) {
+                Ok(TokenData { claims, .. }) => return Ok(claims.data),
+                Err(e) => last_error = Some(e),
+            }
+        }
+
+        Err(last_error
+            .map(anyhow::Error::from)
+            .unwrap_or_else(|| anyhow::anyhow!("No keys configured"))
+            .context("Failed to validate token"))
+    }
+}
 
-    Ok(token_data.claims.data)
+fn key_id(secret: &str) -> String {
+    let digest = Sha256::digest(secret.as_bytes());
+    digest[..8].iter().map(|b| format!("{b:02x}")).collect()
 }
@@ -0,0 +1,92 @@
+use std::collections::{HashMap, HashSet};
+
+use ammonia::{Builder, UrlRelative};
+use pulldown_cmark::{html, Event, Parser};
+
+const WORDS_PER_MINUTE: usize = 200;
+
+const ALLOWED_TAGS: &[&str] = &[
+    "a",
+    "blockquote",
+    "br",
+    "code",
+    "em",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "img",
+    "li",
+    "ol",
+    "p",
+    "pre",
+    "strong",
+    "ul",
+];
+
+pub struct Rendered {
+    pub html: String,
+    pub word_count: i32,
+    pub reading_time_minutes: i32,
+}
+
+/// Renders CommonMark `text` to sanitized HTML and counts the words of its visible text.
+pub fn render(text: &str) -> Rendered {
+    let mut word_count = 0;
+    let events = Parser::new(text).inspect(|event| {
+        if let Event::Text(chunk) | Event::Code(chunk) = event {
+            word_count += chunk.split_whitespace().count();
+        }
+    });
+
+    let mut unsafe_html = String::with_capacity(text.len() * 3 / 2);
+    html::push_html(&mut unsafe_html, events);
+
+    Rendered {
+        html: sanitizer().clean(&unsafe_html).to_string(),
+        word_count: word_count as i32,
+        reading_time_minutes: word_count.div_ceil(WORDS_PER_MINUTE) as i32,
+    }
+}
+
+/// Allows only the tags CommonMark produces, without inline styles, classes or event handlers.
+fn sanitizer() -> Builder<'static> {
+    let tag_attributes = HashMap::from([
+        ("a", HashSet::from(["href", "title"])),
+        ("img", HashSet::from(["src", "alt", "title"])),
+        ("ol", HashSet::from(["start"])),
+    ]);
+
+    let mut builder = Builder::empty();
+    builder
+        .tags(ALLOWED_TAGS.iter().copied().collect())
+        .clean_content_tags(HashSet::from(["script", "style"]))
+        .tag_attributes(tag_attributes)
+        .url_schemes(HashSet::from(["http", "https", "mailto"]))
+        .url_relative(UrlRelative::PassThrough)
+        .link_rel(Some("noopener noreferrer nofollow"));
+    builder
+}
+
+/// The visible text of `text` squashed to one line and cut to at most `max_chars` characters,
+/// e.g. for a page description.
+pub fn summary(text: &str, max_chars: usize) -> String {
+    let plain = Parser::new(text)
+        .filter_map(|event| match event {
+            Event::Text(chunk) | Event::Code(chunk) => Some(chunk),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join(" ");
+    let words = plain.split_whitespace().collect::<Vec<_>>().join(" ");
+
+    if words.chars().count() <= max_chars {
+        return words;
+    }
+
+    let cut: String = words.chars().take(max_chars.saturating_sub(1)).collect();
+    format!("{}…", cut.trim_end())
+}
@@ -1,4 +1,11 @@
+pub mod cursor;
 pub mod err;
 pub mod jwt;
+pub mod markdown;
+pub mod oidc;
 pub mod password;
 pub mod response;
+pub mod slug;
+pub mod tag;
+pub mod url;
+pub mod webauthn;
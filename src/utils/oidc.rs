@@ -0,0 +1,168 @@
+use anyhow::{bail, Context};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use jsonwebtoken::{decode, decode_header, jwk::JwkSet, Algorithm, DecodingKey, Validation};
+use rand::{rngs::OsRng, RngCore};
+use reqwest::{Client, Url};
+use serde::{Deserialize, Deserializer};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+
+use crate::configuration::OidcProviderConfig;
+
+pub struct AuthorizationRequest {
+    pub url: Url,
+    pub state: String,
+    pub nonce: String,
+    pub code_verifier: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct IdTokenClaims {
+    pub sub: String,
+    pub nonce: Option<String>,
+    pub email: Option<String>,
+
+    #[serde(default, deserialize_with = "deserialize_lenient_bool")]
+    pub email_verified: bool,
+
+    pub preferred_username: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct TokenResponse {
+    id_token: String,
+}
+
+/// Builds the authorization code request URL with fresh state, nonce and PKCE verifier.
+pub fn authorization_request(
+    provider: &OidcProviderConfig,
+) -> anyhow::Result<AuthorizationRequest> {
+    let state = random_token();
+    let nonce = random_token();
+    let code_verifier = random_token();
+
+    let url = Url::parse_with_params(
+        &provider.authorization_endpoint,
+        &[
+            ("response_type", "code"),
+            ("client_id", &provider.client_id),
+            ("redirect_uri", &provider.redirect_uri),
+            ("scope", &provider.scopes.join(" ")),
+            ("state", &state),
+            ("nonce", &nonce),
+            ("code_challenge", &code_challenge(&code_verifier)),
+            ("code_challenge_method", "S256"),
+        ],
+    )
+    .context("Invalid authorization endpoint")?;
+
+    Ok(AuthorizationRequest {
+        url,
+        state,
+        nonce,
+        code_verifier,
+    })
+}
+
+pub fn code_challenge(code_verifier: &str) -> String {
+    URL_SAFE_NO_PAD.encode(Sha256::digest(code_verifier.as_bytes()))
+}
+
+/// Exchanges an authorization code for an ID token at the provider's token endpoint.
+pub async fn exchange_code(
+    client: &Client,
+    provider: &OidcProviderConfig,
+    code: &str,
+    code_verifier: &str,
+) -> anyhow::Result<String> {
+    let mut form = vec![
+        ("grant_type", "authorization_code"),
+        ("code", code),
+        ("redirect_uri", &provider.redirect_uri),
+        ("client_id", &provider.client_id),
+        ("code_verifier", code_verifier),
+    ];
+
+    if let Some(secret) = &provider.client_secret {
+        form.push(("client_secret", secret));
+    }
+
+    let response: TokenResponse = client
+        .post(&provider.token_endpoint)
+        .form(&form)
+        .send()
+        .await
+        .context("Failed to reach token endpoint")?
+        .error_for_status()
+        .context("Token endpoint rejected the code")?
+        .json()
+        .await
+        .context("Token response has no id_token")?;
+
+    Ok(response.id_token)
+}
+
+/// Checks the ID token signature against the provider JWKS, its issuer, audience,
+/// expiry and that it carries the nonce sent with the authorization request.
+pub async fn verify_id_token(
+    client: &Client,
+    provider: &OidcProviderConfig,
+    id_token: &str,
+    nonce: &str,
+) -> anyhow::Result<IdTokenClaims> {
+    let header = decode_header(id_token).context("Malformed ID token")?;
+
+    if matches!(
+        header.alg,
+        Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+    ) {
+        bail!("Symmetric ID token signatures are not accepted");
+    }
+
+    let jwks: JwkSet = client
+        .get(&provider.jwks_uri)
+        .send()
+        .await
+        .context("Failed to fetch JWKS")?
+        .error_for_status()?
+        .json()
+        .await
+        .context("Invalid JWKS")?;
+
+    let jwk = match &header.kid {
+        Some(kid) => jwks.find(kid),
+        None => jwks.keys.first(),
+    }
+    .context("No matching key in JWKS")?;
+
+    let key = DecodingKey::from_jwk(jwk).context("Unsupported JWK")?;
+
+    let mut validation = Validation::new(header.alg);
+    validation.set_issuer(&[&provider.issuer]);
+    validation.set_audience(&[&provider.client_id]);
+
+    let claims = decode::<IdTokenClaims>(id_token, &key, &validation)
+        .context("Invalid ID token")?
+        .claims;
+
+    if claims.nonce.as_deref() != Some(nonce) {
+        bail!("ID token nonce mismatch");
+    }
+
+    Ok(claims)
+}
+
+pub fn random_token() -> String {
+    let mut bytes = [0u8; 32];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+// Some providers send `email_verified` as a string
+fn deserialize_lenient_bool<'de, D: Deserializer<'de>>(deserializer: D) -> Result<bool, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::Bool(b) => Ok(b),
+        Value::String(s) => Ok(s.eq_ignore_ascii_case("true")),
+        _ => Ok(false),
+    }
+}
@@ -0,0 +1,43 @@
+use deunicode::deunicode;
+
+const MAX_SLUG_LEN: usize = 80;
+
+/// Transliterates `title` to ASCII and joins its alphanumeric runs with dashes.
+pub fn slugify(title: &str) -> String {
+    let transliterated = deunicode(title).to_lowercase();
+
+    let mut slug = String::new();
+    let parts = transliterated
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty());
+
+    // Words are kept whole and in order, only a first word longer than the cap is cut
+    for part in parts {
+        if slug.is_empty() {
+            slug.push_str(&part[..part.len().min(MAX_SLUG_LEN)]);
+        } else if slug.len() + 1 + part.len() <= MAX_SLUG_LEN {
+            slug.push('-');
+            slug.push_str(part);
+        } else {
+            break;
+        }
+    }
+
+    if slug.is_empty() {
+        "article".to_string()
+    } else {
+        slug
+    }
+}
+
+/// Appends the smallest numeric suffix that makes `base` differ from every slug in `taken`.
+pub fn unique_slug(base: &str, taken: &[String]) -> String {
+    if !taken.iter().any(|slug| slug == base) {
+        return base.to_string();
+    }
+
+    (2..)
+        .map(|n| format!("{base}-{n}"))
+        .find(|candidate| !taken.contains(candidate))
+        .expect("Suffixes are unbounded")
+}
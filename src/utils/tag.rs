@@ -0,0 +1,19 @@
+/// Lowercases `tag`, collapses whitespace runs into single spaces and trims it.
+pub fn normalize_tag(tag: &str) -> String {
+    tag.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
+
+/// Normalizes every tag, dropping empty ones and later duplicates.
+pub fn normalize_tags(tags: &[String]) -> Vec<String> {
+    tags.iter()
+        .map(|tag| normalize_tag(tag))
+        .fold(vec![], |mut normalized, tag| {
+            if !tag.is_empty() && !normalized.contains(&tag) {
+                normalized.push(tag);
+            }
+            normalized
+        })
+}
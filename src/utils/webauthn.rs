@@ -0,0 +1,212 @@
+use anyhow::{bail, ensure, Context};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use ciborium::Value;
+use rand::{rngs::OsRng, RngCore};
+use ring::signature::{UnparsedPublicKey, ECDSA_P256_SHA256_ASN1};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::configuration::WebAuthnConfig;
+
+pub const FLAG_USER_PRESENT: u8 = 0x01;
+pub const FLAG_USER_VERIFIED: u8 = 0x04;
+pub const FLAG_ATTESTED_CREDENTIAL: u8 = 0x40;
+
+/// COSE algorithm identifier for ECDSA with P-256 and SHA-256, the only one supported.
+pub const COSE_ALG_ES256: i64 = -7;
+
+#[derive(Deserialize)]
+struct ClientData {
+    #[serde(rename = "type")]
+    kind: String,
+    challenge: String,
+    origin: String,
+}
+
+pub struct AuthenticatorData {
+    pub rp_id_hash: [u8; 32],
+    pub flags: u8,
+    pub sign_count: u32,
+    pub attested_credential: Option<AttestedCredential>,
+}
+
+pub struct AttestedCredential {
+    pub credential_id: Vec<u8>,
+    /// Uncompressed SEC1 P-256 point.
+    pub public_key: Vec<u8>,
+}
+
+impl AuthenticatorData {
+    pub fn parse(data: &[u8]) -> anyhow::Result<Self> {
+        ensure!(data.len() >= 37, "Authenticator data is too short");
+
+        let rp_id_hash: [u8; 32] = data[..32].try_into()?;
+        let flags = data[32];
+        let sign_count = u32::from_be_bytes(data[33..37].try_into()?);
+
+        let attested_credential = if flags & FLAG_ATTESTED_CREDENTIAL != 0 {
+            Some(parse_attested_credential(&data[37..])?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            rp_id_hash,
+            flags,
+            sign_count,
+            attested_credential,
+        })
+    }
+
+    pub fn has_flag(&self, flag: u8) -> bool {
+        self.flags & flag != 0
+    }
+
+    /// Checks the RP ID hash and that the user was present (and verified, if required).
+    pub fn verify(
+        &self,
+        config: &WebAuthnConfig,
+        require_user_verification: bool,
+    ) -> anyhow::Result<()> {
+        ensure!(
+            self.rp_id_hash[..] == Sha256::digest(config.rp_id.as_bytes())[..],
+            "RP ID hash mismatch"
+        );
+
+        ensure!(
+            self.has_flag(FLAG_USER_PRESENT),
+            "User presence flag is not set"
+        );
+
+        if require_user_verification {
+            ensure!(
+                self.has_flag(FLAG_USER_VERIFIED),
+                "User verification flag is not set"
+            );
+        }
+
+        Ok(())
+    }
+}
+
+pub fn new_challenge() -> Vec<u8> {
+    let mut challenge = vec![0u8; 32];
+    OsRng.fill_bytes(&mut challenge);
+    challenge
+}
+
+pub fn encode(bytes: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+pub fn decode(value: &str) -> anyhow::Result<Vec<u8>> {
+    URL_SAFE_NO_PAD
+        .decode(value.trim_end_matches('='))
+        .context("Invalid base64url value")
+}
+
+/// Checks `clientDataJSON` was produced for this ceremony, challenge and origin.
+pub fn verify_client_data(
+    client_data_json: &[u8],
+    expected_type: &str,
+    challenge: &[u8],
+    config: &WebAuthnConfig,
+) -> anyhow::Result<()> {
+    let client_data: ClientData =
+        serde_json::from_slice(client_data_json).context("Invalid clientDataJSON")?;
+
+    ensure!(
+        client_data.kind == expected_type,
+        "Unexpected ceremony type"
+    );
+    ensure!(
+        decode(&client_data.challenge)? == challenge,
+        "Challenge mismatch"
+    );
+    ensure!(client_data.origin == config.origin, "Origin mismatch");
+
+    Ok(())
+}
+
+/// Extracts the authenticator data from a CBOR attestation object.
+/// Attestation statements are not verified since `none` attestation is requested.
+pub fn parse_attestation_object(attestation_object: &[u8]) -> anyhow::Result<AuthenticatorData> {
+    let value: Value =
+        ciborium::from_reader(attestation_object).context("Invalid attestation object")?;
+
+    let auth_data = value
+        .as_map()
+        .and_then(|map| {
+            map.iter()
+                .find(|(key, _)| key.as_text() == Some("authData"))
+                .and_then(|(_, value)| value.as_bytes())
+        })
+        .context("Attestation object has no authData")?;
+
+    AuthenticatorData::parse(auth_data)
+}
+
+/// Verifies an assertion signature over `authenticatorData || SHA-256(clientDataJSON)`.
+pub fn verify_signature(
+    public_key: &[u8],
+    authenticator_data: &[u8],
+    client_data_json: &[u8],
+    signature: &[u8],
+) -> anyhow::Result<()> {
+    let mut message = authenticator_data.to_vec();
+    message.extend_from_slice(&Sha256::digest(client_data_json));
+
+    UnparsedPublicKey::new(&ECDSA_P256_SHA256_ASN1, public_key)
+        .verify(&message, signature)
+        .map_err(|_| anyhow::anyhow!("Invalid assertion signature"))
+}
+
+fn parse_attested_credential(data: &[u8]) -> anyhow::Result<AttestedCredential> {
+    // 16 bytes AAGUID followed by the 2 bytes credential ID length
+    ensure!(data.len() >= 18, "Attested credential data is too short");
+
+    let id_len = u16::from_be_bytes(data[16..18].try_into()?) as usize;
+    ensure!(data.len() >= 18 + id_len, "Credential ID is truncated");
+
+    let credential_id = data[18..18 + id_len].to_vec();
+    let cose_key: Value =
+        ciborium::from_reader(&data[18 + id_len..]).context("Invalid credential public key")?;
+
+    Ok(AttestedCredential {
+        credential_id,
+        public_key: cose_key_to_sec1(&cose_key)?,
+    })
+}
+
+fn cose_key_to_sec1(cose_key: &Value) -> anyhow::Result<Vec<u8>> {
+    let map = cose_key.as_map().context("COSE key is not a map")?;
+
+    let param = |label: i64| {
+        map.iter()
+            .find(|(key, _)| key.as_integer() == Some(label.into()))
+            .map(|(_, value)| value)
+    };
+
+    let kty = param(1).and_then(Value::as_integer);
+    let alg = param(3).and_then(Value::as_integer);
+    let crv = param(-1).and_then(Value::as_integer);
+
+    if kty != Some(2.into()) || alg != Some(COSE_ALG_ES256.into()) || crv != Some(1.into()) {
+        bail!("Only ES256 credentials are supported");
+    }
+
+    let x = param(-2)
+        .and_then(Value::as_bytes)
+        .context("COSE key has no x")?;
+    let y = param(-3)
+        .and_then(Value::as_bytes)
+        .context("COSE key has no y")?;
+    ensure!(x.len() == 32 && y.len() == 32, "Invalid P-256 coordinates");
+
+    let mut public_key = Vec::with_capacity(65);
+    public_key.push(0x04);
+    public_key.extend_from_slice(x);
+    public_key.extend_from_slice(y);
+
+    Ok(public_key)
+}
@@ -0,0 +1,54 @@
+use lib::{routes::admin::invites::InviteCode, utils::response::DataResponse};
+use reqwest::StatusCode;
+use serde_json::json;
+
+use crate::helper::TestApp;
+
+#[tokio::test]
+async fn fail_to_create_invite_without_admin_role() {
+    let app = TestApp::spawn().await;
+
+    let response = app
+        .create_invite(&app.test_users[0], &json!({ "max_uses": 1 }))
+        .await;
+
+    assert_eq!(response.status().as_u16(), StatusCode::FORBIDDEN);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn admin_creates_invite_code() {
+    let app = TestApp::spawn().await;
+    app.make_admin(&app.test_users[0]).await;
+
+    let response = app
+        .create_invite(&app.test_users[0], &json!({ "max_uses": 3 }))
+        .await;
+
+    assert_eq!(response.status().as_u16(), StatusCode::CREATED);
+
+    let body: DataResponse<InviteCode> = response.json().await.unwrap();
+    assert!(!body.data.code.is_empty());
+    assert_eq!(body.data.max_uses, 3);
+    assert_eq!(body.data.uses, 0);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn fail_to_create_invite_expiring_in_the_past() {
+    let app = TestApp::spawn().await;
+    app.make_admin(&app.test_users[0]).await;
+
+    let response = app
+        .create_invite(
+            &app.test_users[0],
+            &json!({ "expires_at": "2020-01-01T00:00:00" }),
+        )
+        .await;
+
+    assert_eq!(response.status().as_u16(), StatusCode::BAD_REQUEST);
+
+    app.clean().await;
+}
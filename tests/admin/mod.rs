@@ -0,0 +1,2 @@
+mod invites;
+mod tags;
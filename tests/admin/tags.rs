@@ -0,0 +1,93 @@
+use lib::{routes::tags::TagCount, utils::response::DataResponse};
+use reqwest::{header::AUTHORIZATION, Client, Response, StatusCode};
+use serde_json::{json, Value};
+
+use crate::helper::{TestApp, TestUser};
+
+async fn admin_request(app: &TestApp, path: &str, body: &Value, user: &TestUser) -> Response {
+    let url = format!("{}/admin/tags/{}", &app.address, path);
+    let request = match path.ends_with("/merge") {
+        true => Client::new().post(url),
+        false => Client::new().patch(url),
+    };
+
+    request
+        .json(body)
+        .header(AUTHORIZATION, app.get_jwt(user).await)
+        .send()
+        .await
+        .unwrap()
+}
+
+async fn article_tags(app: &TestApp, id: &str) -> Value {
+    let body: Value = app.get_article(id).await.json().await.unwrap();
+    body["data"]["tags"].clone()
+}
+
+#[tokio::test]
+async fn fail_to_rename_tag_without_admin_role() {
+    let app = TestApp::spawn().await;
+    let user = &app.test_users[0];
+    app.create_article_id("Tagged article", &["rust"], user)
+        .await;
+
+    let response = admin_request(&app, "rust", &json!({ "name": "rustlang" }), user).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn admin_renames_tag() {
+    let app = TestApp::spawn().await;
+    let admin = &app.test_users[0];
+    app.make_admin(admin).await;
+    let id = app
+        .create_article_id("Tagged article", &["web", "rust"], admin)
+        .await;
+    app.create_article_id("Tagged article", &["go"], admin)
+        .await;
+
+    let response = admin_request(&app, "Rust", &json!({ "name": " RustLang " }), admin).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: DataResponse<TagCount> = response.json().await.unwrap();
+    assert_eq!(body.data.name, "rustlang");
+    assert_eq!(body.data.article_count, 1);
+
+    assert_eq!(article_tags(&app, &id).await, json!(["web", "rustlang"]));
+
+    let response = admin_request(&app, "rustlang", &json!({ "name": "go" }), admin).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = admin_request(&app, "missing", &json!({ "name": "found" }), admin).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn admin_merges_tags() {
+    let app = TestApp::spawn().await;
+    let admin = &app.test_users[0];
+    app.make_admin(admin).await;
+    let both = app
+        .create_article_id("Tagged article", &["rust", "web", "rustlang"], admin)
+        .await;
+    let single = app
+        .create_article_id("Tagged article", &["rustlang", "axum"], admin)
+        .await;
+
+    let response = admin_request(&app, "rustlang/merge", &json!({ "into": "Rust" }), admin).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: DataResponse<TagCount> = response.json().await.unwrap();
+    assert_eq!(body.data.name, "rust");
+    assert_eq!(body.data.article_count, 2);
+
+    assert_eq!(article_tags(&app, &both).await, json!(["rust", "web"]));
+    assert_eq!(article_tags(&app, &single).await, json!(["rust", "axum"]));
+
+    let response = admin_request(&app, "rust/merge", &json!({ "into": "rust" }), admin).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    app.clean().await;
+}
@@ -0,0 +1,205 @@
+use lib::{
+    routes::articles::{bookmarks::Collection, Article},
+    types::SearchType,
+    utils::response::DataResponse,
+};
+use reqwest::{header::AUTHORIZATION, Client, StatusCode};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::helper::{TestApp, TestUser};
+
+async fn list_bookmarks(
+    app: &TestApp,
+    user: &TestUser,
+    query: &[(&str, String)],
+) -> SearchType<Article> {
+    let response = Client::new()
+        .get(format!("{}/me/bookmarks", &app.address))
+        .query(query)
+        .header(AUTHORIZATION, app.get_jwt(user).await)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    response.json().await.unwrap()
+}
+
+async fn create_collection(app: &TestApp, name: &str, user: &TestUser) -> Collection {
+    let response = Client::new()
+        .post(format!("{}/me/bookmarks/collections", &app.address))
+        .json(&json!({ "name": name }))
+        .header(AUTHORIZATION, app.get_jwt(user).await)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let body: DataResponse<Collection> = response.json().await.unwrap();
+    body.data
+}
+
+fn titles(page: &SearchType<Article>) -> Vec<&str> {
+    page.results.iter().map(|a| a.title.as_str()).collect()
+}
+
+#[tokio::test]
+async fn page_through_bookmarks_newest_first() {
+    let app = TestApp::spawn().await;
+    let reader = &app.test_users[1];
+
+    for title in ["First", "Second", "Third"] {
+        let id = app.create_article_id(title, &[], &app.test_users[0]).await;
+        let response = app.bookmark(&id, None, reader).await;
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+    }
+    app.create_article_id("Not bookmarked", &[], &app.test_users[0])
+        .await;
+
+    let first = list_bookmarks(&app, reader, &[("limit", "2".to_string())]).await;
+    assert_eq!(titles(&first), vec!["Third", "Second"]);
+    assert_eq!(first.total, Some(3));
+
+    let query = [
+        ("limit", "2".to_string()),
+        ("after", first.next_cursor.unwrap()),
+    ];
+    let second = list_bookmarks(&app, reader, &query).await;
+    assert_eq!(titles(&second), vec!["First"]);
+    assert!(second.next_cursor.is_none());
+
+    let other = list_bookmarks(&app, &app.test_users[2], &[]).await;
+    assert!(other.results.is_empty());
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn remove_bookmark() {
+    let app = TestApp::spawn().await;
+    let reader = &app.test_users[1];
+    let id = app
+        .create_article_id("Read later", &[], &app.test_users[0])
+        .await;
+
+    app.bookmark(&id, None, reader).await;
+
+    let response = Client::new()
+        .delete(format!("{}/articles/{}/bookmark", &app.address, id))
+        .header(AUTHORIZATION, app.get_jwt(reader).await)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let page = list_bookmarks(&app, reader, &[]).await;
+    assert!(page.results.is_empty());
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn file_bookmarks_into_collections() {
+    let app = TestApp::spawn().await;
+    let reader = &app.test_users[1];
+
+    let recipes = create_collection(&app, "Recipes", reader).await;
+    let travel = create_collection(&app, "Travel", reader).await;
+    assert_eq!((recipes.position, travel.position), (0, 1));
+
+    let soup = app.create_article_id("Soup", &[], &app.test_users[0]).await;
+    let paris = app
+        .create_article_id("Paris", &[], &app.test_users[0])
+        .await;
+    app.bookmark(&soup, Some(recipes.id), reader).await;
+    app.bookmark(&paris, Some(travel.id), reader).await;
+
+    let query = [("collection_id", recipes.id.to_string())];
+    let page = list_bookmarks(&app, reader, &query).await;
+    assert_eq!(titles(&page), vec!["Soup"]);
+
+    // Collections of other users cannot be used
+    let response = app
+        .bookmark(&soup, Some(recipes.id), &app.test_users[2])
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = Client::new()
+        .post(format!("{}/me/bookmarks/collections", &app.address))
+        .json(&json!({ "name": "Recipes" }))
+        .header(AUTHORIZATION, app.get_jwt(reader).await)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    // Removing a collection keeps its bookmarks
+    let response = Client::new()
+        .delete(format!(
+            "{}/me/bookmarks/collections/{}",
+            &app.address, travel.id
+        ))
+        .header(AUTHORIZATION, app.get_jwt(reader).await)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let page = list_bookmarks(&app, reader, &[]).await;
+    assert_eq!(titles(&page), vec!["Paris", "Soup"]);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn reorder_and_rename_collections() {
+    let app = TestApp::spawn().await;
+    let reader = &app.test_users[1];
+
+    let ids: Vec<Uuid> = {
+        let mut ids = vec![];
+        for name in ["One", "Two", "Three"] {
+            ids.push(create_collection(&app, name, reader).await.id);
+        }
+        ids
+    };
+
+    let url = format!("{}/me/bookmarks/collections/order", &app.address);
+    let response = Client::new()
+        .put(&url)
+        .json(&json!({ "collection_ids": [ids[2], ids[0]] }))
+        .header(AUTHORIZATION, app.get_jwt(reader).await)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = Client::new()
+        .put(&url)
+        .json(&json!({ "collection_ids": [ids[2], ids[0], ids[1]] }))
+        .header(AUTHORIZATION, app.get_jwt(reader).await)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: DataResponse<Vec<Collection>> = response.json().await.unwrap();
+    let names: Vec<_> = body.data.iter().map(|c| c.name.as_str()).collect();
+    assert_eq!(names, vec!["Three", "One", "Two"]);
+
+    let response = Client::new()
+        .patch(format!(
+            "{}/me/bookmarks/collections/{}",
+            &app.address, ids[0]
+        ))
+        .json(&json!({ "name": "First" }))
+        .header(AUTHORIZATION, app.get_jwt(reader).await)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: DataResponse<Collection> = response.json().await.unwrap();
+    assert_eq!((body.data.name.as_str(), body.data.position), ("First", 1));
+
+    app.clean().await;
+}
@@ -0,0 +1,234 @@
+use lib::{
+    routes::articles::{comments::Comment, Article},
+    types::SearchType,
+    utils::response::DataResponse,
+};
+use reqwest::{header::AUTHORIZATION, Client, StatusCode};
+use serde_json::json;
+
+use crate::helper::TestApp;
+
+async fn list_comments(
+    app: &TestApp,
+    article_id: &str,
+    query: &[(&str, &str)],
+) -> SearchType<Comment> {
+    let response = Client::new()
+        .get(format!("{}/articles/{}/comments", &app.address, article_id))
+        .query(query)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn reply_to_comments_in_threads() {
+    let app = TestApp::spawn().await;
+    let article_id = app
+        .create_article_id("A commented article", &[], &app.test_users[0])
+        .await;
+
+    let response = app
+        .create_comment(
+            &article_id,
+            &json!({ "text": "First!" }),
+            &app.test_users[1],
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let parent: DataResponse<Comment> = response.json().await.unwrap();
+    assert_eq!(parent.data.author.unwrap().id, app.test_users[1].id);
+
+    let body = json!({ "text": "Agreed", "parent_id": parent.data.id });
+    let response = app
+        .create_comment(&article_id, &body, &app.test_users[2])
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let comments = list_comments(&app, &article_id, &[]).await;
+    assert_eq!(comments.total, Some(2));
+    assert_eq!(comments.results[0].parent_id, None);
+    assert_eq!(comments.results[1].parent_id, Some(parent.data.id));
+
+    let response = app.get_article(&article_id).await;
+    let article: DataResponse<Article> = response.json().await.unwrap();
+    assert_eq!(article.data.comment_count, 2);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn reject_replies_to_other_articles() {
+    let app = TestApp::spawn().await;
+    let article_id = app
+        .create_article_id("A commented article", &[], &app.test_users[0])
+        .await;
+
+    let other_id = app
+        .create_article_id("Another article", &[], &app.test_users[0])
+        .await;
+
+    let response = app
+        .create_comment(
+            &other_id,
+            &json!({ "text": "Elsewhere" }),
+            &app.test_users[1],
+        )
+        .await;
+    let other: DataResponse<Comment> = response.json().await.unwrap();
+
+    let body = json!({ "text": "Wrong thread", "parent_id": other.data.id });
+    let response = app
+        .create_comment(&article_id, &body, &app.test_users[1])
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let response = app
+        .create_comment(&article_id, &json!({ "text": "" }), &app.test_users[1])
+        .await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn only_author_can_edit_comment() {
+    let app = TestApp::spawn().await;
+    let article_id = app
+        .create_article_id("A commented article", &[], &app.test_users[0])
+        .await;
+
+    let response = app
+        .create_comment(
+            &article_id,
+            &json!({ "text": "Typo here" }),
+            &app.test_users[1],
+        )
+        .await;
+    let comment: DataResponse<Comment> = response.json().await.unwrap();
+    let url = format!(
+        "{}/articles/{}/comments/{}",
+        &app.address, article_id, comment.data.id
+    );
+
+    let response = Client::new()
+        .patch(&url)
+        .json(&json!({ "text": "Not mine" }))
+        .header(AUTHORIZATION, app.get_jwt(&app.test_users[0]).await)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = Client::new()
+        .patch(&url)
+        .json(&json!({ "text": "Fixed here" }))
+        .header(AUTHORIZATION, app.get_jwt(&app.test_users[1]).await)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: DataResponse<Comment> = response.json().await.unwrap();
+    assert_eq!(body.data.text.as_deref(), Some("Fixed here"));
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn soft_delete_keeps_thread_structure() {
+    let app = TestApp::spawn().await;
+    let article_id = app
+        .create_article_id("A commented article", &[], &app.test_users[0])
+        .await;
+
+    let response = app
+        .create_comment(&article_id, &json!({ "text": "Spam" }), &app.test_users[1])
+        .await;
+    let parent: DataResponse<Comment> = response.json().await.unwrap();
+
+    let body = json!({ "text": "Reply to spam", "parent_id": parent.data.id });
+    app.create_comment(&article_id, &body, &app.test_users[2])
+        .await;
+
+    let url = format!(
+        "{}/articles/{}/comments/{}",
+        &app.address, article_id, parent.data.id
+    );
+
+    let response = Client::new()
+        .delete(&url)
+        .header(AUTHORIZATION, app.get_jwt(&app.test_users[2]).await)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    // The article owner moderates comments under it
+    let response = Client::new()
+        .delete(&url)
+        .header(AUTHORIZATION, app.get_jwt(&app.test_users[0]).await)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let comments = list_comments(&app, &article_id, &[]).await;
+    assert_eq!(comments.results.len(), 2);
+    assert!(comments.results[0].deleted);
+    assert!(comments.results[0].text.is_none());
+    assert!(comments.results[0].author.is_none());
+    assert_eq!(comments.results[1].parent_id, Some(parent.data.id));
+
+    let response = app.get_article(&article_id).await;
+    let article: DataResponse<Article> = response.json().await.unwrap();
+    assert_eq!(article.data.comment_count, 1);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn paginate_comments_with_cursors() {
+    let app = TestApp::spawn().await;
+    let article_id = app
+        .create_article_id("A commented article", &[], &app.test_users[0])
+        .await;
+
+    for i in 0..5 {
+        let body = json!({ "text": format!("Comment {i}") });
+        app.create_comment(&article_id, &body, &app.test_users[1])
+            .await;
+    }
+
+    let first = list_comments(&app, &article_id, &[("limit", "2")]).await;
+    let texts: Vec<_> = first
+        .results
+        .iter()
+        .map(|c| c.text.clone().unwrap())
+        .collect();
+    assert_eq!(texts, vec!["Comment 0", "Comment 1"]);
+    assert_eq!(first.total, Some(5));
+
+    let cursor = first.next_cursor.unwrap();
+    let second = list_comments(&app, &article_id, &[("limit", "2"), ("after", &cursor)]).await;
+    let texts: Vec<_> = second
+        .results
+        .iter()
+        .map(|c| c.text.clone().unwrap())
+        .collect();
+    assert_eq!(texts, vec!["Comment 2", "Comment 3"]);
+
+    let cursor = second.prev_cursor.unwrap();
+    let back = list_comments(&app, &article_id, &[("limit", "2"), ("before", &cursor)]).await;
+    let texts: Vec<_> = back
+        .results
+        .iter()
+        .map(|c| c.text.clone().unwrap())
+        .collect();
+    assert_eq!(texts, vec!["Comment 0", "Comment 1"]);
+    assert!(back.prev_cursor.is_none());
+
+    app.clean().await;
+}
@@ -1,7 +1,7 @@
 use std::str::FromStr;
 
 use claims::assert_ok;
-use lib::{routes::articles::create_article, utils::err::AppError};
+use lib::routes::articles::create_article;
 use reqwest::StatusCode;
 use serde_json::Value;
 
@@ -15,6 +15,7 @@ async fn create_new_article() {
         title: "A unique title".to_string(),
         text: "A long new article".to_string(),
         tags: Some(vec!["tag1".to_string(), "tag2".to_string()]),
+        ..Default::default()
     };
 
     let response = app.create_article(&payload, &app.test_users[0]).await;
@@ -29,25 +30,30 @@ async fn create_new_article() {
 }
 
 #[tokio::test]
-async fn fail_on_creating_article_with_duplicated_title() {
+async fn duplicated_titles_get_distinct_slugs() {
     let app = TestApp::spawn().await;
 
     let payload = create_article::Payload {
         title: "A unique title".to_string(),
         text: "A long new article".to_string(),
         tags: Some(vec!["tag1".to_string(), "tag2".to_string()]),
+        ..Default::default()
     };
 
-    let response = app.create_article(&payload, &app.test_users[0]).await;
-    assert_eq!(response.status().as_u16(), StatusCode::CREATED);
+    let mut slugs = vec![];
 
-    let response = app.create_article(&payload, &app.test_users[0]).await;
-    assert_eq!(response.status().as_u16(), StatusCode::BAD_REQUEST);
+    for user in [&app.test_users[0], &app.test_users[0], &app.test_users[1]] {
+        let response = app.create_article(&payload, user).await;
+        assert_eq!(response.status().as_u16(), StatusCode::CREATED);
+
+        let json: Value = response.json().await.unwrap();
+        slugs.push(json["data"]["slug"].as_str().unwrap().to_string());
+    }
 
-    let json: Value = response.json().await.unwrap();
+    // Slugs are unique per author only
     assert_eq!(
-        AppError::DuplicatedArticle.to_string(),
-        json["message"].as_str().unwrap().to_string()
+        slugs,
+        vec!["a-unique-title", "a-unique-title-2", "a-unique-title"]
     );
 
     app.clean().await;
@@ -0,0 +1,35 @@
+use lib::routes::articles::create_article;
+use reqwest::StatusCode;
+use serde_json::Value;
+
+use crate::helper::TestApp;
+
+#[tokio::test]
+async fn author_deletes_article() {
+    let app = TestApp::spawn().await;
+
+    let payload = create_article::Payload {
+        title: "A unique title".to_string(),
+        text: "A long new article".to_string(),
+        tags: None,
+        ..Default::default()
+    };
+
+    let response = app.create_article(&payload, &app.test_users[0]).await;
+    let body: Value = response.json().await.unwrap();
+    let id = body["data"]["id"].as_str().unwrap();
+
+    let response = app.delete_article(id, &app.test_users[1]).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = app.delete_article(id, &app.test_users[0]).await;
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = app.get_article(id).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = app.delete_article(id, &app.test_users[0]).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    app.clean().await;
+}
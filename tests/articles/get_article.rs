@@ -0,0 +1,45 @@
+use lib::{
+    routes::articles::{create_article, Article},
+    utils::response::DataResponse,
+};
+use reqwest::StatusCode;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::helper::TestApp;
+
+#[tokio::test]
+async fn return_article_by_id() {
+    let app = TestApp::spawn().await;
+
+    let payload = create_article::Payload {
+        title: "A unique title".to_string(),
+        text: "A long new article".to_string(),
+        tags: Some(vec!["tag1".to_string()]),
+        ..Default::default()
+    };
+
+    let response = app.create_article(&payload, &app.test_users[0]).await;
+    let body: Value = response.json().await.unwrap();
+    let id = body["data"]["id"].as_str().unwrap();
+
+    let response = app.get_article(id).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body: DataResponse<Article> = response.json().await.unwrap();
+    assert_eq!(body.data.title, payload.title);
+    assert_eq!(body.data.author.id, app.test_users[0].id);
+    assert_eq!(body.data.created_at, body.data.updated_at);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn return_404_for_missing_article() {
+    let app = TestApp::spawn().await;
+
+    let response = app.get_article(&Uuid::new_v4().to_string()).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    app.clean().await;
+}
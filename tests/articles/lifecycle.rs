@@ -0,0 +1,154 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use lib::{
+    domains::article::ArticleStatus,
+    routes::articles::{create_article, list, Article},
+    types::SearchType,
+    utils::response::DataResponse,
+};
+use reqwest::{header::AUTHORIZATION, Client, StatusCode};
+use serde_json::{json, Value};
+
+use crate::helper::{TestApp, TestUser};
+
+async fn create(app: &TestApp, payload: &create_article::Payload) -> String {
+    let response = app.create_article(payload, &app.test_users[0]).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let body: Value = response.json().await.unwrap();
+    body["data"]["id"].as_str().unwrap().to_string()
+}
+
+async fn list_titles(app: &TestApp, user: Option<&TestUser>) -> Vec<String> {
+    let mut request = Client::new()
+        .post(format!("{}/articles/get-articles", &app.address))
+        .json(&list::Payload::default());
+
+    if let Some(user) = user {
+        request = request.header(AUTHORIZATION, app.get_jwt(user).await);
+    }
+
+    let body: SearchType<Article> = request.send().await.unwrap().json().await.unwrap();
+    body.results.into_iter().map(|a| a.title).collect()
+}
+
+#[tokio::test]
+async fn drafts_are_visible_only_to_author() {
+    let app = TestApp::spawn().await;
+
+    let id = create(
+        &app,
+        &create_article::Payload {
+            title: "A draft".to_string(),
+            text: "Not ready for readers".to_string(),
+            status: ArticleStatus::Draft,
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let response = app.get_article(&id).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = Client::new()
+        .get(format!("{}/articles/{}", &app.address, id))
+        .header(AUTHORIZATION, app.get_jwt(&app.test_users[0]).await)
+        .send()
+        .await
+        .unwrap();
+    let body: DataResponse<Article> = response.json().await.unwrap();
+    assert_eq!(body.data.status, ArticleStatus::Draft);
+    assert_eq!(body.data.published_at, None);
+
+    assert!(list_titles(&app, None).await.is_empty());
+    assert!(list_titles(&app, Some(&app.test_users[1])).await.is_empty());
+    assert_eq!(
+        list_titles(&app, Some(&app.test_users[0])).await,
+        vec!["A draft".to_string()]
+    );
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn scheduler_publishes_due_articles() {
+    let app = TestApp::spawn_with(|config| config.jobs.publish_interval_seconds = 1).await;
+
+    let id = create(
+        &app,
+        &create_article::Payload {
+            title: "Scheduled".to_string(),
+            text: "Published a bit later".to_string(),
+            status: ArticleStatus::Scheduled,
+            published_at: Some(Utc::now().naive_utc() + chrono::Duration::seconds(2)),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let response = app.get_article(&id).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    tokio::time::sleep(Duration::from_secs(4)).await;
+
+    let response = app.get_article(&id).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body: DataResponse<Article> = response.json().await.unwrap();
+    assert_eq!(body.data.status, ArticleStatus::Published);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn publishing_scheduled_article_by_hand_publishes_it_now() {
+    let app = TestApp::spawn().await;
+
+    let id = create(
+        &app,
+        &create_article::Payload {
+            title: "Scheduled".to_string(),
+            text: "Published earlier than planned".to_string(),
+            status: ArticleStatus::Scheduled,
+            published_at: Some(Utc::now().naive_utc() + chrono::Duration::hours(1)),
+            ..Default::default()
+        },
+    )
+    .await;
+
+    let body = json!({ "status": "published", "version": 1 });
+    let response = app.update_article(&id, &body, &app.test_users[0]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app.get_article(&id).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body: DataResponse<Article> = response.json().await.unwrap();
+    assert_eq!(body.data.status, ArticleStatus::Published);
+    assert!(body.data.published_at.unwrap() <= Utc::now().naive_utc());
+
+    assert_eq!(list_titles(&app, None).await, vec!["Scheduled".to_string()]);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn reject_schedule_without_future_date() {
+    let app = TestApp::spawn().await;
+
+    for published_at in [None, Some(Utc::now().naive_utc())] {
+        let payload = create_article::Payload {
+            title: "Scheduled".to_string(),
+            text: "Published a bit later".to_string(),
+            status: ArticleStatus::Scheduled,
+            published_at,
+            ..Default::default()
+        };
+
+        let response = app.create_article(&payload, &app.test_users[0]).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+
+    app.clean().await;
+}
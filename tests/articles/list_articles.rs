@@ -3,6 +3,7 @@ use std::time::Duration;
 use lib::{
     routes::articles::{create_article, list::Payload, Article},
     types::SearchType,
+    utils::response::DataResponse,
 };
 use reqwest::{Response, StatusCode};
 use serde::Serialize;
@@ -16,11 +17,13 @@ fn get_payload() -> Vec<create_article::Payload> {
             title: "Around the planet".to_string(),
             text: "Lorem ipsum donna".to_string(),
             tags: Some(vec!["tag1".to_string()]),
+            ..Default::default()
         },
         create_article::Payload {
             title: "Circle in life".to_string(),
             text: "A long new article".to_string(),
             tags: Some(vec!["tag1".to_string(), "tag2".to_string()]),
+            ..Default::default()
         },
     ]
 }
@@ -45,7 +48,7 @@ async fn successfully_return_articles() {
     let response = get_articles_list(&body, &app.address).await;
     let response: SearchType<Article> = response.json().await.unwrap();
 
-    assert_eq!(response.total, 1);
+    assert_eq!(response.total, Some(1));
     assert_eq!(response.results[0].title, article_payloads[0].title);
     assert_eq!(response.results[0].text, article_payloads[0].text);
 
@@ -76,7 +79,7 @@ async fn filter_articles_by_tags() {
     let response = get_articles_list(&body, &app.address).await;
     let response: SearchType<Article> = response.json().await.unwrap();
 
-    assert_eq!(response.total, 1);
+    assert_eq!(response.total, Some(1));
 
     assert_eq!(response.results[0].title, article_payloads[1].title);
     assert_eq!(response.results[0].text, article_payloads[1].text);
@@ -116,7 +119,7 @@ async fn order_articles_by_created_at_asc() {
     let response = get_articles_list(&body, &app.address).await;
     let response: SearchType<Article> = response.json().await.unwrap();
 
-    assert_eq!(response.total, 2);
+    assert_eq!(response.total, Some(2));
 
     // Have to keep .rev() because we get articles sorted by last inserted
     for (i, item) in article_payloads.into_iter().enumerate() {
@@ -136,7 +139,7 @@ async fn filter_articles_by_username() {
 
     for (i, article_payload) in article_payloads.iter().enumerate() {
         let response = app
-            .create_article(&article_payload, &app.test_users[i])
+            .create_article(article_payload, &app.test_users[i])
             .await;
 
         assert_eq!(response.status().as_u16(), StatusCode::CREATED);
@@ -147,7 +150,7 @@ async fn filter_articles_by_username() {
     let response = get_articles_list(&body, &app.address).await;
     let response: SearchType<Article> = response.json().await.unwrap();
 
-    assert_eq!(response.total, 1);
+    assert_eq!(response.total, Some(1));
 
     assert_eq!(response.results[0].title, article_payloads[0].title);
     assert_eq!(response.results[0].text, article_payloads[0].text);
@@ -160,6 +163,143 @@ async fn filter_articles_by_username() {
     app.clean().await;
 }
 
+#[tokio::test]
+async fn filter_articles_by_multiple_tags() {
+    let app = TestApp::spawn().await;
+
+    let tagged = [
+        ("Rust on the web", vec!["rust", "web"]),
+        ("Plain Rust", vec!["rust"]),
+        ("Going places", vec!["go"]),
+        ("Untagged", vec![]),
+    ];
+    for (title, tags) in &tagged {
+        let payload = create_article::Payload {
+            title: title.to_string(),
+            text: "Article about tags".to_string(),
+            tags: (!tags.is_empty()).then(|| tags.iter().map(|tag| tag.to_string()).collect()),
+            ..Default::default()
+        };
+        let response = app.create_article(&payload, &app.test_users[0]).await;
+        assert_eq!(response.status().as_u16(), StatusCode::CREATED);
+    }
+
+    let cases = [
+        (
+            json!({ "tags_any": ["Go", "web"] }),
+            vec!["Going places", "Rust on the web"],
+        ),
+        (
+            json!({ "tags_all": ["rust", " WEB "] }),
+            vec!["Rust on the web"],
+        ),
+        (
+            json!({ "exclude_tags": ["rust"] }),
+            vec!["Going places", "Untagged"],
+        ),
+        (json!({ "has_tags": false }), vec!["Untagged"]),
+        (
+            json!({ "has_tags": true, "exclude_tags": ["go"] }),
+            vec!["Plain Rust", "Rust on the web"],
+        ),
+    ];
+    for (body, expected) in cases {
+        let response = get_articles_list(&body, &app.address).await;
+        assert_eq!(response.status().as_u16(), StatusCode::OK);
+
+        assert_eq!(sorted_titles(response).await, expected, "{body}");
+    }
+
+    let invalid = [
+        json!({ "tags_any": [] }),
+        json!({ "tags_all": [" "] }),
+        json!({ "exclude_tags": ["rust", ""] }),
+    ];
+    for body in invalid {
+        let response = get_articles_list(&body, &app.address).await;
+        assert_eq!(
+            response.status().as_u16(),
+            StatusCode::BAD_REQUEST,
+            "{body}"
+        );
+    }
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn filter_articles_by_authors_and_creation_dates() {
+    let app = TestApp::spawn().await;
+
+    let mut created = vec![];
+    for (i, title) in ["First article", "Second article", "Third article"]
+        .iter()
+        .enumerate()
+    {
+        let payload = create_article::Payload {
+            title: title.to_string(),
+            text: "Article in a date range".to_string(),
+            ..Default::default()
+        };
+        let response = app.create_article(&payload, &app.test_users[i]).await;
+        let body: DataResponse<Article> = response.json().await.unwrap();
+        created.push(body.data.created_at);
+
+        // For articles to have different created_at
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+
+    let authors = json!([&app.test_users[0].username, &app.test_users[2].username]);
+    let cases = [
+        (
+            json!({ "authors": authors }),
+            vec!["First article", "Third article"],
+        ),
+        (
+            json!({ "created_after": created[1] }),
+            vec!["Second article", "Third article"],
+        ),
+        (
+            json!({ "created_after": created[1], "created_before": created[2] }),
+            vec!["Second article"],
+        ),
+        (
+            json!({ "authors": authors, "created_before": created[2] }),
+            vec!["First article"],
+        ),
+    ];
+    for (body, expected) in cases {
+        let response = get_articles_list(&body, &app.address).await;
+        assert_eq!(response.status().as_u16(), StatusCode::OK);
+
+        assert_eq!(sorted_titles(response).await, expected, "{body}");
+    }
+
+    let invalid = [
+        json!({ "authors": [] }),
+        json!({ "authors": ["ab"] }),
+        json!({ "author": &app.test_users[0].username, "authors": authors }),
+        json!({ "created_after": created[2], "created_before": created[1] }),
+    ];
+    for body in invalid {
+        let response = get_articles_list(&body, &app.address).await;
+        assert_eq!(
+            response.status().as_u16(),
+            StatusCode::BAD_REQUEST,
+            "{body}"
+        );
+    }
+
+    app.clean().await;
+}
+
+async fn sorted_titles(response: Response) -> Vec<String> {
+    let response: SearchType<Article> = response.json().await.unwrap();
+    let mut titles: Vec<String> = response.results.into_iter().map(|a| a.title).collect();
+    titles.sort();
+    titles
+}
+
 async fn get_articles_list<P: Serialize>(payload: &P, addr: &str) -> Response {
     reqwest::Client::new()
         .post(format!("{}/articles/get-articles", addr))
@@ -0,0 +1,76 @@
+use lib::{
+    routes::articles::{create_article, Article},
+    utils::response::DataResponse,
+};
+use reqwest::StatusCode;
+use serde_json::json;
+
+use crate::helper::TestApp;
+
+#[tokio::test]
+async fn render_sanitized_html_on_create() {
+    let app = TestApp::spawn().await;
+    let payload = create_article::Payload {
+        title: "Markdown article".to_string(),
+        text: concat!(
+            "# Heading\n\n",
+            "Some *emphasis* and a [link](https://example.com \"Example\").\n\n",
+            "<script>alert('x')</script>\n\n",
+            "[bad](javascript:alert(1)) <img src=\"/uploads/1\" onerror=\"alert(1)\">\n",
+        )
+        .to_string(),
+        ..Default::default()
+    };
+
+    let response = app.create_article(&payload, &app.test_users[0]).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let body: DataResponse<Article> = response.json().await.unwrap();
+    let article = body.data;
+
+    assert_eq!(article.text, payload.text);
+    assert!(article.html.contains("<h1>Heading</h1>"));
+    assert!(article.html.contains("<em>emphasis</em>"));
+    assert!(article.html.contains(
+        "<a href=\"https://example.com\" title=\"Example\" rel=\"noopener noreferrer nofollow\">"
+    ));
+    assert!(article.html.contains("<img src=\"/uploads/1\">"));
+    assert!(!article.html.contains("script"));
+    assert!(!article.html.contains("javascript"));
+    assert!(!article.html.contains("onerror"));
+
+    let response = app.get_article(&article.id).await;
+    let body: DataResponse<Article> = response.json().await.unwrap();
+    assert_eq!(body.data.html, article.html);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn count_words_and_reading_time() {
+    let app = TestApp::spawn().await;
+    let payload = create_article::Payload {
+        title: "Counted article".to_string(),
+        text: "A **short** article with `six` words".to_string(),
+        ..Default::default()
+    };
+
+    let response = app.create_article(&payload, &app.test_users[0]).await;
+    let body: DataResponse<Article> = response.json().await.unwrap();
+    let article = body.data;
+    assert_eq!(article.word_count, 6);
+    assert_eq!(article.reading_time_minutes, 1);
+
+    // Updating the text renders it again
+    let text = "word ".repeat(450);
+    let update = json!({ "text": text, "version": article.version });
+    let response = app
+        .update_article(&article.id, &update, &app.test_users[0])
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: DataResponse<Article> = response.json().await.unwrap();
+    assert_eq!(body.data.word_count, 450);
+    assert_eq!(body.data.reading_time_minutes, 3);
+    assert_eq!(body.data.html, format!("<p>{}</p>\n", text.trim_end()));
+
+    app.clean().await;
+}
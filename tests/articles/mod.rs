@@ -1,4 +1,17 @@
+mod bookmarks;
+mod comments;
 mod create_article;
+mod delete_article;
+mod get_article;
 mod get_subscribed;
+mod lifecycle;
 mod list_articles;
+mod markdown;
+mod pagination;
+mod reactions;
+mod revisions;
+mod search;
+mod slugs;
 mod subscribe;
+mod totals;
+mod update_article;
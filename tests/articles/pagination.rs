@@ -0,0 +1,154 @@
+use lib::{
+    routes::articles::{create_article, Article},
+    types::SearchType,
+};
+use reqwest::{header::AUTHORIZATION, Client, StatusCode};
+use serde_json::{json, Value};
+
+use crate::helper::{TestApp, TestUser};
+
+async fn create(app: &TestApp, title: &str, user: &TestUser) {
+    let payload = create_article::Payload {
+        title: title.to_string(),
+        text: "A long new article".to_string(),
+        ..Default::default()
+    };
+
+    let response = app.create_article(&payload, user).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+}
+
+async fn list(app: &TestApp, body: Value) -> SearchType<Article> {
+    let response = Client::new()
+        .post(format!("{}/articles/get-articles", &app.address))
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    response.json().await.unwrap()
+}
+
+fn titles(page: &SearchType<Article>) -> Vec<&str> {
+    page.results.iter().map(|a| a.title.as_str()).collect()
+}
+
+#[tokio::test]
+async fn walk_pages_with_cursors() {
+    let app = TestApp::spawn().await;
+
+    for title in ["First", "Second", "Third", "Fourth", "Fifth"] {
+        create(&app, title, &app.test_users[0]).await;
+    }
+
+    let first = list(&app, json!({ "limit": 2 })).await;
+    assert_eq!(titles(&first), vec!["Fifth", "Fourth"]);
+    assert!(first.prev_cursor.is_none());
+
+    // New articles must not shift the following pages
+    create(&app, "Sixth", &app.test_users[0]).await;
+
+    let second = list(&app, json!({ "limit": 2, "after": first.next_cursor })).await;
+    assert_eq!(titles(&second), vec!["Third", "Second"]);
+
+    let third = list(&app, json!({ "limit": 2, "after": second.next_cursor })).await;
+    assert_eq!(titles(&third), vec!["First"]);
+    assert!(third.next_cursor.is_none());
+
+    let back = list(&app, json!({ "limit": 2, "before": third.prev_cursor })).await;
+    assert_eq!(titles(&back), vec!["Third", "Second"]);
+
+    let back = list(&app, json!({ "limit": 2, "before": back.prev_cursor })).await;
+    assert_eq!(titles(&back), vec!["Fifth", "Fourth"]);
+    assert!(back.next_cursor.is_some());
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn cursor_follows_requested_order() {
+    let app = TestApp::spawn().await;
+
+    for (i, user) in app.test_users[..3].iter().enumerate() {
+        create(&app, &format!("Article {i}a"), user).await;
+        create(&app, &format!("Article {i}b"), user).await;
+    }
+
+    let order_by = json!([{ "username": "ASC" }, { "created_at": "DESC" }]);
+    let mut seen = vec![];
+    let mut cursor: Option<String> = None;
+
+    loop {
+        let page = list(
+            &app,
+            json!({ "limit": 4, "order_by": order_by, "after": cursor }),
+        )
+        .await;
+        seen.extend(page.results.iter().map(|a| a.author.username.0.clone()));
+
+        match page.next_cursor {
+            Some(next) => cursor = Some(next),
+            None => break,
+        }
+    }
+
+    let mut expected = seen.clone();
+    expected.sort();
+    assert_eq!(seen.len(), 6);
+    assert_eq!(seen, expected);
+
+    // A cursor is only valid for the ordering it was issued for
+    let page = list(&app, json!({ "limit": 4, "order_by": order_by })).await;
+    let response = Client::new()
+        .post(format!("{}/articles/get-articles", &app.address))
+        .json(&json!({ "limit": 4, "after": page.next_cursor }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn page_subscriptions_with_cursors() {
+    let app = TestApp::spawn().await;
+    app.subscribe(&app.test_users[0], &app.test_users[1].id)
+        .await;
+
+    for title in ["First", "Second", "Third"] {
+        create(&app, title, &app.test_users[1]).await;
+    }
+
+    let jwt = app.get_jwt(&app.test_users[0]).await;
+    let get_page = |query: Vec<(&'static str, String)>| {
+        let jwt = jwt.clone();
+        let address = app.address.clone();
+        async move {
+            Client::new()
+                .get(format!("{}/articles/get-subscribed", address))
+                .query(&query)
+                .header(AUTHORIZATION, jwt)
+                .send()
+                .await
+                .unwrap()
+                .json::<SearchType<Article>>()
+                .await
+                .unwrap()
+        }
+    };
+
+    let first = get_page(vec![("limit", "2".to_string())]).await;
+    assert_eq!(titles(&first), vec!["Third", "Second"]);
+
+    let after = first.next_cursor.clone().unwrap();
+    let second = get_page(vec![("limit", "2".to_string()), ("after", after)]).await;
+    assert_eq!(titles(&second), vec!["First"]);
+
+    let before = second.prev_cursor.clone().unwrap();
+    let back = get_page(vec![("limit", "2".to_string()), ("before", before)]).await;
+    assert_eq!(titles(&back), vec!["Third", "Second"]);
+
+    app.clean().await;
+}
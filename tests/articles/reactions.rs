@@ -0,0 +1,131 @@
+use lib::{
+    domains::article::ReactionKind,
+    routes::articles::{reactions::ReactionCount, Article},
+    types::SearchType,
+    utils::response::DataResponse,
+};
+use reqwest::{header::AUTHORIZATION, Client, StatusCode};
+use serde_json::json;
+
+use crate::helper::TestApp;
+
+#[tokio::test]
+async fn aggregate_reactions_per_kind() {
+    let app = TestApp::spawn().await;
+    let id = app
+        .create_article_id("Reacted article", &[], &app.test_users[0])
+        .await;
+
+    for user in &app.test_users[1..4] {
+        let response = app.react(&id, "like", user).await;
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+    app.react(&id, "laugh", &app.test_users[1]).await;
+
+    // Repeating a reaction does not count twice
+    let response = app.react(&id, "like", &app.test_users[1]).await;
+    let body: DataResponse<Vec<ReactionCount>> = response.json().await.unwrap();
+    assert_eq!(body.data[0].count, 3);
+
+    let response = app.get_article(&id).await;
+    let body: DataResponse<Article> = response.json().await.unwrap();
+    assert_eq!(
+        body.data.reactions,
+        vec![
+            ReactionCount {
+                kind: ReactionKind::Like,
+                count: 3,
+                reacted_by_me: None,
+            },
+            ReactionCount {
+                kind: ReactionKind::Laugh,
+                count: 1,
+                reacted_by_me: None,
+            },
+        ]
+    );
+
+    let response = Client::new()
+        .get(format!("{}/articles/{}", &app.address, id))
+        .header(AUTHORIZATION, app.get_jwt(&app.test_users[2]).await)
+        .send()
+        .await
+        .unwrap();
+    let body: DataResponse<Article> = response.json().await.unwrap();
+    let flags: Vec<_> = body
+        .data
+        .reactions
+        .iter()
+        .map(|r| r.reacted_by_me)
+        .collect();
+    assert_eq!(flags, vec![Some(true), Some(false)]);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn remove_reaction_and_reject_unknown_kinds() {
+    let app = TestApp::spawn().await;
+    let id = app
+        .create_article_id("Reacted article", &[], &app.test_users[0])
+        .await;
+
+    app.react(&id, "love", &app.test_users[1]).await;
+
+    let response = Client::new()
+        .delete(format!("{}/articles/{}/reactions/love", &app.address, id))
+        .header(AUTHORIZATION, app.get_jwt(&app.test_users[1]).await)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = app.get_article(&id).await;
+    let body: DataResponse<Article> = response.json().await.unwrap();
+    assert!(body.data.reactions.is_empty());
+
+    let response = app.react(&id, "shrug", &app.test_users[1]).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn order_articles_by_likes() {
+    let app = TestApp::spawn().await;
+
+    let quiet = app
+        .create_article_id("Quiet article", &[], &app.test_users[0])
+        .await;
+    let popular = app
+        .create_article_id("Popular article", &[], &app.test_users[0])
+        .await;
+    let liked = app
+        .create_article_id("Liked article", &[], &app.test_users[0])
+        .await;
+
+    for user in &app.test_users[1..4] {
+        app.react(&popular, "like", user).await;
+    }
+    app.react(&liked, "like", &app.test_users[1]).await;
+    // Other kinds do not count towards likes
+    app.react(&quiet, "love", &app.test_users[1]).await;
+    app.react(&quiet, "wow", &app.test_users[2]).await;
+
+    let response = Client::new()
+        .post(format!("{}/articles/get-articles", &app.address))
+        .json(&json!({ "order_by": [{ "most_liked": "DESC" }] }))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: SearchType<Article> = response.json().await.unwrap();
+
+    let titles: Vec<_> = body.results.iter().map(|a| a.title.as_str()).collect();
+    assert_eq!(
+        titles,
+        vec!["Popular article", "Liked article", "Quiet article"]
+    );
+
+    app.clean().await;
+}
@@ -0,0 +1,133 @@
+use lib::{
+    routes::articles::{
+        create_article,
+        revisions::{DiffKind, DiffLine, Revision, RevisionDiff},
+        Article,
+    },
+    utils::response::DataResponse,
+};
+use reqwest::{header::AUTHORIZATION, Client, StatusCode};
+use serde_json::{json, Value};
+
+use crate::helper::TestApp;
+
+async fn create_and_edit(app: &TestApp) -> String {
+    let payload = create_article::Payload {
+        title: "A unique title".to_string(),
+        text: "first line\nsecond line".to_string(),
+        tags: None,
+        ..Default::default()
+    };
+
+    let response = app.create_article(&payload, &app.test_users[0]).await;
+    let body: Value = response.json().await.unwrap();
+    let id = body["data"]["id"].as_str().unwrap().to_string();
+
+    let body = json!({ "text": "first line\nchanged line", "version": 1 });
+    let response = app.update_article(&id, &body, &app.test_users[0]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    id
+}
+
+#[tokio::test]
+async fn record_revision_for_every_edit() {
+    let app = TestApp::spawn().await;
+    let id = create_and_edit(&app).await;
+
+    let response = Client::new()
+        .get(format!("{}/articles/{}/revisions", &app.address, id))
+        .send()
+        .await
+        .unwrap();
+    let body: DataResponse<Vec<Revision>> = response.json().await.unwrap();
+
+    let versions: Vec<i32> = body.data.iter().map(|r| r.version).collect();
+    assert_eq!(versions, vec![2, 1]);
+    assert_eq!(body.data[0].editor.id, app.test_users[0].id);
+
+    let response = Client::new()
+        .get(format!("{}/articles/{}/revisions/1", &app.address, id))
+        .send()
+        .await
+        .unwrap();
+    let body: DataResponse<Revision> = response.json().await.unwrap();
+    assert_eq!(body.data.text, "first line\nsecond line");
+
+    let response = Client::new()
+        .get(format!("{}/articles/{}/revisions/7", &app.address, id))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn diff_two_revisions_line_by_line() {
+    let app = TestApp::spawn().await;
+    let id = create_and_edit(&app).await;
+
+    let response = Client::new()
+        .get(format!("{}/articles/{}/revisions/diff", &app.address, id))
+        .query(&[("from", 1), ("to", 2)])
+        .send()
+        .await
+        .unwrap();
+    let body: DataResponse<RevisionDiff> = response.json().await.unwrap();
+
+    let line = |kind, text: &str| DiffLine {
+        kind,
+        text: text.to_string(),
+    };
+
+    assert_eq!(
+        body.data.lines,
+        vec![
+            line(DiffKind::Equal, "first line"),
+            line(DiffKind::Delete, "second line"),
+            line(DiffKind::Insert, "changed line"),
+        ]
+    );
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn restore_old_revision_as_new_version() {
+    let app = TestApp::spawn().await;
+    let id = create_and_edit(&app).await;
+
+    let restore = |user, version| {
+        let app = &app;
+        let id = &id;
+        async move {
+            Client::new()
+                .post(format!(
+                    "{}/articles/{}/revisions/1/restore",
+                    &app.address, id
+                ))
+                .json(&json!({ "version": version }))
+                .header(AUTHORIZATION, app.get_jwt(user).await)
+                .send()
+                .await
+                .unwrap()
+        }
+    };
+
+    let response = restore(&app.test_users[1], 2).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = restore(&app.test_users[0], 1).await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let response = restore(&app.test_users[0], 2).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body: DataResponse<Article> = response.json().await.unwrap();
+    assert_eq!(body.data.text, "first line\nsecond line");
+    assert_eq!(body.data.version, 3);
+
+    app.clean().await;
+}
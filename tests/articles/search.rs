@@ -0,0 +1,118 @@
+use lib::{
+    routes::articles::{create_article, list::Payload, Article},
+    types::SearchType,
+};
+use reqwest::Client;
+
+use crate::helper::TestApp;
+
+async fn seed(app: &TestApp) {
+    let articles = [
+        (
+            "Rust ownership explained",
+            "Borrowing rules keep memory safe.",
+            "rust",
+        ),
+        (
+            "Gardening tips",
+            "Tomatoes need sun. Rust on leaves is a fungus.",
+            "garden",
+        ),
+        ("Cooking pasta", "Boil water and add salt.", "food"),
+    ];
+
+    for (title, text, tag) in articles {
+        let payload = create_article::Payload {
+            title: title.to_string(),
+            text: text.to_string(),
+            tags: Some(vec![tag.to_string()]),
+            ..Default::default()
+        };
+        app.create_article(&payload, &app.test_users[0]).await;
+    }
+}
+
+async fn search(app: &TestApp, q: &str) -> SearchType<Article> {
+    let body = Payload {
+        q: Some(q.to_string()),
+        ..Payload::default()
+    };
+
+    Client::new()
+        .post(format!("{}/articles/get-articles", &app.address))
+        .json(&body)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap()
+}
+
+fn titles(result: &SearchType<Article>) -> Vec<&str> {
+    result.results.iter().map(|a| a.title.as_str()).collect()
+}
+
+#[tokio::test]
+async fn rank_and_highlight_matches() {
+    let app = TestApp::spawn().await;
+    seed(&app).await;
+
+    let result = search(&app, "rust").await;
+    assert_eq!(
+        titles(&result),
+        vec!["Rust ownership explained", "Gardening tips"]
+    );
+
+    let headline = result.results[1].headline.as_deref().unwrap();
+    assert!(headline.contains("<mark>Rust</mark>"));
+
+    let result = search(&app, "rust -fungus").await;
+    assert_eq!(titles(&result), vec!["Rust ownership explained"]);
+
+    let result = search(&app, "\"memory safe\" or pasta").await;
+    assert_eq!(result.total, Some(2));
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn headline_escapes_article_text() {
+    let app = TestApp::spawn().await;
+
+    let payload = create_article::Payload {
+        title: "Unsafe markup".to_string(),
+        text: "Rust <script>alert('x')</script> and <img src=x onerror=alert(1)> Rust".to_string(),
+        ..Default::default()
+    };
+    app.create_article(&payload, &app.test_users[0]).await;
+
+    let result = search(&app, "rust").await;
+    let headline = result.results[0].headline.as_deref().unwrap();
+
+    assert!(headline.contains("<mark>Rust</mark>"));
+    assert!(headline.contains("&lt;script&gt;"));
+    assert!(!headline.contains("<script>"));
+    assert!(!headline.contains("<img"));
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn search_in_configured_language() {
+    let english = TestApp::spawn().await;
+    let simple = TestApp::spawn_with(|config| config.search.language = "simple".to_string()).await;
+
+    seed(&english).await;
+    seed(&simple).await;
+
+    // Only the english configuration stems "tomatoes" to "tomato"
+    assert_eq!(
+        titles(&search(&english, "tomato").await),
+        vec!["Gardening tips"]
+    );
+    assert!(search(&simple, "tomato").await.results.is_empty());
+
+    english.clean().await;
+    simple.clean().await;
+}
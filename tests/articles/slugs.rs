@@ -0,0 +1,190 @@
+use lib::{
+    domains::user::Username,
+    jobs::backfill_slugs,
+    routes::articles::{create_article, Article},
+    utils::response::DataResponse,
+};
+use reqwest::{header::LOCATION, redirect::Policy, Client, Response, StatusCode};
+use serde_json::{json, Value};
+use uuid::Uuid;
+
+use crate::helper::TestApp;
+
+async fn get_by_slug(app: &TestApp, author: &Username, slug: &str) -> Response {
+    Client::builder()
+        .redirect(Policy::none())
+        .build()
+        .unwrap()
+        .get(format!(
+            "{}/articles/by-slug/{}/{}",
+            &app.address, author, slug
+        ))
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn transliterate_title_into_slug() {
+    let app = TestApp::spawn().await;
+
+    let payload = create_article::Payload {
+        title: "Привет, мир! Ça va?".to_string(),
+        text: "A long new article".to_string(),
+        ..Default::default()
+    };
+
+    let response = app.create_article(&payload, &app.test_users[0]).await;
+    let body: DataResponse<Article> = response.json().await.unwrap();
+    assert_eq!(body.data.slug, "privet-mir-ca-va");
+
+    let response = get_by_slug(&app, &app.test_users[0].username, "privet-mir-ca-va").await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = get_by_slug(&app, &app.test_users[1].username, "privet-mir-ca-va").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn redirect_from_old_slug() {
+    let app = TestApp::spawn().await;
+    let username = &app.test_users[0].username;
+
+    let payload = create_article::Payload {
+        title: "First title".to_string(),
+        text: "A long new article".to_string(),
+        ..Default::default()
+    };
+
+    let response = app.create_article(&payload, &app.test_users[0]).await;
+    let body: Value = response.json().await.unwrap();
+    let id = body["data"]["id"].as_str().unwrap();
+
+    let body = json!({ "title": "Second title", "version": 1 });
+    let response = app.update_article(id, &body, &app.test_users[0]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = get_by_slug(&app, username, "first-title").await;
+    assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+    assert_eq!(
+        response.headers()[LOCATION],
+        format!("/articles/by-slug/{username}/second-title")
+    );
+
+    // The old slug stays reserved for the redirect
+    let response = app.create_article(&payload, &app.test_users[0]).await;
+    let body: DataResponse<Article> = response.json().await.unwrap();
+    assert_eq!(body.data.slug, "first-title-2");
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn encode_redirect_for_reserved_characters_in_username() {
+    let app = TestApp::spawn().await;
+    let user = &app.test_users[0];
+
+    let id = app.create_article_id("First title", &[], user).await;
+    let body = json!({ "title": "Second title", "version": 1 });
+    let response = app.update_article(&id, &body, user).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Usernames may contain any characters, including ones reserved in URLs
+    sqlx::query!(
+        "UPDATE public.users SET username = 'ann?lee ü' WHERE id = $1",
+        user.id.as_ref()
+    )
+    .execute(&app.pool)
+    .await
+    .unwrap();
+
+    let response = Client::builder()
+        .redirect(Policy::none())
+        .build()
+        .unwrap()
+        .get(format!(
+            "{}/articles/by-slug/ann%3Flee%20%C3%BC/first-title",
+            &app.address
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+    assert_eq!(
+        response.headers()[LOCATION],
+        "/articles/by-slug/ann%3Flee%20%C3%BC/second-title"
+    );
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn recompute_slugs_backfilled_by_migration() {
+    let app = TestApp::spawn().await;
+    let username = &app.test_users[0].username;
+
+    let payload = create_article::Payload {
+        title: "Привет, мир".to_string(),
+        text: "A long new article".to_string(),
+        ..Default::default()
+    };
+
+    let response = app.create_article(&payload, &app.test_users[0]).await;
+    let body: DataResponse<Article> = response.json().await.unwrap();
+    let id = Uuid::parse_str(&body.data.id).unwrap();
+
+    // What the slugs migration derived for titles without ASCII letters
+    sqlx::query!(
+        "UPDATE articles SET slug = 'article', legacy_slug = TRUE WHERE id = $1",
+        id
+    )
+    .execute(&app.pool)
+    .await
+    .unwrap();
+    sqlx::query!(
+        "INSERT INTO article_slugs (author_id, slug, article_id) VALUES ($1, 'article', $2)",
+        app.test_users[0].id.as_ref(),
+        id
+    )
+    .execute(&app.pool)
+    .await
+    .unwrap();
+
+    backfill_slugs::run(app.pool.clone()).await;
+
+    let response = get_by_slug(&app, username, "privet-mir").await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = get_by_slug(&app, username, "article").await;
+    assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn cap_long_slugs_without_skipping_words() {
+    let app = TestApp::spawn().await;
+
+    let long_word = "a".repeat(100);
+    let words = format!("intro {} end", "b".repeat(75));
+    let cases = [
+        (long_word.as_str(), "a".repeat(80)),
+        (words.as_str(), "intro".to_string()),
+    ];
+
+    for (title, expected) in cases {
+        let payload = create_article::Payload {
+            title: title.to_string(),
+            text: "A long new article".to_string(),
+            ..Default::default()
+        };
+
+        let response = app.create_article(&payload, &app.test_users[0]).await;
+        let body: DataResponse<Article> = response.json().await.unwrap();
+        assert_eq!(body.data.slug, expected);
+    }
+
+    app.clean().await;
+}
@@ -1,5 +1,7 @@
 use axum::http::StatusCode;
 use lib::domains::user::UserID;
+use lib::{routes::articles::Article, types::SearchType};
+use reqwest::{header::AUTHORIZATION, Client};
 use serde_json::Value;
 use uuid::Uuid;
 
@@ -57,3 +59,28 @@ async fn successfully_subscribed_twice() {
 
     app.clean().await;
 }
+
+#[tokio::test]
+async fn unsubscribe_from_author() {
+    let app = TestApp::spawn().await;
+    let (reader, author) = (&app.test_users[0], &app.test_users[1]);
+
+    app.subscribe(reader, &author.id).await;
+
+    for _ in 0..2 {
+        let response = Client::new()
+            .delete(format!("{}/articles/subscribe/{}", &app.address, author.id))
+            .header(AUTHORIZATION, app.get_jwt(reader).await)
+            .send()
+            .await
+            .unwrap();
+
+        assert_eq!(response.status().as_u16(), StatusCode::NO_CONTENT);
+    }
+
+    let response = app.get_subscribed(reader, None).await;
+    let body: SearchType<Article> = response.json().await.unwrap();
+    assert_eq!(body.total, Some(0));
+
+    app.clean().await;
+}
@@ -0,0 +1,87 @@
+use lib::{
+    routes::articles::{create_article, Article},
+    types::SearchType,
+};
+use reqwest::{header::AUTHORIZATION, Client};
+use serde_json::{json, Value};
+
+use crate::helper::TestApp;
+
+async fn list(app: &TestApp, body: Value) -> SearchType<Article> {
+    Client::new()
+        .post(format!("{}/articles/get-articles", &app.address))
+        .json(&body)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap()
+}
+
+async fn seed(app: &TestApp, count: usize) {
+    for i in 0..count {
+        let payload = create_article::Payload {
+            title: format!("Article {i}"),
+            text: "A long new article".to_string(),
+            ..Default::default()
+        };
+        app.create_article(&payload, &app.test_users[1]).await;
+    }
+}
+
+#[tokio::test]
+async fn count_all_matching_articles() {
+    let app = TestApp::spawn().await;
+    seed(&app, 5).await;
+
+    let page = list(&app, json!({ "limit": 2 })).await;
+    assert_eq!(page.results.len(), 2);
+    assert_eq!(page.total, Some(5));
+    assert!(page.total_is_exact);
+
+    let page = list(&app, json!({ "limit": 2, "after": page.next_cursor })).await;
+    assert_eq!(page.total, Some(5));
+
+    // Pages past the end still report the total
+    let page = list(&app, json!({ "limit": 2, "offset": 10 })).await;
+    assert!(page.results.is_empty());
+    assert_eq!(page.total, Some(5));
+
+    let jwt = app.get_jwt(&app.test_users[0]).await;
+    app.subscribe(&app.test_users[0], &app.test_users[1].id)
+        .await;
+
+    let page: SearchType<Article> = Client::new()
+        .get(format!("{}/articles/get-subscribed", &app.address))
+        .query(&[("limit", "2")])
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await
+        .unwrap()
+        .json()
+        .await
+        .unwrap();
+    assert_eq!(page.results.len(), 2);
+    assert_eq!(page.total, Some(5));
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn estimate_or_skip_count() {
+    let app = TestApp::spawn().await;
+    seed(&app, 3).await;
+
+    let page = list(&app, json!({ "limit": 2, "count": "estimated" })).await;
+    assert_eq!(page.results.len(), 2);
+    assert!(page.total.is_some());
+    assert!(!page.total_is_exact);
+
+    let page = list(&app, json!({ "limit": 2, "count": "none" })).await;
+    assert_eq!(page.results.len(), 2);
+    assert_eq!(page.total, None);
+    assert!(!page.total_is_exact);
+
+    app.clean().await;
+}
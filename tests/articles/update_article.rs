@@ -0,0 +1,91 @@
+use lib::{routes::articles::Article, utils::response::DataResponse};
+use reqwest::StatusCode;
+use serde_json::json;
+
+use crate::helper::TestApp;
+
+#[tokio::test]
+async fn author_updates_article() {
+    let app = TestApp::spawn().await;
+    let id = app
+        .create_article_id("A unique title", &["tag1"], &app.test_users[0])
+        .await;
+
+    let body = json!({ "text": "A corrected long article", "tags": ["tag2"], "version": 1 });
+    let response = app.update_article(&id, &body, &app.test_users[0]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body: DataResponse<Article> = response.json().await.unwrap();
+    assert_eq!(body.data.title, "A unique title");
+    assert_eq!(body.data.text, "A corrected long article");
+    assert_eq!(body.data.tags, vec!["tag2".to_string()]);
+    assert!(body.data.updated_at > body.data.created_at);
+    assert_eq!(body.data.version, 2);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn only_author_can_update_article() {
+    let app = TestApp::spawn().await;
+    let id = app
+        .create_article_id("A unique title", &["tag1"], &app.test_users[0])
+        .await;
+
+    let body = json!({ "title": "Hijacked title", "version": 1 });
+    let response = app.update_article(&id, &body, &app.test_users[1]).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = app
+        .update_article(&uuid::Uuid::new_v4().to_string(), &body, &app.test_users[0])
+        .await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn retitle_article_to_existing_title() {
+    let app = TestApp::spawn().await;
+    app.create_article_id("A unique title", &["tag1"], &app.test_users[0])
+        .await;
+    let id = app
+        .create_article_id("Another title", &["tag1"], &app.test_users[0])
+        .await;
+
+    let body = json!({ "title": "A unique title", "version": 1 });
+    let response = app.update_article(&id, &body, &app.test_users[0]).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body: DataResponse<Article> = response.json().await.unwrap();
+    assert_eq!(body.data.slug, "a-unique-title-2");
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn reject_update_based_on_stale_version() {
+    let app = TestApp::spawn().await;
+    let id = app
+        .create_article_id("A unique title", &["tag1"], &app.test_users[0])
+        .await;
+
+    let first_tab = json!({ "text": "Edited in the first tab", "version": 1 });
+    let second_tab = json!({ "text": "Edited in the second tab", "version": 1 });
+
+    let response = app
+        .update_article(&id, &first_tab, &app.test_users[0])
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = app
+        .update_article(&id, &second_tab, &app.test_users[0])
+        .await;
+    assert_eq!(response.status(), StatusCode::CONFLICT);
+
+    let response = app.get_article(&id).await;
+    let body: DataResponse<Article> = response.json().await.unwrap();
+    assert_eq!(body.data.text, "Edited in the first tab");
+
+    app.clean().await;
+}
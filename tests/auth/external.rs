@@ -0,0 +1,262 @@
+use lib::utils::{err::AppError, jwt::UserData, response::DataResponse};
+use reqwest::{
+    header::{AUTHORIZATION, LOCATION},
+    redirect::Policy,
+    Client, Response, StatusCode, Url,
+};
+use serde_json::{json, Value};
+
+use crate::helper::{MockIdp, TestApp, CLIENT_ID};
+
+async fn spawn_app() -> (TestApp, MockIdp) {
+    let idp = MockIdp::spawn().await;
+    let provider = idp.provider_config();
+
+    let app = TestApp::spawn_with(|config| {
+        config.oidc.providers.insert("mock".to_string(), provider);
+    })
+    .await;
+
+    (app, idp)
+}
+
+async fn start(app: &TestApp) -> Url {
+    let response = Client::builder()
+        .redirect(Policy::none())
+        .build()
+        .unwrap()
+        .get(format!("{}/auth/external/mock/start", &app.address))
+        .send()
+        .await
+        .unwrap();
+
+    assert!(response.status().is_redirection());
+
+    let location = response.headers().get(LOCATION).unwrap().to_str().unwrap();
+    Url::parse(location).unwrap()
+}
+
+async fn callback(app: &TestApp, state: &str, code: &str) -> Response {
+    Client::new()
+        .get(format!("{}/auth/external/mock/callback", &app.address))
+        .query(&[("state", state), ("code", code)])
+        .send()
+        .await
+        .unwrap()
+}
+
+async fn login_with(app: &TestApp, idp: &MockIdp, claims: Value) -> Response {
+    let url = start(app).await;
+    let code = idp.issue_code(&url, claims);
+
+    callback(app, &query_param(&url, "state"), &code).await
+}
+
+async fn logged_in_user(app: &TestApp, response: Response) -> UserData {
+    assert_eq!(response.status().as_u16(), StatusCode::OK);
+    let jwt = response.headers().get(AUTHORIZATION).unwrap().to_owned();
+
+    let response = Client::new()
+        .get(format!("{}/auth/me", &app.address))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await
+        .unwrap();
+
+    let body: DataResponse<UserData> = response.json().await.unwrap();
+    body.data
+}
+
+fn query_param(url: &Url, name: &str) -> String {
+    url.query_pairs()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.into_owned())
+        .unwrap()
+}
+
+#[tokio::test]
+async fn start_redirects_with_state_nonce_and_pkce() {
+    let (app, _idp) = spawn_app().await;
+    let url = start(&app).await;
+
+    assert_eq!(url.path(), "/authorize");
+    assert_eq!(query_param(&url, "client_id"), CLIENT_ID);
+    assert_eq!(query_param(&url, "code_challenge_method"), "S256");
+
+    for param in ["state", "nonce", "code_challenge"] {
+        assert!(!query_param(&url, param).is_empty());
+    }
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn first_login_creates_user_and_next_login_reuses_it() {
+    let (app, idp) = spawn_app().await;
+
+    let claims = json!({
+        "sub": "subject-1",
+        "email": "new.user@mail.com",
+        "email_verified": true,
+        "preferred_username": "new.user"
+    });
+
+    let response = login_with(&app, &idp, claims.clone()).await;
+    let first = logged_in_user(&app, response).await;
+
+    let response = login_with(&app, &idp, claims).await;
+    let second = logged_in_user(&app, response).await;
+
+    assert_eq!(first.user_id, second.user_id);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn derive_usernames_that_pass_login_validation() {
+    let (app, idp) = spawn_app().await;
+
+    let claims = json!({
+        "sub": "subject-5",
+        "email": "long.name@mail.com",
+        "email_verified": true,
+        "preferred_username": "ü".repeat(60)
+    });
+
+    let response = login_with(&app, &idp, claims).await;
+    let user = logged_in_user(&app, response).await;
+
+    let username: String = sqlx::query_scalar("SELECT username FROM users WHERE id = $1")
+        .bind(user.user_id.as_ref())
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    assert!(username.len() <= 50, "{username}");
+    assert!(username.chars().all(|c| c == 'ü'), "{username}");
+
+    let response = app
+        .login(&json!({ "username": username, "password": "not-the-password" }))
+        .await;
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(
+        json["message"].as_str().unwrap(),
+        AppError::InvalidCredentials.to_string()
+    );
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn links_existing_account_with_verified_email() {
+    let (app, idp) = spawn_app().await;
+    sqlx::query("UPDATE users SET email_verified = TRUE WHERE id = $1")
+        .bind(app.test_users[0].id.as_ref())
+        .execute(&app.pool)
+        .await
+        .unwrap();
+
+    let claims = json!({
+        "sub": "subject-2",
+        "email": &app.test_users[0].email,
+        "email_verified": "true"
+    });
+
+    let response = login_with(&app, &idp, claims).await;
+    let user = logged_in_user(&app, response).await;
+
+    assert_eq!(user.user_id, app.test_users[0].id);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn refuse_to_link_account_registered_with_same_email() {
+    let (app, idp) = spawn_app().await;
+
+    // Anyone can register an address they do not own
+    let response = app
+        .register(&json!({
+            "username": "squatter",
+            "email": "victim@corp.com",
+            "password": "squatter-password"
+        }))
+        .await;
+    assert_eq!(response.status().as_u16(), StatusCode::OK);
+
+    let claims = json!({
+        "sub": "victim-subject",
+        "email": "victim@corp.com",
+        "email_verified": true
+    });
+
+    let response = login_with(&app, &idp, claims.clone()).await;
+    assert_eq!(response.status().as_u16(), StatusCode::CONFLICT);
+    assert!(response.headers().get(AUTHORIZATION).is_none());
+
+    let linked: i64 = sqlx::query_scalar("SELECT COUNT(*) FROM identities")
+        .fetch_one(&app.pool)
+        .await
+        .unwrap();
+    assert_eq!(linked, 0);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn reject_unverified_email() {
+    let (app, idp) = spawn_app().await;
+
+    let claims = json!({
+        "sub": "subject-3",
+        "email": &app.test_users[0].email,
+        "email_verified": false
+    });
+
+    let response = login_with(&app, &idp, claims).await;
+    assert_eq!(response.status().as_u16(), StatusCode::BAD_REQUEST);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn reject_nonce_mismatch_and_replayed_state() {
+    let (app, idp) = spawn_app().await;
+
+    let url = start(&app).await;
+    let state = query_param(&url, "state");
+
+    let code = idp.issue_code(
+        &url,
+        json!({ "sub": "subject-4", "nonce": "forged", "email_verified": true }),
+    );
+
+    let response = callback(&app, &state, &code).await;
+    assert_eq!(response.status().as_u16(), StatusCode::UNAUTHORIZED);
+
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(
+        json["message"].as_str().unwrap(),
+        AppError::ExternalAuthFailed.to_string()
+    );
+
+    let code = idp.issue_code(&url, json!({ "sub": "subject-4" }));
+    let response = callback(&app, &state, &code).await;
+    assert_eq!(response.status().as_u16(), StatusCode::UNAUTHORIZED);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn return_404_for_unknown_provider() {
+    let (app, _idp) = spawn_app().await;
+
+    let response = Client::new()
+        .get(format!("{}/auth/external/unknown/start", &app.address))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), StatusCode::NOT_FOUND);
+
+    app.clean().await;
+}
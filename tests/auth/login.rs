@@ -18,7 +18,7 @@ async fn login_failed_on_invalid_json_data() {
         "password": "invalid_password"
     });
 
-    for body in vec![invalid_username, invalid_password] {
+    for body in [invalid_username, invalid_password] {
         let response = app.login(&body).await;
         assert_eq!(response.status().as_u16(), 400);
 
@@ -45,7 +45,7 @@ async fn success_login_returns_header_token() {
     let response = app.login(&body).await;
 
     assert_eq!(response.status().as_u16(), 200);
-    assert!(response.headers().get(AUTHORIZATION).unwrap().len() > 0);
+    assert!(!response.headers().get(AUTHORIZATION).unwrap().is_empty());
 
     app.clean().await;
 }
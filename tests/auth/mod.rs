@@ -1,2 +1,5 @@
+mod external;
 mod login;
 mod register;
+mod token;
+mod webauthn;
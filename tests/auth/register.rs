@@ -1,7 +1,11 @@
 use crate::helper::TestApp;
 
 use claims::assert_ok;
-use lib::utils::{err::AppError, response::DataResponse};
+use lib::{
+    configuration::RegistrationMode,
+    routes::admin::invites::InviteCode,
+    utils::{err::AppError, response::DataResponse},
+};
 use reqwest::Response;
 use serde_json::{json, Value};
 use uuid::Uuid;
@@ -81,7 +85,7 @@ async fn return_400_on_existing_user() {
         "email": "another_email@mail.com"
     });
 
-    for body in vec![duplicated_username_body, duplicated_email_body] {
+    for body in [duplicated_username_body, duplicated_email_body] {
         let response = make_request(&body, &app.address).await;
         assert_eq!(response.status().as_u16(), 400);
 
@@ -115,3 +119,118 @@ async fn make_request(body: &Value, address: &str) -> Response {
         .await
         .unwrap()
 }
+
+#[tokio::test]
+async fn return_403_when_registration_is_closed() {
+    let app = TestApp::spawn_with(|config| {
+        config.app.registration_mode = RegistrationMode::Closed;
+    })
+    .await;
+
+    let response = app.register(&valid_user_body(None)).await;
+    assert_eq!(response.status().as_u16(), 403);
+
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(
+        json["message"].as_str().unwrap(),
+        &AppError::RegistrationClosed.to_string()
+    );
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn return_400_on_missing_or_unknown_invite_code() {
+    let app = TestApp::spawn_with(|config| {
+        config.app.registration_mode = RegistrationMode::InviteOnly;
+    })
+    .await;
+
+    for invite_code in [None, Some("unknown_code")] {
+        let response = app.register(&valid_user_body(invite_code)).await;
+        assert_eq!(response.status().as_u16(), 400);
+
+        let json: Value = response.json().await.unwrap();
+        assert_eq!(
+            json["message"].as_str().unwrap(),
+            &AppError::InvalidInviteCode.to_string()
+        );
+    }
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn invite_code_is_consumed_up_to_max_uses() {
+    let app = TestApp::spawn_with(|config| {
+        config.app.registration_mode = RegistrationMode::InviteOnly;
+    })
+    .await;
+
+    app.make_admin(&app.test_users[0]).await;
+
+    let response = app
+        .create_invite(&app.test_users[0], &json!({ "max_uses": 1 }))
+        .await;
+
+    let invite: DataResponse<InviteCode> = response.json().await.unwrap();
+
+    let response = app
+        .register(&valid_user_body(Some(&invite.data.code)))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    let body = json!({
+        "username": "another_username",
+        "password": "pass12359823",
+        "email": "another_email@mail.com",
+        "invite_code": &invite.data.code
+    });
+
+    let response = app.register(&body).await;
+    assert_eq!(response.status().as_u16(), 400);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn failed_registration_does_not_consume_invite_code() {
+    let app = TestApp::spawn_with(|config| {
+        config.app.registration_mode = RegistrationMode::InviteOnly;
+    })
+    .await;
+
+    app.make_admin(&app.test_users[0]).await;
+
+    let response = app
+        .create_invite(&app.test_users[0], &json!({ "max_uses": 1 }))
+        .await;
+
+    let invite: DataResponse<InviteCode> = response.json().await.unwrap();
+
+    let duplicated_user = json!({
+        "username": &app.test_users[1].username,
+        "password": "pass12359823",
+        "email": "user@mail.com",
+        "invite_code": &invite.data.code
+    });
+
+    let response = app.register(&duplicated_user).await;
+    assert_eq!(response.status().as_u16(), 400);
+
+    let response = app
+        .register(&valid_user_body(Some(&invite.data.code)))
+        .await;
+    assert_eq!(response.status().as_u16(), 200);
+
+    app.clean().await;
+}
+
+fn valid_user_body(invite_code: Option<&str>) -> Value {
+    json!({
+        "username": "username",
+        "password": "pass12359823",
+        "email": "user@mail.com",
+        "invite_code": invite_code
+    })
+}
@@ -0,0 +1,142 @@
+use std::time::Duration;
+
+use jsonwebtoken::{DecodingKey, Validation};
+use lib::{
+    configuration::{self, JwtConfig},
+    utils::jwt::TokenService,
+};
+use reqwest::{header::HeaderValue, header::AUTHORIZATION, Client, StatusCode};
+use serde_json::Value;
+
+use crate::helper::TestApp;
+
+async fn me_status(app: &TestApp, jwt: &HeaderValue) -> u16 {
+    Client::new()
+        .get(format!("{}/auth/me", &app.address))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await
+        .unwrap()
+        .status()
+        .as_u16()
+}
+
+#[tokio::test]
+async fn token_contains_registered_claims() {
+    let app = TestApp::spawn_with(|config| {
+        config.jwt.issuer = "test-issuer".to_string();
+        config.jwt.audience = "test-audience".to_string();
+        config.jwt.ttl_seconds = 600;
+    })
+    .await;
+
+    let jwt = app.get_jwt(&app.test_users[0]).await;
+
+    let mut validation = Validation::default();
+    validation.insecure_disable_signature_validation();
+    validation.validate_aud = false;
+
+    let claims = jsonwebtoken::decode::<Value>(
+        jwt.to_str().unwrap(),
+        &DecodingKey::from_secret(&[]),
+        &validation,
+    )
+    .unwrap()
+    .claims;
+
+    assert_eq!(claims["sub"], app.test_users[0].id.to_string());
+    assert_eq!(claims["iss"], "test-issuer");
+    assert_eq!(claims["aud"], "test-audience");
+    assert!(claims["jti"].as_str().is_some());
+
+    let iat = claims["iat"].as_u64().unwrap();
+    assert_eq!(claims["nbf"].as_u64().unwrap(), iat);
+    assert_eq!(claims["exp"].as_u64().unwrap(), iat + 600);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn reject_token_from_another_environment() {
+    let production = TestApp::spawn().await;
+    let staging = TestApp::spawn_with(|config| {
+        config.jwt.issuer = "staging".to_string();
+        config.jwt.audience = "staging".to_string();
+    })
+    .await;
+
+    let jwt = staging.get_jwt(&staging.test_users[0]).await;
+
+    assert_eq!(me_status(&staging, &jwt).await, StatusCode::OK);
+    assert_eq!(me_status(&production, &jwt).await, StatusCode::UNAUTHORIZED);
+
+    production.clean().await;
+    staging.clean().await;
+}
+
+#[tokio::test]
+async fn reject_expired_token() {
+    let app = TestApp::spawn_with(|config| {
+        config.jwt.ttl_seconds = 1;
+        config.jwt.leeway_seconds = 0;
+    })
+    .await;
+
+    let jwt = app.get_jwt(&app.test_users[0]).await;
+    tokio::time::sleep(Duration::from_secs(2)).await;
+
+    assert_eq!(me_status(&app, &jwt).await, StatusCode::UNAUTHORIZED);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn accept_token_signed_with_previous_secret() {
+    let old_secret = "an-old-secret-that-is-long-enough-to-use".to_string();
+    let new_secret = "a-new-secret-that-is-long-enough-to-use".to_string();
+
+    let old = TokenService::new(&JwtConfig {
+        secret: Some(old_secret.clone()),
+        ..configuration::parse_config().jwt
+    })
+    .unwrap();
+    // Signed for a user of the given app, which checks that its users are still active
+    let old_jwt = |app: &TestApp| {
+        HeaderValue::from_str(&old.sign(app.test_users[0].id.clone()).unwrap()).unwrap()
+    };
+
+    let rotated = TestApp::spawn_with(|config| {
+        config.jwt.secret = Some(new_secret.clone());
+        config.jwt.previous_secrets = vec![old_secret.clone()];
+    })
+    .await;
+    let retired = TestApp::spawn_with(|config| config.jwt.secret = Some(new_secret.clone())).await;
+
+    assert_eq!(
+        me_status(&rotated, &old_jwt(&rotated)).await,
+        StatusCode::OK
+    );
+    assert_eq!(
+        me_status(&retired, &old_jwt(&retired)).await,
+        StatusCode::UNAUTHORIZED
+    );
+
+    rotated.clean().await;
+    retired.clean().await;
+}
+
+#[test]
+fn reject_short_secrets() {
+    let config = JwtConfig {
+        secret: Some("too-short".to_string()),
+        ..Default::default()
+    };
+    assert!(TokenService::new(&config).is_err());
+
+    let config = JwtConfig {
+        secret: Some("a-secret-that-is-long-enough-to-use".to_string()),
+        previous_secrets: vec!["too-short".to_string()],
+        ..Default::default()
+    };
+    assert!(TokenService::new(&config).is_err());
+}
@@ -0,0 +1,239 @@
+use lib::{
+    routes::auth::webauthn::ChallengeResponse,
+    utils::{err::AppError, jwt::UserData, response::DataResponse},
+};
+use reqwest::{header::AUTHORIZATION, Client, Response, StatusCode};
+use serde_json::{json, Value};
+
+use crate::helper::{SoftAuthenticator, TestApp, TestUser};
+
+const ORIGIN: &str = "http://localhost:3000";
+
+async fn spawn_app() -> TestApp {
+    TestApp::spawn_with(|config| {
+        config.webauthn.rp_id = "localhost".to_string();
+        config.webauthn.origin = ORIGIN.to_string();
+    })
+    .await
+}
+
+async fn post(app: &TestApp, path: &str, body: &Value, user: Option<&TestUser>) -> Response {
+    let mut request = Client::new()
+        .post(format!("{}{}", &app.address, path))
+        .json(body);
+
+    if let Some(user) = user {
+        request = request.header(AUTHORIZATION, app.get_jwt(user).await);
+    }
+
+    request.send().await.unwrap()
+}
+
+async fn register_passkey(
+    app: &TestApp,
+    user: &TestUser,
+    authenticator: &mut SoftAuthenticator,
+) -> Response {
+    let response = post(app, "/auth/webauthn/register/start", &json!({}), Some(user)).await;
+    assert_eq!(response.status().as_u16(), StatusCode::OK);
+
+    let options: DataResponse<ChallengeResponse> = response.json().await.unwrap();
+    let credential = authenticator.register(&options.data.public_key);
+
+    let body = json!({
+        "challenge_id": options.data.challenge_id,
+        "name": "Laptop",
+        "credential": credential
+    });
+
+    post(app, "/auth/webauthn/register/finish", &body, Some(user)).await
+}
+
+async fn finish_login(
+    app: &TestApp,
+    challenge: &ChallengeResponse,
+    authenticator: &mut SoftAuthenticator,
+) -> Response {
+    let body = json!({
+        "challenge_id": challenge.challenge_id,
+        "credential": authenticator.authenticate(&challenge.public_key)
+    });
+
+    post(app, "/auth/webauthn/login/finish", &body, None).await
+}
+
+async fn passwordless_challenge(app: &TestApp, user: &TestUser) -> ChallengeResponse {
+    let body = json!({ "username": &user.username });
+    let response = post(app, "/auth/webauthn/login/start", &body, None).await;
+    let options: DataResponse<ChallengeResponse> = response.json().await.unwrap();
+
+    options.data
+}
+
+async fn me(app: &TestApp, response: &Response) -> UserData {
+    let jwt = response.headers().get(AUTHORIZATION).unwrap();
+
+    let response = Client::new()
+        .get(format!("{}/auth/me", &app.address))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await
+        .unwrap();
+
+    let body: DataResponse<UserData> = response.json().await.unwrap();
+    body.data
+}
+
+#[tokio::test]
+async fn register_and_login_without_password() {
+    let app = spawn_app().await;
+    let user = &app.test_users[0];
+    let mut authenticator = SoftAuthenticator::new(ORIGIN);
+
+    let response = register_passkey(&app, user, &mut authenticator).await;
+    assert_eq!(response.status().as_u16(), StatusCode::CREATED);
+
+    let challenge = passwordless_challenge(&app, user).await;
+    assert_eq!(
+        challenge.public_key["allowCredentials"]
+            .as_array()
+            .unwrap()
+            .len(),
+        1
+    );
+
+    let response = finish_login(&app, &challenge, &mut authenticator).await;
+    assert_eq!(response.status().as_u16(), StatusCode::OK);
+    assert_eq!(me(&app, &response).await.user_id, user.id);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn reject_registration_from_another_origin() {
+    let app = spawn_app().await;
+    let mut authenticator = SoftAuthenticator::new("https://evil.example.com");
+
+    let response = register_passkey(&app, &app.test_users[0], &mut authenticator).await;
+    assert_eq!(response.status().as_u16(), StatusCode::UNAUTHORIZED);
+
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(
+        json["message"].as_str().unwrap(),
+        AppError::WebAuthnFailed.to_string()
+    );
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn reject_reused_challenge_and_cloned_authenticator() {
+    let app = spawn_app().await;
+    let user = &app.test_users[0];
+    let mut authenticator = SoftAuthenticator::new(ORIGIN);
+
+    register_passkey(&app, user, &mut authenticator).await;
+
+    let challenge = passwordless_challenge(&app, user).await;
+    let response = finish_login(&app, &challenge, &mut authenticator).await;
+    assert_eq!(response.status().as_u16(), StatusCode::OK);
+
+    let response = finish_login(&app, &challenge, &mut authenticator).await;
+    assert_eq!(response.status().as_u16(), StatusCode::UNAUTHORIZED);
+
+    // A clone that lags behind reuses an old signature counter
+    authenticator.sign_count -= 2;
+    let challenge = passwordless_challenge(&app, user).await;
+    let response = finish_login(&app, &challenge, &mut authenticator).await;
+    assert_eq!(response.status().as_u16(), StatusCode::UNAUTHORIZED);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn reject_deactivated_user_without_touching_credential() {
+    let app = spawn_app().await;
+    let user = &app.test_users[0];
+    let mut authenticator = SoftAuthenticator::new(ORIGIN);
+
+    register_passkey(&app, user, &mut authenticator).await;
+    let challenge = passwordless_challenge(&app, user).await;
+
+    sqlx::query!(
+        "UPDATE public.users SET active = FALSE WHERE id = $1",
+        user.id.as_ref()
+    )
+    .execute(&app.pool)
+    .await
+    .unwrap();
+
+    let usage = || {
+        sqlx::query!(
+            "SELECT sign_count, last_used_at FROM webauthn_credentials WHERE user_id = $1",
+            user.id.as_ref()
+        )
+        .fetch_one(&app.pool)
+    };
+    let before = usage().await.unwrap();
+
+    let response = finish_login(&app, &challenge, &mut authenticator).await;
+    assert_eq!(response.status().as_u16(), StatusCode::BAD_REQUEST);
+    assert!(response.headers().get(AUTHORIZATION).is_none());
+
+    let after = usage().await.unwrap();
+    assert_eq!(after.sign_count, before.sign_count);
+    assert_eq!(after.last_used_at, before.last_used_at);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn passkey_as_second_factor() {
+    let app = spawn_app().await;
+    let user = &app.test_users[0];
+    let mut authenticator = SoftAuthenticator::new(ORIGIN);
+
+    register_passkey(&app, user, &mut authenticator).await;
+
+    let response = Client::new()
+        .put(format!("{}/auth/webauthn/second-factor", &app.address))
+        .header(AUTHORIZATION, app.get_jwt(user).await)
+        .json(&json!({ "enabled": true }))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), StatusCode::NO_CONTENT);
+
+    let response = app
+        .login(&json!({ "username": &user.username, "password": &user.password }))
+        .await;
+
+    assert_eq!(response.status().as_u16(), StatusCode::OK);
+    assert!(response.headers().get(AUTHORIZATION).is_none());
+
+    let challenge: DataResponse<ChallengeResponse> = response.json().await.unwrap();
+    let response = finish_login(&app, &challenge.data, &mut authenticator).await;
+
+    assert_eq!(response.status().as_u16(), StatusCode::OK);
+    assert_eq!(me(&app, &response).await.user_id, user.id);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn fail_to_enable_second_factor_without_passkey() {
+    let app = spawn_app().await;
+
+    let response = Client::new()
+        .put(format!("{}/auth/webauthn/second-factor", &app.address))
+        .header(AUTHORIZATION, app.get_jwt(&app.test_users[0]).await)
+        .json(&json!({ "enabled": true }))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), StatusCode::BAD_REQUEST);
+
+    app.clean().await;
+}
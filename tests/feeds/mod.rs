@@ -0,0 +1,2 @@
+mod site_feeds;
+mod subscribed_feed;
@@ -0,0 +1,171 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use lib::{domains::article::ArticleStatus, routes::articles::create_article};
+use reqwest::{
+    header::{CONTENT_TYPE, ETAG, IF_MODIFIED_SINCE, IF_NONE_MATCH, LAST_MODIFIED},
+    Client, StatusCode,
+};
+
+use crate::helper::{TestApp, TestUser};
+
+async fn create(app: &TestApp, title: &str, tags: &[&str], user: &TestUser) {
+    let payload = create_article::Payload {
+        title: title.to_string(),
+        text: "Feed *entry* & more".to_string(),
+        tags: (!tags.is_empty()).then(|| tags.iter().map(|tag| tag.to_string()).collect()),
+        ..Default::default()
+    };
+
+    app.create_article(&payload, user).await;
+}
+
+async fn get_feed(app: &TestApp, path: &str) -> reqwest::Response {
+    Client::new()
+        .get(format!("{}{}", &app.address, path))
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn site_feed_lists_published_articles() {
+    let app = TestApp::spawn().await;
+    let alice = &app.test_users[0];
+    create(&app, "First <entry>", &["rust"], alice).await;
+    create(&app, "Second entry", &[], alice).await;
+
+    let draft = create_article::Payload {
+        title: "Unfinished draft".to_string(),
+        text: "Not ready for readers".to_string(),
+        status: lib::domains::article::ArticleStatus::Draft,
+        ..Default::default()
+    };
+    app.create_article(&draft, alice).await;
+
+    let response = get_feed(&app, "/feeds/articles.atom").await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(
+        response.headers()[CONTENT_TYPE],
+        "application/atom+xml; charset=utf-8"
+    );
+    let feed = response.text().await.unwrap();
+
+    assert!(feed.starts_with("<?xml"));
+    assert!(feed.contains("<title>First &lt;entry&gt;</title>"));
+    assert!(feed.contains("<title>Second entry</title>"));
+    assert!(!feed.contains("Unfinished draft"));
+    assert!(feed.contains("&lt;em&gt;entry&lt;/em&gt; &amp;amp; more"));
+    assert!(feed.contains(&format!(
+        "href=\"http://localhost:3000/authors/{}/second-entry\"",
+        alice.username
+    )));
+    assert!(feed.find("Second entry").unwrap() < feed.find("First &lt;entry").unwrap());
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn author_and_tag_feeds_filter_articles() {
+    let app = TestApp::spawn().await;
+    let (alice, bob) = (&app.test_users[0], &app.test_users[1]);
+    create(&app, "Rust by Alice", &["rust"], alice).await;
+    create(&app, "Food by Alice", &["food"], alice).await;
+    create(&app, "Rust by Bob", &["rust"], bob).await;
+
+    let path = format!("/feeds/authors/{}.atom", alice.username);
+    let feed = get_feed(&app, &path).await.text().await.unwrap();
+    assert!(feed.contains("Rust by Alice"));
+    assert!(feed.contains("Food by Alice"));
+    assert!(!feed.contains("Rust by Bob"));
+
+    let response = get_feed(&app, "/feeds/tags/rust.rss").await;
+    assert_eq!(
+        response.headers()[CONTENT_TYPE],
+        "application/rss+xml; charset=utf-8"
+    );
+    let feed = response.text().await.unwrap();
+    assert!(feed.contains("<rss version=\"2.0\""));
+    assert!(feed.contains("Rust by Alice"));
+    assert!(feed.contains("Rust by Bob"));
+    assert!(!feed.contains("Food by Alice"));
+
+    let response = get_feed(&app, "/feeds/authors/nobody_here.atom").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = get_feed(&app, "/feeds/tags/rust.xml").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn answer_conditional_requests_with_not_modified() {
+    let app = TestApp::spawn().await;
+    create(&app, "Cached entry", &[], &app.test_users[0]).await;
+
+    let response = get_feed(&app, "/feeds/articles.atom").await;
+    let etag = response.headers()[ETAG].clone();
+    let last_modified = response.headers()[LAST_MODIFIED].clone();
+
+    let response = Client::new()
+        .get(format!("{}/feeds/articles.atom", &app.address))
+        .header(IF_NONE_MATCH, etag.clone())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+    assert_eq!(response.headers()[ETAG], etag);
+
+    // Removed entries do not move Last-Modified, so only the ETag can confirm the body
+    let response = Client::new()
+        .get(format!("{}/feeds/articles.atom", &app.address))
+        .header(IF_MODIFIED_SINCE, last_modified.clone())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // A new article changes both validators
+    create(&app, "Newer entry", &[], &app.test_users[1]).await;
+
+    let response = Client::new()
+        .get(format!("{}/feeds/articles.atom", &app.address))
+        .header(IF_NONE_MATCH, etag.clone())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_ne!(response.headers()[ETAG], etag);
+    assert!(response.text().await.unwrap().contains("Newer entry"));
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn publishing_scheduled_article_moves_feed_updated() {
+    let app = TestApp::spawn_with(|config| config.jobs.publish_interval_seconds = 1).await;
+    let alice = &app.test_users[0];
+    create(&app, "Earlier entry", &[], alice).await;
+
+    let scheduled = create_article::Payload {
+        title: "Scheduled entry".to_string(),
+        text: "Published a bit later".to_string(),
+        status: ArticleStatus::Scheduled,
+        published_at: Some(Utc::now().naive_utc() + chrono::Duration::seconds(2)),
+        ..Default::default()
+    };
+    app.create_article(&scheduled, alice).await;
+
+    let response = get_feed(&app, "/feeds/articles.atom").await;
+    let last_modified = response.headers()[LAST_MODIFIED].clone();
+    assert!(!response.text().await.unwrap().contains("Scheduled entry"));
+
+    tokio::time::sleep(Duration::from_secs(4)).await;
+
+    let response = get_feed(&app, "/feeds/articles.atom").await;
+    assert_ne!(response.headers()[LAST_MODIFIED], last_modified);
+    assert!(response.text().await.unwrap().contains("Scheduled entry"));
+
+    app.clean().await;
+}
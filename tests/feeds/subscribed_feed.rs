@@ -0,0 +1,118 @@
+use lib::routes::{articles::create_article, feeds::tokens::FeedToken};
+use reqwest::{header::AUTHORIZATION, Client, StatusCode};
+
+use crate::helper::{TestApp, TestUser};
+
+async fn regenerate_token(app: &TestApp, user: &TestUser) -> FeedToken {
+    let response = Client::new()
+        .post(format!("{}/me/feed-token", &app.address))
+        .header(AUTHORIZATION, app.get_jwt(user).await)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    let body: serde_json::Value = response.json().await.unwrap();
+    serde_json::from_value(body["data"].clone()).unwrap()
+}
+
+async fn get_feed(app: &TestApp, token: &str) -> reqwest::Response {
+    Client::new()
+        .get(format!("{}/feeds/subscribed/{token}.atom", &app.address))
+        .send()
+        .await
+        .unwrap()
+}
+
+async fn create(app: &TestApp, title: &str, user: &TestUser) {
+    let payload = create_article::Payload {
+        title: title.to_string(),
+        text: "Article for subscribers".to_string(),
+        ..Default::default()
+    };
+
+    app.create_article(&payload, user).await;
+}
+
+#[tokio::test]
+async fn private_feed_lists_subscribed_articles() {
+    let app = TestApp::spawn().await;
+    let [reader, followed, other] = [&app.test_users[0], &app.test_users[1], &app.test_users[2]];
+    app.subscribe(reader, &followed.id).await;
+    create(&app, "Followed article", followed).await;
+    create(&app, "Other article", other).await;
+
+    let token = regenerate_token(&app, reader).await;
+    assert_eq!(
+        token.url,
+        format!(
+            "http://localhost:3000/feeds/subscribed/{}.atom",
+            token.token
+        )
+    );
+
+    let response = get_feed(&app, &token.token).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let feed = response.text().await.unwrap();
+    assert!(feed.contains("Followed article"));
+    assert!(!feed.contains("Other article"));
+
+    let response = get_feed(&app, "not-a-token").await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn regenerate_and_revoke_feed_token() {
+    let app = TestApp::spawn().await;
+    let reader = &app.test_users[0];
+    let jwt = app.get_jwt(reader).await;
+
+    let response = Client::new()
+        .get(format!("{}/me/feed-token", &app.address))
+        .header(AUTHORIZATION, jwt.clone())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let first = regenerate_token(&app, reader).await;
+    let second = regenerate_token(&app, reader).await;
+    assert_ne!(first.token, second.token);
+
+    // Regenerating invalidates the previous URL
+    let response = get_feed(&app, &first.token).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    let response = get_feed(&app, &second.token).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = Client::new()
+        .get(format!("{}/me/feed-token", &app.address))
+        .header(AUTHORIZATION, jwt.clone())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = Client::new()
+        .delete(format!("{}/me/feed-token", &app.address))
+        .header(AUTHORIZATION, jwt.clone())
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+    let response = get_feed(&app, &second.token).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = Client::new()
+        .delete(format!("{}/me/feed-token", &app.address))
+        .header(AUTHORIZATION, jwt)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    app.clean().await;
+}
@@ -1,15 +1,18 @@
-use std::cell::RefCell;
+use std::{io::Cursor, path::PathBuf};
 
 use dotenv::dotenv;
+use image::{ImageFormat, Rgb, RgbImage};
 use lib::{
     application::App,
-    configuration::{self, AppConfig, Configuration, DBConfig},
+    configuration::{self, Configuration, StorageBackend},
     domains::user::UserID,
     routes::articles::create_article,
 };
 use reqwest::{
-    header::{HeaderValue, AUTHORIZATION},
-    Client, Response,
+    header::{HeaderValue, AUTHORIZATION, COOKIE, ORIGIN, SET_COOKIE},
+    multipart::{Form, Part},
+    redirect::Policy,
+    Client, Response, StatusCode,
 };
 use serde_json::{json, Value};
 use sqlx::{Executor, PgConnection, PgPool};
@@ -21,10 +24,12 @@ use super::{create_db, create_test_user, TestUser};
 
 pub struct TestApp {
     pub address: String,
+    pub public_url: String,
     pub pool: PgPool,
     pub test_users: Vec<TestUser>,
-    connection: RefCell<PgConnection>,
+    connection: PgConnection,
     db_name: String,
+    uploads_dir: PathBuf,
 }
 
 async fn init_tracing() {
@@ -41,25 +46,29 @@ static TRACING: OnceCell<()> = OnceCell::const_new();
 
 impl TestApp {
     pub async fn spawn() -> TestApp {
+        Self::spawn_with(|_| {}).await
+    }
+
+    pub async fn spawn_with(configure: impl FnOnce(&mut Configuration)) -> TestApp {
         TRACING.get_or_init(init_tracing).await;
 
         dotenv().unwrap();
-        let config = configuration::parse_config();
+        let mut config = configuration::parse_config();
         let db_name = Uuid::new_v4().to_string();
 
-        let config = Configuration {
-            app: AppConfig {
-                port: 0,
-                host: config.app.host,
-            },
-            db: DBConfig {
-                db_name: db_name.clone(),
-                ..config.db
-            },
+        config.app.port = 0;
+        config.db.db_name = db_name.clone();
+
+        let uploads_dir = std::env::temp_dir().join(format!("uploads-{db_name}"));
+        config.storage.backend = StorageBackend::Local {
+            root: uploads_dir.clone(),
         };
 
+        configure(&mut config);
+
         let (connection, pool) = create_db(&config.db).await;
 
+        let public_url = config.app.public_url.clone();
         let app = App::build(&config).await;
         let port = app.get_port();
 
@@ -72,17 +81,17 @@ impl TestApp {
 
         TestApp {
             address: format!("http://127.0.0.1:{port}"),
-            connection: RefCell::new(connection),
+            public_url,
+            connection,
             pool,
             db_name,
             test_users,
+            uploads_dir,
         }
     }
 
-    pub async fn clean(self) {
-        let mut connection = self.connection.borrow_mut();
-
-        connection
+    pub async fn clean(mut self) {
+        self.connection
             .execute(
                 format!(
                     r#"SELECT pg_terminate_backend(pid) FROM pg_stat_activity WHERE datname = '{}';"#,
@@ -93,10 +102,12 @@ impl TestApp {
             .await
             .expect("Failed to disconnect users from database before drop");
 
-        connection
+        self.connection
             .execute(format!(r#"DROP DATABASE IF EXISTS "{}";"#, &self.db_name).as_str())
             .await
             .expect("Failed to drop database");
+
+        let _ = tokio::fs::remove_dir_all(&self.uploads_dir).await;
     }
 
     pub async fn login(&self, body: &Value) -> Response {
@@ -124,7 +135,84 @@ impl TestApp {
             .unwrap()
     }
 
-    async fn get_jwt(&self, test_user: &TestUser) -> HeaderValue {
+    pub async fn register(&self, body: &Value) -> Response {
+        reqwest::Client::new()
+            .post(format!("{}/auth/register", &self.address))
+            .json(body)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    pub async fn make_admin(&self, test_user: &TestUser) {
+        sqlx::query!(
+            "UPDATE public.users SET is_admin = TRUE WHERE id = $1",
+            test_user.id.as_ref()
+        )
+        .execute(&self.pool)
+        .await
+        .unwrap();
+    }
+
+    pub async fn create_invite(&self, test_user: &TestUser, body: &Value) -> Response {
+        let jwt = self.get_jwt(test_user).await;
+
+        Client::new()
+            .post(format!("{}/admin/invites", &self.address))
+            .json(body)
+            .header(AUTHORIZATION, jwt)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    /// Fetches a server-rendered page without following redirects.
+    pub async fn get_page(&self, path: &str, session: Option<&str>) -> Response {
+        let mut request = page_client().get(format!("{}{}", &self.address, path));
+
+        if let Some(session) = session {
+            request = request.header(COOKIE, session);
+        }
+
+        request.send().await.unwrap()
+    }
+
+    /// Submits a form the way the app's own pages do.
+    pub async fn post_form(&self, path: &str, form: &[(&str, &str)]) -> Response {
+        self.post_form_from(path, form, Some(&self.public_url))
+            .await
+    }
+
+    /// Submits a form with the given `Origin` header, or none at all.
+    pub async fn post_form_from(
+        &self,
+        path: &str,
+        form: &[(&str, &str)],
+        origin: Option<&str>,
+    ) -> Response {
+        let mut request = page_client()
+            .post(format!("{}{}", &self.address, path))
+            .form(form);
+
+        if let Some(origin) = origin {
+            request = request.header(ORIGIN, origin);
+        }
+
+        request.send().await.unwrap()
+    }
+
+    /// Signs in through the login form and returns the session as a `Cookie` header value.
+    pub async fn page_session(&self, test_user: &TestUser) -> String {
+        let form = [
+            ("username", test_user.username.0.as_str()),
+            ("password", test_user.password.0.as_str()),
+        ];
+        let response = self.post_form("/login", &form).await;
+
+        session_cookie(&response).unwrap()
+    }
+
+    pub async fn get_jwt(&self, test_user: &TestUser) -> HeaderValue {
         let body = json!({
             "username": &test_user.username,
             "password": &test_user.password
@@ -151,6 +239,22 @@ impl TestApp {
             .unwrap()
     }
 
+    /// Creates a published article and returns its id.
+    pub async fn create_article_id(&self, title: &str, tags: &[&str], user: &TestUser) -> String {
+        let payload = create_article::Payload {
+            title: title.to_string(),
+            text: "A long new article".to_string(),
+            tags: (!tags.is_empty()).then(|| tags.iter().map(|tag| tag.to_string()).collect()),
+            ..Default::default()
+        };
+
+        let response = self.create_article(&payload, user).await;
+        assert_eq!(response.status(), StatusCode::CREATED);
+
+        let body: Value = response.json().await.unwrap();
+        body["data"]["id"].as_str().unwrap().to_string()
+    }
+
     pub async fn get_subscribed(
         &self,
         user: &TestUser,
@@ -166,4 +270,131 @@ impl TestApp {
             .await
             .unwrap()
     }
+
+    pub async fn get_article(&self, id: &str) -> Response {
+        Client::new()
+            .get(format!("{}/articles/{}", &self.address, id))
+            .send()
+            .await
+            .unwrap()
+    }
+
+    pub async fn update_article(&self, id: &str, body: &Value, user: &TestUser) -> Response {
+        let jwt = self.get_jwt(user).await;
+
+        Client::new()
+            .patch(format!("{}/articles/{}", &self.address, id))
+            .json(body)
+            .header(AUTHORIZATION, jwt)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    pub async fn delete_article(&self, id: &str, user: &TestUser) -> Response {
+        let jwt = self.get_jwt(user).await;
+
+        Client::new()
+            .delete(format!("{}/articles/{}", &self.address, id))
+            .header(AUTHORIZATION, jwt)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    pub async fn create_comment(
+        &self,
+        article_id: &str,
+        body: &Value,
+        user: &TestUser,
+    ) -> Response {
+        let jwt = self.get_jwt(user).await;
+
+        Client::new()
+            .post(format!(
+                "{}/articles/{}/comments",
+                &self.address, article_id
+            ))
+            .json(body)
+            .header(AUTHORIZATION, jwt)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    pub async fn react(&self, article_id: &str, kind: &str, user: &TestUser) -> Response {
+        let jwt = self.get_jwt(user).await;
+
+        Client::new()
+            .put(format!(
+                "{}/articles/{}/reactions/{}",
+                &self.address, article_id, kind
+            ))
+            .header(AUTHORIZATION, jwt)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    pub async fn bookmark(
+        &self,
+        article_id: &str,
+        collection_id: Option<Uuid>,
+        user: &TestUser,
+    ) -> Response {
+        let jwt = self.get_jwt(user).await;
+
+        Client::new()
+            .put(format!(
+                "{}/articles/{}/bookmark",
+                &self.address, article_id
+            ))
+            .query(&[("collection_id", collection_id)])
+            .header(AUTHORIZATION, jwt)
+            .send()
+            .await
+            .unwrap()
+    }
+
+    pub async fn upload(&self, path: &str, file: Vec<u8>, user: &TestUser) -> Response {
+        let jwt = self.get_jwt(user).await;
+        // The declared type is deliberately wrong, the server must sniff the contents
+        let part = Part::bytes(file)
+            .file_name("upload.bin")
+            .mime_str("text/plain")
+            .unwrap();
+
+        Client::new()
+            .post(format!("{}{}", &self.address, path))
+            .multipart(Form::new().part("file", part))
+            .header(AUTHORIZATION, jwt)
+            .send()
+            .await
+            .unwrap()
+    }
+}
+
+/// PNG image of the given size filled with a single color.
+pub fn png_image(width: u32, height: u32) -> Vec<u8> {
+    let image = RgbImage::from_pixel(width, height, Rgb([200, 80, 40]));
+    let mut data = Cursor::new(vec![]);
+    image.write_to(&mut data, ImageFormat::Png).unwrap();
+
+    data.into_inner()
+}
+
+fn page_client() -> Client {
+    Client::builder().redirect(Policy::none()).build().unwrap()
+}
+
+/// The `name=value` part of the session cookie a response sets.
+pub fn session_cookie(response: &Response) -> Option<String> {
+    response
+        .headers()
+        .get_all(SET_COOKIE)
+        .iter()
+        .filter_map(|header| header.to_str().ok())
+        .find(|header| header.starts_with("session="))
+        .and_then(|header| header.split(';').next())
+        .map(str::to_string)
 }
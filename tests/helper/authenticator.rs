@@ -0,0 +1,137 @@
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use ciborium::Value as Cbor;
+use ring::{
+    rand::SystemRandom,
+    signature::{EcdsaKeyPair, KeyPair, ECDSA_P256_SHA256_ASN1_SIGNING},
+};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+const FLAGS_USER_PRESENT_AND_VERIFIED: u8 = 0x01 | 0x04;
+const FLAG_ATTESTED_CREDENTIAL: u8 = 0x40;
+
+/// Software ES256 authenticator producing `none` attestations and assertions
+/// in the JSON shape browsers return from `PublicKeyCredential.toJSON()`.
+pub struct SoftAuthenticator {
+    key_pair: EcdsaKeyPair,
+    rng: SystemRandom,
+    credential_id: Vec<u8>,
+    user_handle: Option<String>,
+    pub origin: String,
+    pub sign_count: u32,
+}
+
+impl SoftAuthenticator {
+    pub fn new(origin: &str) -> SoftAuthenticator {
+        let rng = SystemRandom::new();
+        let pkcs8 = EcdsaKeyPair::generate_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, &rng).unwrap();
+        let key_pair =
+            EcdsaKeyPair::from_pkcs8(&ECDSA_P256_SHA256_ASN1_SIGNING, pkcs8.as_ref(), &rng)
+                .unwrap();
+
+        SoftAuthenticator {
+            key_pair,
+            rng,
+            credential_id: Uuid::new_v4().as_bytes().to_vec(),
+            user_handle: None,
+            origin: origin.to_string(),
+            sign_count: 0,
+        }
+    }
+
+    /// Answers `navigator.credentials.create({ publicKey: options })`.
+    pub fn register(&mut self, options: &Value) -> Value {
+        self.user_handle = options["user"]["id"].as_str().map(str::to_string);
+        self.sign_count += 1;
+
+        let client_data = self.client_data("webauthn.create", options);
+        let public_key = self.key_pair.public_key().as_ref();
+
+        let cose_key = Cbor::Map(vec![
+            (Cbor::from(1), Cbor::from(2)),
+            (Cbor::from(3), Cbor::from(-7)),
+            (Cbor::from(-1), Cbor::from(1)),
+            (Cbor::from(-2), Cbor::Bytes(public_key[1..33].to_vec())),
+            (Cbor::from(-3), Cbor::Bytes(public_key[33..65].to_vec())),
+        ]);
+
+        let mut auth_data = self.auth_data(
+            options["rp"]["id"].as_str().unwrap(),
+            FLAGS_USER_PRESENT_AND_VERIFIED | FLAG_ATTESTED_CREDENTIAL,
+        );
+
+        auth_data.extend_from_slice(&[0u8; 16]);
+        auth_data.extend_from_slice(&(self.credential_id.len() as u16).to_be_bytes());
+        auth_data.extend_from_slice(&self.credential_id);
+        ciborium::into_writer(&cose_key, &mut auth_data).unwrap();
+
+        let attestation_object = Cbor::Map(vec![
+            (Cbor::from("fmt"), Cbor::from("none")),
+            (Cbor::from("attStmt"), Cbor::Map(vec![])),
+            (Cbor::from("authData"), Cbor::Bytes(auth_data)),
+        ]);
+
+        let mut attestation_bytes = vec![];
+        ciborium::into_writer(&attestation_object, &mut attestation_bytes).unwrap();
+
+        json!({
+            "id": encode(&self.credential_id),
+            "rawId": encode(&self.credential_id),
+            "type": "public-key",
+            "response": {
+                "clientDataJSON": encode(client_data.as_bytes()),
+                "attestationObject": encode(&attestation_bytes),
+                "transports": ["internal"]
+            }
+        })
+    }
+
+    /// Answers `navigator.credentials.get({ publicKey: options })`.
+    pub fn authenticate(&mut self, options: &Value) -> Value {
+        self.sign_count += 1;
+
+        let client_data = self.client_data("webauthn.get", options);
+        let auth_data = self.auth_data(
+            options["rpId"].as_str().unwrap(),
+            FLAGS_USER_PRESENT_AND_VERIFIED,
+        );
+
+        let mut message = auth_data.clone();
+        message.extend_from_slice(&Sha256::digest(client_data.as_bytes()));
+        let signature = self.key_pair.sign(&self.rng, &message).unwrap();
+
+        json!({
+            "id": encode(&self.credential_id),
+            "rawId": encode(&self.credential_id),
+            "type": "public-key",
+            "response": {
+                "clientDataJSON": encode(client_data.as_bytes()),
+                "authenticatorData": encode(&auth_data),
+                "signature": encode(signature.as_ref()),
+                "userHandle": &self.user_handle
+            }
+        })
+    }
+
+    fn client_data(&self, kind: &str, options: &Value) -> String {
+        json!({
+            "type": kind,
+            "challenge": options["challenge"],
+            "origin": &self.origin,
+            "crossOrigin": false
+        })
+        .to_string()
+    }
+
+    fn auth_data(&self, rp_id: &str, flags: u8) -> Vec<u8> {
+        let mut auth_data = Sha256::digest(rp_id.as_bytes()).to_vec();
+        auth_data.push(flags);
+        auth_data.extend_from_slice(&self.sign_count.to_be_bytes());
+        auth_data
+    }
+}
+
+fn encode(bytes: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(bytes)
+}
@@ -0,0 +1,155 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use axum::{
+    extract::State,
+    http::StatusCode,
+    routing::{get, post},
+    Form, Json, Router,
+};
+use jsonwebtoken::{Algorithm, EncodingKey, Header};
+use lib::{configuration::OidcProviderConfig, utils::oidc::code_challenge};
+use reqwest::Url;
+use serde_json::{json, Value};
+use tokio::net::TcpListener;
+use uuid::Uuid;
+
+pub const CLIENT_ID: &str = "test-client";
+const KEY_ID: &str = "test-key";
+const PRIVATE_KEY: &[u8] = include_bytes!("idp_key.pem");
+const MODULUS: &str = "u64YPUPbSF46o50wBsPBMjQnk-adQCu2vEmc1QvkILcPJSoNQykI981SmwD0_XjSphQOraLve51c6TghiAhAdVmninQS6oj378v2a8k0s-PUKcBR4M2OUJiebxhTCYeCNHzJzSZSs7zVGsrjkOhYWAmrMsQZefxscBLQewKcAn3J0Xz9UT8Dcfb_wSLSguIyOmbt6Z5Dury3qDhMuGq7tJMyVPCYCUegJDyYY76QULHcIat91h1PKFvHxjcc7-PYRvGPJ0URAOnZVhJ5C7b1QI37VOGUSe9o-aFG7ka7djiRyR3ktuFaZfiBG2F1QzNEOTL87iNYAna0CwvFeBnoOw";
+
+struct IssuedCode {
+    claims: Value,
+    code_challenge: String,
+}
+
+type Codes = Arc<Mutex<HashMap<String, IssuedCode>>>;
+
+/// Minimal OpenID provider serving a token endpoint and JWKS.
+pub struct MockIdp {
+    pub address: String,
+    codes: Codes,
+}
+
+impl MockIdp {
+    pub async fn spawn() -> MockIdp {
+        let codes: Codes = Arc::default();
+
+        let router = Router::new()
+            .route("/token", post(token))
+            .route("/jwks", get(jwks))
+            .with_state(codes.clone());
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        MockIdp {
+            address: format!("http://127.0.0.1:{port}"),
+            codes,
+        }
+    }
+
+    pub fn provider_config(&self) -> OidcProviderConfig {
+        OidcProviderConfig {
+            issuer: self.address.clone(),
+            authorization_endpoint: format!("{}/authorize", &self.address),
+            token_endpoint: format!("{}/token", &self.address),
+            jwks_uri: format!("{}/jwks", &self.address),
+            client_id: CLIENT_ID.to_string(),
+            client_secret: Some("test-secret".to_string()),
+            redirect_uri: "http://localhost/callback".to_string(),
+            scopes: vec!["openid".to_string(), "email".to_string()],
+        }
+    }
+
+    /// Simulates the user approving the authorization request. The nonce from the
+    /// request is added to the claims unless they already carry one.
+    pub fn issue_code(&self, authorize_url: &Url, mut claims: Value) -> String {
+        let params: HashMap<String, String> = authorize_url.query_pairs().into_owned().collect();
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+
+        let defaults = json!({
+            "iss": &self.address,
+            "aud": CLIENT_ID,
+            "iat": now,
+            "exp": now + 600,
+            "nonce": &params["nonce"],
+        });
+
+        for (key, value) in defaults.as_object().unwrap() {
+            if claims.get(key).is_none() {
+                claims[key] = value.clone();
+            }
+        }
+
+        let code = Uuid::new_v4().to_string();
+
+        self.codes.lock().unwrap().insert(
+            code.clone(),
+            IssuedCode {
+                claims,
+                code_challenge: params["code_challenge"].clone(),
+            },
+        );
+
+        code
+    }
+}
+
+async fn token(
+    State(codes): State<Codes>,
+    Form(form): Form<HashMap<String, String>>,
+) -> Result<Json<Value>, StatusCode> {
+    let issued = codes
+        .lock()
+        .unwrap()
+        .remove(&form["code"])
+        .ok_or(StatusCode::BAD_REQUEST)?;
+
+    if form["grant_type"] != "authorization_code"
+        || form["client_id"] != CLIENT_ID
+        || code_challenge(&form["code_verifier"]) != issued.code_challenge
+    {
+        return Err(StatusCode::BAD_REQUEST);
+    }
+
+    let header = Header {
+        kid: Some(KEY_ID.to_string()),
+        ..Header::new(Algorithm::RS256)
+    };
+
+    let id_token = jsonwebtoken::encode(
+        &header,
+        &issued.claims,
+        &EncodingKey::from_rsa_pem(PRIVATE_KEY).unwrap(),
+    )
+    .unwrap();
+
+    Ok(Json(json!({
+        "access_token": "mock-access-token",
+        "token_type": "Bearer",
+        "id_token": id_token
+    })))
+}
+
+async fn jwks() -> Json<Value> {
+    Json(json!({
+        "keys": [{
+            "kty": "RSA",
+            "kid": KEY_ID,
+            "use": "sig",
+            "alg": "RS256",
+            "n": MODULUS,
+            "e": "AQAB"
+        }]
+    }))
+}
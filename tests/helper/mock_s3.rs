@@ -0,0 +1,114 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex},
+};
+
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::{HeaderMap, Method, StatusCode},
+    response::{IntoResponse, Response},
+    routing::any,
+    Router,
+};
+use lib::configuration::StorageBackend;
+use sha2::{Digest, Sha256};
+use tokio::net::TcpListener;
+
+const ACCESS_KEY: &str = "test-access-key";
+const BUCKET: &str = "test-bucket";
+
+type Objects = Arc<Mutex<HashMap<String, Bytes>>>;
+
+/// In-memory stand-in for an S3-compatible service, checking the shape of signed requests.
+pub struct MockS3 {
+    pub address: String,
+    objects: Objects,
+}
+
+impl MockS3 {
+    pub async fn spawn() -> MockS3 {
+        let objects: Objects = Arc::default();
+
+        let router = Router::new()
+            .route("/*path", any(object))
+            .with_state(objects.clone());
+
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+
+        tokio::spawn(async move { axum::serve(listener, router).await.unwrap() });
+
+        MockS3 {
+            address: format!("http://127.0.0.1:{port}"),
+            objects,
+        }
+    }
+
+    pub fn backend(&self) -> StorageBackend {
+        StorageBackend::S3 {
+            endpoint: self.address.clone(),
+            bucket: BUCKET.to_string(),
+            region: "us-east-1".to_string(),
+            access_key: ACCESS_KEY.to_string(),
+            secret_key: "test-secret-key".to_string(),
+        }
+    }
+
+    /// Keys stored in the bucket, without the bucket prefix.
+    pub fn keys(&self) -> Vec<String> {
+        let mut keys: Vec<String> = self.objects.lock().unwrap().keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+}
+
+async fn object(
+    State(objects): State<Objects>,
+    Path(path): Path<String>,
+    method: Method,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Response {
+    let header = |name: &str| {
+        headers
+            .get(name)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default()
+    };
+
+    let payload_hash: String = Sha256::digest(&body)
+        .iter()
+        .map(|b| format!("{b:02x}"))
+        .collect();
+    let credential = format!("AWS4-HMAC-SHA256 Credential={ACCESS_KEY}/");
+
+    if !header("authorization").starts_with(&credential)
+        || header("x-amz-content-sha256") != payload_hash
+        || header("x-amz-date").is_empty()
+    {
+        return StatusCode::FORBIDDEN.into_response();
+    }
+
+    let Some(key) = path.strip_prefix(&format!("{BUCKET}/")) else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let mut objects = objects.lock().unwrap();
+
+    match method {
+        Method::PUT => {
+            objects.insert(key.to_string(), body);
+            StatusCode::OK.into_response()
+        }
+        Method::GET => match objects.get(key) {
+            Some(data) => data.clone().into_response(),
+            None => StatusCode::NOT_FOUND.into_response(),
+        },
+        Method::DELETE => {
+            objects.remove(key);
+            StatusCode::NO_CONTENT.into_response()
+        }
+        _ => StatusCode::METHOD_NOT_ALLOWED.into_response(),
+    }
+}
@@ -1,7 +1,13 @@
 mod app;
+mod authenticator;
+mod mock_idp;
+mod mock_s3;
 mod users;
 
 pub use app::*;
+pub use authenticator::*;
+pub use mock_idp::*;
+pub use mock_s3::*;
 pub use users::*;
 
 use lib::configuration::DBConfig;
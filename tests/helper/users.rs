@@ -16,7 +16,7 @@ pub async fn create_test_user(pool: &PgPool) -> TestUser {
     let user_id = Uuid::new_v4();
     let test_user = TestUser {
         id: UserID(user_id),
-        email: Email(format!("{}@mail.com", Uuid::new_v4().to_string())),
+        email: Email(format!("{}@mail.com", Uuid::new_v4())),
         username: Username(Uuid::new_v4().to_string()),
         password: Password(Uuid::new_v4().to_string()),
     };
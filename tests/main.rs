@@ -1,3 +1,10 @@
+mod admin;
 mod articles;
 mod auth;
+mod feeds;
 mod helper;
+mod pages;
+mod scim;
+mod tags;
+mod uploads;
+mod users;
@@ -0,0 +1,118 @@
+use lib::routes::articles::create_article;
+use reqwest::{header::LOCATION, StatusCode};
+use serde_json::{json, Value};
+
+use crate::helper::{TestApp, TestUser};
+
+async fn create(app: &TestApp, title: &str, tags: &[&str], user: &TestUser) -> Value {
+    let payload = create_article::Payload {
+        title: title.to_string(),
+        text: "Some **bold** text with <script>alert(1)</script>".to_string(),
+        tags: (!tags.is_empty()).then(|| tags.iter().map(|tag| tag.to_string()).collect()),
+        ..Default::default()
+    };
+
+    let response = app.create_article(&payload, user).await;
+    let body: Value = response.json().await.unwrap();
+    body["data"].clone()
+}
+
+#[tokio::test]
+async fn index_lists_and_filters_articles() {
+    let app = TestApp::spawn().await;
+    let (alice, bob) = (&app.test_users[0], &app.test_users[1]);
+    create(&app, "Rust <tips>", &["rust"], alice).await;
+    create(&app, "Cooking notes", &["food"], bob).await;
+
+    let response = app.get_page("/", None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.headers()["content-type"]
+        .to_str()
+        .unwrap()
+        .starts_with("text/html"));
+    let page = response.text().await.unwrap();
+    assert!(page.contains("Rust &lt;tips&gt;"));
+    assert!(page.contains("Cooking notes"));
+
+    let page = app
+        .get_page("/?tag=rust&author=", None)
+        .await
+        .text()
+        .await
+        .unwrap();
+    assert!(page.contains("Rust &lt;tips&gt;"));
+    assert!(!page.contains("Cooking notes"));
+
+    let path = format!("/?author={}", bob.username);
+    let page = app.get_page(&path, None).await.text().await.unwrap();
+    assert!(!page.contains("Rust &lt;tips&gt;"));
+    assert!(page.contains("Cooking notes"));
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn render_article_and_author_pages() {
+    let app = TestApp::spawn().await;
+    let alice = &app.test_users[0];
+    let article = create(&app, "Readable article", &[], alice).await;
+
+    let path = format!("/authors/{}/readable-article", alice.username);
+    let response = app.get_page(&path, None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let page = response.text().await.unwrap();
+    assert!(page.contains("<h1>Readable article</h1>"));
+    assert!(page.contains("<strong>bold</strong>"));
+    assert!(!page.contains("<script>"));
+    assert!(page.contains("<meta name=\"description\" content=\"Some bold text with"));
+
+    // Renamed articles redirect from their former slug
+    let body = json!({ "title": "Renamed article", "version": article["version"] });
+    let id = article["id"].as_str().unwrap();
+    app.update_article(id, &body, alice).await;
+
+    let response = app.get_page(&path, None).await;
+    assert_eq!(response.status(), StatusCode::PERMANENT_REDIRECT);
+    assert_eq!(
+        response.headers()[LOCATION],
+        format!("/authors/{}/renamed-article", alice.username)
+    );
+
+    let path = format!("/authors/{}", alice.username);
+    let page = app.get_page(&path, None).await.text().await.unwrap();
+    assert!(page.contains("Renamed article"));
+    assert!(page.contains("0 followers"));
+
+    let response = app.get_page("/authors/nobody_here", None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    assert!(response.text().await.unwrap().contains("User not found"));
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn drafts_are_only_shown_to_their_author() {
+    let app = TestApp::spawn().await;
+    let alice = &app.test_users[0];
+    let payload = create_article::Payload {
+        title: "Secret draft".to_string(),
+        text: "Not ready for readers yet".to_string(),
+        status: lib::domains::article::ArticleStatus::Draft,
+        ..Default::default()
+    };
+    app.create_article(&payload, alice).await;
+
+    let page = app.get_page("/", None).await.text().await.unwrap();
+    assert!(!page.contains("Secret draft"));
+
+    let path = format!("/authors/{}/secret-draft", alice.username);
+    let response = app.get_page(&path, None).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let session = app.page_session(alice).await;
+    let response = app.get_page(&path, Some(&session)).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.text().await.unwrap().contains("Secret draft"));
+
+    app.clean().await;
+}
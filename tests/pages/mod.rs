@@ -0,0 +1,2 @@
+mod articles;
+mod session;
@@ -0,0 +1,132 @@
+use reqwest::{
+    header::{LOCATION, REFERER, SET_COOKIE},
+    redirect::Policy,
+    Client, StatusCode,
+};
+
+use crate::helper::{session_cookie, TestApp};
+
+#[tokio::test]
+async fn register_through_form_starts_session() {
+    let app = TestApp::spawn().await;
+
+    let response = app.get_page("/register", None).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response
+        .text()
+        .await
+        .unwrap()
+        .contains("action=\"/register\""));
+
+    let form = [
+        ("username", "page_reader"),
+        ("email", "page_reader@mail.com"),
+        ("password", "Password_123"),
+    ];
+    let response = app.post_form("/register", &form).await;
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    assert_eq!(response.headers()[LOCATION], "/");
+
+    let cookie = response.headers()[SET_COOKIE].to_str().unwrap();
+    assert!(cookie.contains("HttpOnly"));
+    assert!(cookie.contains("SameSite=Lax"));
+
+    let session = session_cookie(&response).unwrap();
+    let response = app.get_page("/", Some(&session)).await;
+    assert!(response.text().await.unwrap().contains("Log out"));
+
+    // A taken username is reported on the form, which keeps the entered values
+    let response = app.post_form("/register", &form).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let page = response.text().await.unwrap();
+    assert!(page.contains("already exists"));
+    assert!(page.contains("value=\"page_reader@mail.com\""));
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn login_and_logout_through_forms() {
+    let app = TestApp::spawn().await;
+    let user = &app.test_users[0];
+
+    let form = [
+        ("username", user.username.0.as_str()),
+        ("password", "wrong_password"),
+    ];
+    let response = app.post_form("/login", &form).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    assert!(session_cookie(&response).is_none());
+    assert!(response
+        .text()
+        .await
+        .unwrap()
+        .contains("Invalid login or password"));
+
+    let session = app.page_session(user).await;
+
+    // Signed in readers are sent away from the forms
+    let response = app.get_page("/login", Some(&session)).await;
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+
+    let response = app.post_form("/logout", &[]).await;
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    let cookie = response.headers()[SET_COOKIE].to_str().unwrap();
+    assert!(cookie.starts_with("session=;"));
+    assert!(cookie.contains("Max-Age=0"));
+
+    // An invalid session counts as signed out
+    let response = app.get_page("/", Some("session=garbage")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    assert!(response.text().await.unwrap().contains("Log in"));
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn reject_forms_posted_from_other_sites() {
+    let app = TestApp::spawn().await;
+    let user = &app.test_users[0];
+
+    let form = [
+        ("username", user.username.0.as_str()),
+        ("password", user.password.0.as_str()),
+    ];
+
+    for origin in [Some("https://evil.example"), Some("null"), None] {
+        let response = app.post_form_from("/login", &form, origin).await;
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+        assert!(session_cookie(&response).is_none());
+    }
+
+    let form = [
+        ("username", "forged_reader"),
+        ("email", "forged_reader@mail.com"),
+        ("password", "Password_123"),
+    ];
+    let response = app
+        .post_form_from("/register", &form, Some("https://evil.example"))
+        .await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    assert!(session_cookie(&response).is_none());
+
+    // Without an origin the referer has to point at the app's own pages
+    let login = [
+        ("username", user.username.0.as_str()),
+        ("password", user.password.0.as_str()),
+    ];
+    let response = Client::builder()
+        .redirect(Policy::none())
+        .build()
+        .unwrap()
+        .post(format!("{}/login", &app.address))
+        .header(REFERER, format!("{}/login", &app.public_url))
+        .form(&login)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::SEE_OTHER);
+    assert!(session_cookie(&response).is_some());
+
+    app.clean().await;
+}
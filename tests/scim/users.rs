@@ -0,0 +1,239 @@
+use lib::routes::scim::{ListResponse, ScimUser};
+use reqwest::{
+    header::{AUTHORIZATION, CONTENT_TYPE},
+    Method, RequestBuilder, StatusCode,
+};
+use serde_json::{json, Value};
+
+use crate::helper::TestApp;
+
+const SCIM_TOKEN: &str = "test-provisioning-token";
+
+async fn spawn_app() -> TestApp {
+    TestApp::spawn_with(|config| config.scim.token = Some(SCIM_TOKEN.to_string())).await
+}
+
+fn scim_request(app: &TestApp, method: Method, path: &str) -> RequestBuilder {
+    reqwest::Client::new()
+        .request(method, format!("{}/scim/v2/Users{}", &app.address, path))
+        .header(AUTHORIZATION, format!("Bearer {SCIM_TOKEN}"))
+}
+
+fn new_user_body() -> Value {
+    json!({
+        "schemas": ["urn:ietf:params:scim:schemas:core:2.0:User"],
+        "userName": "provisioned_user",
+        "externalId": "hr-42",
+        "password": "pass12359823",
+        "emails": [{ "value": "provisioned@mail.com", "primary": true, "type": "work" }],
+        "active": true
+    })
+}
+
+async fn create_user(app: &TestApp) -> ScimUser {
+    let response = scim_request(app, Method::POST, "")
+        .json(&new_user_body())
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), StatusCode::CREATED);
+    response.json().await.unwrap()
+}
+
+#[tokio::test]
+async fn reject_requests_without_valid_token() {
+    let app = spawn_app().await;
+
+    for token in [None, Some("Bearer wrong-token")] {
+        let mut request = reqwest::Client::new().get(format!("{}/scim/v2/Users", &app.address));
+
+        if let Some(token) = token {
+            request = request.header(AUTHORIZATION, token);
+        }
+
+        let response = request.send().await.unwrap();
+        assert_eq!(response.status().as_u16(), StatusCode::UNAUTHORIZED);
+
+        let json: Value = response.json().await.unwrap();
+        assert_eq!(json["status"], "401");
+    }
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn create_get_and_filter_user() {
+    let app = spawn_app().await;
+    let created = create_user(&app).await;
+
+    assert_eq!(created.user_name.as_ref(), "provisioned_user");
+    assert_eq!(created.external_id.as_deref(), Some("hr-42"));
+    assert!(created.active);
+
+    let response = scim_request(&app, Method::GET, &format!("/{}", created.id))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), StatusCode::OK);
+    let fetched: ScimUser = response.json().await.unwrap();
+    assert_eq!(fetched.emails[0].value.as_ref(), "provisioned@mail.com");
+
+    let response = scim_request(&app, Method::GET, "")
+        .query(&[("filter", r#"userName eq "provisioned_user""#)])
+        .send()
+        .await
+        .unwrap();
+
+    let list: ListResponse<ScimUser> = response.json().await.unwrap();
+    assert_eq!(list.total_results, 1);
+    assert_eq!(list.resources[0].id, created.id);
+
+    let response = scim_request(&app, Method::GET, "")
+        .query(&[("filter", r#"displayName co "x""#)])
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), StatusCode::BAD_REQUEST);
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["scimType"], "invalidFilter");
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn return_409_on_duplicated_user() {
+    let app = spawn_app().await;
+    create_user(&app).await;
+
+    let response = scim_request(&app, Method::POST, "")
+        .json(&new_user_body())
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), StatusCode::CONFLICT);
+
+    let json: Value = response.json().await.unwrap();
+    assert_eq!(json["scimType"], "uniqueness");
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn return_scim_errors_on_unparsable_requests() {
+    let app = spawn_app().await;
+
+    let requests = [
+        scim_request(&app, Method::POST, "")
+            .header(CONTENT_TYPE, "application/scim+json")
+            .body("{ not json"),
+        scim_request(&app, Method::POST, "").body(new_user_body().to_string()),
+        scim_request(&app, Method::POST, "").json(&json!({ "userName": 42 })),
+        scim_request(&app, Method::GET, "?startIndex=first"),
+    ];
+
+    for request in requests {
+        let response = request.send().await.unwrap();
+        let status = response.status().as_u16();
+        assert!(status == 400 || status == 415, "{status}");
+
+        let json: Value = response.json().await.unwrap();
+        assert_eq!(
+            json["schemas"],
+            json!(["urn:ietf:params:scim:api:messages:2.0:Error"])
+        );
+        assert_eq!(json["status"], status.to_string());
+    }
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn patch_replace_add_and_remove() {
+    let app = spawn_app().await;
+    let created = create_user(&app).await;
+
+    let body = json!({
+        "schemas": ["urn:ietf:params:scim:api:messages:2.0:PatchOp"],
+        "Operations": [
+            { "op": "Replace", "path": "userName", "value": "renamed_user" },
+            { "op": "add", "value": { "emails": [{ "value": "renamed@mail.com", "primary": true }] } },
+            { "op": "remove", "path": "externalId" }
+        ]
+    });
+
+    let response = scim_request(&app, Method::PATCH, &format!("/{}", created.id))
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), StatusCode::OK);
+
+    let patched: ScimUser = response.json().await.unwrap();
+    assert_eq!(patched.user_name.as_ref(), "renamed_user");
+    assert_eq!(patched.emails[0].value.as_ref(), "renamed@mail.com");
+    assert_eq!(patched.external_id, None);
+
+    let body = json!({
+        "Operations": [{ "op": "remove", "path": "userName" }]
+    });
+
+    let response = scim_request(&app, Method::PATCH, &format!("/{}", created.id))
+        .json(&body)
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), StatusCode::BAD_REQUEST);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn delete_deactivates_user_and_blocks_login() {
+    let app = spawn_app().await;
+    let created = create_user(&app).await;
+
+    let credentials = json!({
+        "username": "provisioned_user",
+        "password": "pass12359823"
+    });
+
+    let response = app.login(&credentials).await;
+    assert_eq!(response.status().as_u16(), 200);
+    let jwt = response.headers()[AUTHORIZATION].clone();
+
+    let me = || {
+        reqwest::Client::new()
+            .get(format!("{}/auth/me", &app.address))
+            .header(AUTHORIZATION, jwt.clone())
+            .send()
+    };
+    assert_eq!(me().await.unwrap().status(), StatusCode::OK);
+
+    let response = scim_request(&app, Method::DELETE, &format!("/{}", created.id))
+        .send()
+        .await
+        .unwrap();
+
+    assert_eq!(response.status().as_u16(), StatusCode::NO_CONTENT);
+
+    let response = scim_request(&app, Method::GET, &format!("/{}", created.id))
+        .send()
+        .await
+        .unwrap();
+
+    let fetched: ScimUser = response.json().await.unwrap();
+    assert!(!fetched.active);
+
+    assert_eq!(app.login(&credentials).await.status().as_u16(), 400);
+
+    // Tokens issued before the deactivation stop working too
+    assert_eq!(me().await.unwrap().status(), StatusCode::UNAUTHORIZED);
+
+    app.clean().await;
+}
@@ -0,0 +1,131 @@
+use lib::{
+    domains::article::ArticleStatus,
+    routes::{
+        articles::{create_article, Article},
+        tags::TagCount,
+    },
+    utils::response::DataResponse,
+};
+use reqwest::{Client, StatusCode};
+
+use crate::helper::{TestApp, TestUser};
+
+async fn create(
+    app: &TestApp,
+    tags: &[&str],
+    status: ArticleStatus,
+    user: &TestUser,
+) -> DataResponse<Article> {
+    let payload = create_article::Payload {
+        title: "Tagged article".to_string(),
+        text: "Article with tags".to_string(),
+        tags: Some(tags.iter().map(|tag| tag.to_string()).collect()),
+        status,
+        ..Default::default()
+    };
+
+    let response = app.create_article(&payload, user).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+
+    response.json().await.unwrap()
+}
+
+async fn get_tags(app: &TestApp, path: &str) -> Vec<TagCount> {
+    let response = Client::new()
+        .get(format!("{}{}", &app.address, path))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let body: DataResponse<Vec<TagCount>> = response.json().await.unwrap();
+    body.data
+}
+
+fn tag(name: &str, article_count: i64) -> TagCount {
+    TagCount {
+        name: name.to_string(),
+        article_count,
+    }
+}
+
+#[tokio::test]
+async fn tags_are_normalized_on_write() {
+    let app = TestApp::spawn().await;
+    let user = &app.test_users[0];
+
+    let article = create(
+        &app,
+        &[" Rust ", "web  Dev", "rust", "  "],
+        ArticleStatus::Published,
+        user,
+    )
+    .await;
+    assert_eq!(article.data.tags, ["rust", "web dev"]);
+
+    let id = article.data.id.to_string();
+    let response = app
+        .update_article(
+            &id,
+            &serde_json::json!({ "tags": ["AXUM", "Rust"], "version": 1 }),
+            user,
+        )
+        .await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let tags = get_tags(&app, "/tags").await;
+    assert_eq!(tags, [tag("axum", 1), tag("rust", 1)]);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn list_tags_counts_published_articles() {
+    let app = TestApp::spawn().await;
+    let user = &app.test_users[0];
+    create(&app, &["rust", "web"], ArticleStatus::Published, user).await;
+    create(&app, &["Rust"], ArticleStatus::Published, user).await;
+    create(&app, &["rust", "drafts"], ArticleStatus::Draft, user).await;
+
+    let tags = get_tags(&app, "/tags").await;
+    assert_eq!(tags, [tag("rust", 2), tag("web", 1)]);
+
+    let tags = get_tags(&app, "/tags?limit=1&offset=1").await;
+    assert_eq!(tags, [tag("web", 1)]);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn autocomplete_tags_by_prefix() {
+    let app = TestApp::spawn().await;
+    let user = &app.test_users[0];
+    create(&app, &["rust", "rustls"], ArticleStatus::Published, user).await;
+    create(
+        &app,
+        &["rust", "ruby", "go"],
+        ArticleStatus::Published,
+        user,
+    )
+    .await;
+    create(&app, &["100%_done"], ArticleStatus::Published, user).await;
+
+    let tags = get_tags(&app, "/tags/autocomplete?prefix=RUS").await;
+    assert_eq!(tags, [tag("rust", 2), tag("rustls", 1)]);
+
+    let tags = get_tags(&app, "/tags/autocomplete?prefix=r&limit=2").await;
+    assert_eq!(tags, [tag("rust", 2), tag("ruby", 1)]);
+
+    // LIKE wildcards in the prefix match literally
+    let tags = get_tags(&app, "/tags/autocomplete?prefix=%25").await;
+    assert!(tags.is_empty());
+
+    let response = Client::new()
+        .get(format!("{}/tags/autocomplete?prefix=", &app.address))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    app.clean().await;
+}
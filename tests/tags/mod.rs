@@ -0,0 +1 @@
+mod list_tags;
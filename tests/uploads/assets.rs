@@ -0,0 +1,55 @@
+use lib::{routes::uploads::Upload, utils::response::DataResponse};
+use reqwest::{header::AUTHORIZATION, Client, Response, StatusCode};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::helper::{png_image, TestApp, TestUser};
+
+async fn upload(app: &TestApp, user: &TestUser) -> Uuid {
+    let response = app.upload("/uploads", png_image(32, 32), user).await;
+    let body: DataResponse<Upload> = response.json().await.unwrap();
+    body.data.id
+}
+
+async fn attach(app: &TestApp, article_id: &str, upload_id: Uuid, user: &TestUser) -> Response {
+    Client::new()
+        .post(format!("{}/articles/{}/assets", &app.address, article_id))
+        .json(&json!({ "upload_id": upload_id }))
+        .header(AUTHORIZATION, app.get_jwt(user).await)
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn attach_uploaded_images_to_article() {
+    let app = TestApp::spawn().await;
+    let (author, other) = (&app.test_users[0], &app.test_users[1]);
+
+    let article_id = app
+        .create_article_id("Illustrated article", &[], author)
+        .await;
+
+    let own = upload(&app, author).await;
+    let foreign = upload(&app, other).await;
+
+    let response = attach(&app, &article_id, own, author).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = attach(&app, &article_id, foreign, author).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    let response = attach(&app, &article_id, foreign, other).await;
+    assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+    let response = Client::new()
+        .get(format!("{}/articles/{}/assets", &app.address, article_id))
+        .send()
+        .await
+        .unwrap();
+    let body: DataResponse<Vec<Upload>> = response.json().await.unwrap();
+    let ids: Vec<Uuid> = body.data.iter().map(|upload| upload.id).collect();
+    assert_eq!(ids, vec![own]);
+
+    app.clean().await;
+}
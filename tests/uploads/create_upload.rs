@@ -0,0 +1,171 @@
+use lib::{
+    routes::{uploads::Upload, users::profile::Profile},
+    utils::response::DataResponse,
+};
+use reqwest::{
+    header::{AUTHORIZATION, CACHE_CONTROL, CONTENT_TYPE, ETAG, IF_NONE_MATCH},
+    Client, StatusCode,
+};
+use serde_json::json;
+use sqlx::Executor;
+
+use crate::helper::{png_image, MockS3, TestApp};
+
+#[tokio::test]
+async fn upload_image_and_serve_it_with_cache_headers() {
+    let app = TestApp::spawn().await;
+
+    let response = app
+        .upload("/uploads", png_image(800, 600), &app.test_users[0])
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let upload: DataResponse<Upload> = response.json().await.unwrap();
+    assert_eq!(upload.data.content_type, "image/png");
+    assert_eq!((upload.data.width, upload.data.height), (800, 600));
+
+    let response = Client::new()
+        .get(format!("{}{}", &app.address, upload.data.url))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    assert_eq!(response.headers()[CONTENT_TYPE], "image/png");
+    assert_eq!(response.headers()["x-content-type-options"], "nosniff");
+    let cache_control = response.headers()[CACHE_CONTROL].to_str().unwrap();
+    assert!(cache_control.contains("max-age="), "{cache_control}");
+    let etag = response.headers()[ETAG].clone();
+    assert_eq!(
+        response.bytes().await.unwrap().len() as i64,
+        upload.data.size
+    );
+
+    let response = Client::new()
+        .get(format!("{}{}", &app.address, upload.data.url))
+        .header(IF_NONE_MATCH, etag)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_MODIFIED);
+
+    let response = Client::new()
+        .get(format!("{}{}", &app.address, upload.data.thumbnail_url))
+        .send()
+        .await
+        .unwrap();
+    let thumbnail = image::load_from_memory(&response.bytes().await.unwrap()).unwrap();
+    assert_eq!((thumbnail.width(), thumbnail.height()), (320, 240));
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn reject_files_that_are_not_acceptable_images() {
+    let app = TestApp::spawn_with(|config| {
+        config.storage.max_upload_bytes = 4096;
+        config.storage.max_image_dimension = 100;
+    })
+    .await;
+    let user = &app.test_users[0];
+
+    let response = app.upload("/uploads", b"plain text".to_vec(), user).await;
+    assert_eq!(response.status(), StatusCode::UNSUPPORTED_MEDIA_TYPE);
+
+    let response = app.upload("/uploads", png_image(200, 50), user).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+    let mut too_large = png_image(10, 10);
+    too_large.resize(8192, 0);
+    let response = app.upload("/uploads", too_large, user).await;
+    assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    let response = Client::new()
+        .post(format!("{}/uploads", &app.address))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn store_uploads_in_s3_compatible_backend() {
+    let s3 = MockS3::spawn().await;
+    let app = TestApp::spawn_with(|config| config.storage.backend = s3.backend()).await;
+
+    let image = png_image(64, 64);
+    let response = app
+        .upload("/uploads", image.clone(), &app.test_users[0])
+        .await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let upload: DataResponse<Upload> = response.json().await.unwrap();
+
+    let id = upload.data.id;
+    assert_eq!(
+        s3.keys(),
+        vec![
+            format!("uploads/{id}/original"),
+            format!("uploads/{id}/thumbnail")
+        ]
+    );
+
+    let response = Client::new()
+        .get(format!("{}{}", &app.address, upload.data.url))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.bytes().await.unwrap().to_vec(), image);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn set_avatar_from_upload() {
+    let app = TestApp::spawn().await;
+    let user = &app.test_users[0];
+
+    let response = app.upload("/me/avatar", png_image(400, 400), user).await;
+    assert_eq!(response.status(), StatusCode::CREATED);
+    let upload: DataResponse<Upload> = response.json().await.unwrap();
+
+    let response = Client::new()
+        .get(format!("{}/users/{}", &app.address, user.username))
+        .send()
+        .await
+        .unwrap();
+    let profile: DataResponse<Profile> = response.json().await.unwrap();
+    let avatar_url = format!("http://localhost:3000{}", upload.data.thumbnail_url);
+    assert_eq!(
+        profile.data.avatar_url.as_deref(),
+        Some(avatar_url.as_str())
+    );
+
+    let response = Client::new()
+        .patch(format!("{}/me/profile", &app.address))
+        .json(&json!({ "avatar_url": avatar_url }))
+        .header(AUTHORIZATION, app.get_jwt(user).await)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn remove_stored_files_when_upload_is_not_recorded() {
+    let s3 = MockS3::spawn().await;
+    let app = TestApp::spawn_with(|config| config.storage.backend = s3.backend()).await;
+    app.pool
+        .execute("ALTER TABLE uploads ADD CONSTRAINT reject_uploads CHECK (false)")
+        .await
+        .unwrap();
+
+    let response = app
+        .upload("/uploads", png_image(64, 64), &app.test_users[0])
+        .await;
+    assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    assert!(s3.keys().is_empty(), "{:?}", s3.keys());
+
+    app.clean().await;
+}
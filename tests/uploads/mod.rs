@@ -0,0 +1,2 @@
+mod assets;
+mod create_upload;
@@ -0,0 +1,121 @@
+use lib::{
+    routes::users::{follows::Follow, profile::Profile},
+    types::SearchType,
+    utils::response::DataResponse,
+};
+use reqwest::{header::AUTHORIZATION, Client, StatusCode};
+use uuid::Uuid;
+
+use crate::helper::{TestApp, TestUser};
+
+async fn list(
+    app: &TestApp,
+    user: &TestUser,
+    relation: &str,
+    query: &[(&str, String)],
+) -> SearchType<Follow> {
+    let response = Client::new()
+        .get(format!("{}/users/{}/{}", &app.address, user.id, relation))
+        .query(query)
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+
+    response.json().await.unwrap()
+}
+
+fn usernames(page: &SearchType<Follow>) -> Vec<String> {
+    page.results.iter().map(|f| f.username.0.clone()).collect()
+}
+
+#[tokio::test]
+async fn list_followers_and_following() {
+    let app = TestApp::spawn().await;
+    let author = &app.test_users[0];
+
+    for follower in &app.test_users[1..4] {
+        app.subscribe(follower, &author.id).await;
+    }
+    app.subscribe(author, &app.test_users[1].id).await;
+
+    let first = list(&app, author, "followers", &[("limit", "2".to_string())]).await;
+    assert_eq!(
+        usernames(&first),
+        vec![
+            app.test_users[3].username.0.clone(),
+            app.test_users[2].username.0.clone()
+        ]
+    );
+    assert_eq!(first.total, Some(3));
+
+    let query = [
+        ("limit", "2".to_string()),
+        ("after", first.next_cursor.unwrap()),
+    ];
+    let second = list(&app, author, "followers", &query).await;
+    assert_eq!(
+        usernames(&second),
+        vec![app.test_users[1].username.0.clone()]
+    );
+
+    let following = list(&app, author, "following", &[]).await;
+    assert_eq!(
+        usernames(&following),
+        vec![app.test_users[1].username.0.clone()]
+    );
+
+    let response = Client::new()
+        .get(format!(
+            "{}/users/{}/followers",
+            &app.address,
+            Uuid::new_v4()
+        ))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn show_follow_counts_on_profile() {
+    let app = TestApp::spawn().await;
+    let author = &app.test_users[0];
+
+    for follower in &app.test_users[1..3] {
+        app.subscribe(follower, &author.id).await;
+    }
+
+    let url = format!("{}/users/{}", &app.address, author.username);
+
+    let response = Client::new().get(&url).send().await.unwrap();
+    assert_eq!(response.status(), StatusCode::OK);
+    let body: DataResponse<Profile> = response.json().await.unwrap();
+    assert_eq!(
+        (body.data.follower_count, body.data.following_count),
+        (2, 0)
+    );
+    assert_eq!(body.data.is_following, None);
+
+    for (viewer, expected) in [(&app.test_users[1], true), (&app.test_users[5], false)] {
+        let response = Client::new()
+            .get(&url)
+            .header(AUTHORIZATION, app.get_jwt(viewer).await)
+            .send()
+            .await
+            .unwrap();
+        let body: DataResponse<Profile> = response.json().await.unwrap();
+        assert_eq!(body.data.is_following, Some(expected));
+    }
+
+    let response = Client::new()
+        .get(format!("{}/users/nobody-here", &app.address))
+        .send()
+        .await
+        .unwrap();
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+    app.clean().await;
+}
@@ -0,0 +1,2 @@
+mod follows;
+mod profile;
@@ -0,0 +1,104 @@
+use lib::{
+    routes::{
+        articles::{create_article, Article},
+        users::profile::Profile,
+    },
+    utils::response::DataResponse,
+};
+use reqwest::{header::AUTHORIZATION, Client, Response, StatusCode};
+use serde_json::{json, Value};
+
+use crate::helper::{TestApp, TestUser};
+
+async fn update_profile(app: &TestApp, user: &TestUser, body: &Value) -> Response {
+    Client::new()
+        .patch(format!("{}/me/profile", &app.address))
+        .json(body)
+        .header(AUTHORIZATION, app.get_jwt(user).await)
+        .send()
+        .await
+        .unwrap()
+}
+
+#[tokio::test]
+async fn update_and_clear_profile_fields() {
+    let app = TestApp::spawn().await;
+    let user = &app.test_users[0];
+
+    let body = json!({
+        "display_name": "Ada Lovelace",
+        "bio": "Writes about engines",
+        "website": "https://example.com/ada",
+    });
+    let response = update_profile(&app, user, &body).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    // Missing fields are kept, null clears them
+    let response = update_profile(&app, user, &json!({ "bio": null })).await;
+    let body: DataResponse<Profile> = response.json().await.unwrap();
+    assert_eq!(body.data.display_name.as_deref(), Some("Ada Lovelace"));
+    assert_eq!(
+        body.data.website.as_deref(),
+        Some("https://example.com/ada")
+    );
+    assert_eq!(body.data.bio, None);
+
+    let response = Client::new()
+        .get(format!("{}/users/{}", &app.address, user.username))
+        .send()
+        .await
+        .unwrap();
+    let body: DataResponse<Profile> = response.json().await.unwrap();
+    assert_eq!(body.data.display_name.as_deref(), Some("Ada Lovelace"));
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn reject_invalid_profile_fields() {
+    let app = TestApp::spawn().await;
+    let user = &app.test_users[0];
+
+    for body in [
+        json!({ "display_name": "" }),
+        json!({ "bio": "x".repeat(1001) }),
+        json!({ "website": "not a url" }),
+        json!({ "avatar_url": "javascript:alert(1)" }),
+    ] {
+        let response = update_profile(&app, user, &body).await;
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{body}");
+    }
+
+    app.clean().await;
+}
+
+#[tokio::test]
+async fn embed_profile_in_article_author() {
+    let app = TestApp::spawn().await;
+    let user = &app.test_users[0];
+
+    let body = json!({
+        "display_name": "Ada Lovelace",
+        "avatar_url": "https://example.com/ada.png",
+    });
+    update_profile(&app, user, &body).await;
+
+    let payload = create_article::Payload {
+        title: "Notes on the engine".to_string(),
+        text: "A long new article".to_string(),
+        ..Default::default()
+    };
+    let response = app.create_article(&payload, user).await;
+    let body: DataResponse<Article> = response.json().await.unwrap();
+
+    assert_eq!(
+        body.data.author.display_name.as_deref(),
+        Some("Ada Lovelace")
+    );
+    assert_eq!(
+        body.data.author.avatar_url.as_deref(),
+        Some("https://example.com/ada.png")
+    );
+
+    app.clean().await;
+}